@@ -0,0 +1,356 @@
+use std::{fmt, str::FromStr};
+
+use rand::Rng;
+
+use crate::vec::Float;
+
+/// Color with red, green and blue from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: Float,
+    pub g: Float,
+    pub b: Float,
+}
+
+/// Hue in degrees from 0 to 360, saturation and lightness from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub h: Float,
+    pub s: Float,
+    pub l: Float,
+}
+
+impl Color {
+    pub const BLACK: Color = Color {
+        r: 0.0,
+        g: 0.0,
+        b: 0.0,
+    };
+
+    pub fn to_hsl(self) -> Hsl {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let l = (max + min) / 2.0;
+        let delta = max - min;
+
+        if delta == 0.0 {
+            // Gray, the hue doesn't matter.
+            return Hsl { h: 0.0, s: 0.0, l };
+        }
+
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == self.r {
+            ((self.g - self.b) / delta).rem_euclid(6.0)
+        } else if max == self.g {
+            (self.b - self.r) / delta + 2.0
+        } else {
+            (self.r - self.g) / delta + 4.0
+        };
+
+        Hsl { h: h * 60.0, s, l }
+    }
+
+    pub fn from_hsl(hsl: Hsl) -> Color {
+        let chroma = (1.0 - (2.0 * hsl.l - 1.0).abs()) * hsl.s;
+        let h = hsl.h.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = hsl.l - chroma / 2.0;
+
+        Color {
+            r: r + m,
+            g: g + m,
+            b: b + m,
+        }
+    }
+
+    /// Mixes the two colors, `t` = 0 gives this color and `t` = 1 gives `other`.
+    pub fn lerp(self, other: Color, t: Float) -> Color {
+        Color {
+            r: self.r * (1.0 - t) + other.r * t,
+            g: self.g * (1.0 - t) + other.g * t,
+            b: self.b * (1.0 - t) + other.b * t,
+        }
+    }
+
+    /// A random color close to this one: the hue moves by up to `amount` times a full
+    /// circle either way, and the saturation and lightness by up to `amount`.
+    pub fn jitter(self, amount: Float, rng: &mut impl Rng) -> Color {
+        if amount <= 0.0 {
+            return self;
+        }
+
+        let hsl = self.to_hsl();
+        let mut random = || rng.gen_range(-amount..=amount);
+        Color::from_hsl(Hsl {
+            h: hsl.h + random() * 360.0,
+            s: (hsl.s + random()).clamp(0.0, 1.0),
+            l: (hsl.l + random()).clamp(0.0, 1.0),
+        })
+    }
+}
+
+/// Formats as `#rrggbb`, like svg expects.
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let byte = |value: Float| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        write!(
+            f,
+            "#{:02x}{:02x}{:02x}",
+            byte(self.r),
+            byte(self.g),
+            byte(self.b)
+        )
+    }
+}
+
+/// Parses `#rrggbb`, `#rgb`, `rgb(r, g, b)` with values from 0 to 255 or percentages, or
+/// one of the named svg colors like `red`, for use in command line arguments and svg files.
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase();
+        if let Some(&(_, rgb)) = NAMED_COLORS.iter().find(|(named, _)| *named == name) {
+            let [_, r, g, b] = rgb.to_be_bytes();
+            return Ok(Color {
+                r: r as Float / 255.0,
+                g: g as Float / 255.0,
+                b: b as Float / 255.0,
+            });
+        }
+
+        if let Some(values) = name.strip_prefix("rgb(").and_then(|v| v.strip_suffix(')')) {
+            let channel = |value: &str| match value.strip_suffix('%') {
+                Some(percentage) => percentage.trim().parse::<Float>().ok().map(|p| p / 100.0),
+                None => value.parse::<Float>().ok().map(|v| v / 255.0),
+            };
+            let channels = values
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|value| !value.is_empty())
+                .map(|value| channel(value).map(|c| c.clamp(0.0, 1.0)))
+                .collect::<Option<Vec<_>>>();
+            return match channels.as_deref() {
+                Some(&[r, g, b]) => Ok(Color { r, g, b }),
+                _ => Err(format!(
+                    "Expected a color like `rgb(255, 128, 0)`, got `{s}`"
+                )),
+            };
+        }
+
+        let invalid = || format!("Expected a color like `#ff8000` or `red`, got `{s}`");
+        let hex = name.strip_prefix('#').ok_or_else(invalid)?;
+        let digits = hex
+            .chars()
+            .map(|c| c.to_digit(16).map(|d| d as Float))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+
+        let [r, g, b] = match digits[..] {
+            [r, g, b] => [r, g, b].map(|d| d * 17.0 / 255.0),
+            [r1, r2, g1, g2, b1, b2] => {
+                [(r1, r2), (g1, g2), (b1, b2)].map(|(high, low)| (high * 16.0 + low) / 255.0)
+            }
+            _ => return Err(invalid()),
+        };
+
+        Ok(Color { r, g, b })
+    }
+}
+
+/// The named colors of svg and css, as `0xrrggbb`.
+const NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn jitter_stays_close_in_hue() {
+        let base: Color = "#3080c0".parse().unwrap();
+        assert_eq!(base.to_string(), "#3080c0");
+        assert_eq!("rgb(48, 128, 192)".parse(), Ok(base));
+        assert_eq!("rgb(100%,0%,0%)".parse(), "Red".parse::<Color>());
+        assert_eq!(
+            " cornflowerblue ".parse::<Color>().unwrap().to_string(),
+            "#6495ed"
+        );
+        assert!("rgb(1, 2)".parse::<Color>().is_err());
+        assert!("blurple".parse::<Color>().is_err());
+        assert_eq!(Color::from_hsl(base.to_hsl()).to_string(), "#3080c0");
+
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(base.jitter(0.0, &mut rng), base);
+
+        let amount = 0.05;
+        let mut any_different = false;
+        for _ in 0..100 {
+            let jittered = base.jitter(amount, &mut rng);
+            any_different |= jittered.to_string() != base.to_string();
+
+            let difference =
+                (jittered.to_hsl().h - base.to_hsl().h + 180.0).rem_euclid(360.0) - 180.0;
+            assert!(difference.abs() <= amount * 360.0 + 1e-3);
+        }
+        assert!(any_different);
+    }
+}
@@ -0,0 +1,300 @@
+use color_eyre::{
+    eyre::{bail, eyre},
+    Result,
+};
+
+use crate::vec::{consts, Float};
+
+/// A parsed math expression in the variables `x` and `y`.
+///
+/// Supports numbers, `+ - * / ^`, parentheses, the constants `pi` and `e`,
+/// and the functions `sin cos tan sqrt abs exp ln`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Number(Float),
+    X,
+    Y,
+    Negate(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+    Function(Function, Box<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Function {
+    Sin,
+    Cos,
+    Tan,
+    Sqrt,
+    Abs,
+    Exp,
+    Ln,
+}
+
+impl Expression {
+    pub fn parse(input: &str) -> Result<Expression> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+        };
+
+        let expression = parser.sum()?;
+        if let Some(token) = parser.peek() {
+            bail!("Unexpected `{token:?}` in expression `{input}`");
+        }
+
+        Ok(expression)
+    }
+
+    pub fn evaluate(&self, x: Float, y: Float) -> Float {
+        match self {
+            Expression::Number(value) => *value,
+            Expression::X => x,
+            Expression::Y => y,
+            Expression::Negate(inner) => -inner.evaluate(x, y),
+            Expression::Binary(operator, left, right) => {
+                let left = left.evaluate(x, y);
+                let right = right.evaluate(x, y);
+                match operator {
+                    Operator::Add => left + right,
+                    Operator::Subtract => left - right,
+                    Operator::Multiply => left * right,
+                    Operator::Divide => left / right,
+                    Operator::Power => left.powf(right),
+                }
+            }
+            Expression::Function(function, inner) => {
+                let value = inner.evaluate(x, y);
+                match function {
+                    Function::Sin => value.sin(),
+                    Function::Cos => value.cos(),
+                    Function::Tan => value.tan(),
+                    Function::Sqrt => value.sqrt(),
+                    Function::Abs => value.abs(),
+                    Function::Exp => value.exp(),
+                    Function::Ln => value.ln(),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(Float),
+    Identifier(String),
+    Operator(char),
+    Open,
+    Close,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut number = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_ascii_digit() || c == '.' {
+                    number.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let value = number
+                .parse()
+                .map_err(|_| eyre!("Invalid number `{number}` in expression `{input}`"))?;
+            tokens.push(Token::Number(value));
+        } else if c.is_ascii_alphabetic() {
+            let mut identifier = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_ascii_alphanumeric() {
+                    identifier.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Identifier(identifier));
+        } else if "+-*/^".contains(c) {
+            tokens.push(Token::Operator(c));
+            chars.next();
+        } else if c == '(' {
+            tokens.push(Token::Open);
+            chars.next();
+        } else if c == ')' {
+            tokens.push(Token::Close);
+            chars.next();
+        } else {
+            bail!("Unexpected character `{c}` in expression `{input}`");
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser, every method parses one level of precedence.
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    /// `product (('+' | '-') product)*`
+    fn sum(&mut self) -> Result<Expression> {
+        let mut left = self.product()?;
+
+        while let Some(Token::Operator(c @ ('+' | '-'))) = self.peek() {
+            let operator = if *c == '+' {
+                Operator::Add
+            } else {
+                Operator::Subtract
+            };
+            self.next();
+            let right = self.product()?;
+            left = Expression::Binary(operator, Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    /// `unary (('*' | '/') unary)*`
+    fn product(&mut self) -> Result<Expression> {
+        let mut left = self.unary()?;
+
+        while let Some(Token::Operator(c @ ('*' | '/'))) = self.peek() {
+            let operator = if *c == '*' {
+                Operator::Multiply
+            } else {
+                Operator::Divide
+            };
+            self.next();
+            let right = self.unary()?;
+            left = Expression::Binary(operator, Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    /// `'-' unary | power`
+    fn unary(&mut self) -> Result<Expression> {
+        if let Some(Token::Operator('-')) = self.peek() {
+            self.next();
+            return Ok(Expression::Negate(Box::new(self.unary()?)));
+        }
+
+        self.power()
+    }
+
+    /// `atom ('^' unary)?`, which makes the power right associative.
+    fn power(&mut self) -> Result<Expression> {
+        let base = self.atom()?;
+
+        if let Some(Token::Operator('^')) = self.peek() {
+            self.next();
+            let exponent = self.unary()?;
+            return Ok(Expression::Binary(
+                Operator::Power,
+                Box::new(base),
+                Box::new(exponent),
+            ));
+        }
+
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Expression> {
+        match self.next().cloned() {
+            Some(Token::Number(value)) => Ok(Expression::Number(value)),
+            Some(Token::Open) => {
+                let inner = self.sum()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => bail!("Missing closing parenthesis"),
+                }
+            }
+            Some(Token::Identifier(name)) => {
+                let function = match name.as_str() {
+                    "x" => return Ok(Expression::X),
+                    "y" => return Ok(Expression::Y),
+                    "pi" => return Ok(Expression::Number(consts::PI)),
+                    "e" => return Ok(Expression::Number(consts::E)),
+                    "sin" => Function::Sin,
+                    "cos" => Function::Cos,
+                    "tan" => Function::Tan,
+                    "sqrt" => Function::Sqrt,
+                    "abs" => Function::Abs,
+                    "exp" => Function::Exp,
+                    "ln" => Function::Ln,
+                    _ => bail!("Unknown name `{name}`"),
+                };
+
+                match self.next() {
+                    Some(Token::Open) => {}
+                    _ => bail!("Expected `(` after `{name}`"),
+                }
+                let argument = self.sum()?;
+                match self.next() {
+                    Some(Token::Close) => {}
+                    _ => bail!("Missing closing parenthesis after `{name}(`"),
+                }
+
+                Ok(Expression::Function(function, Box::new(argument)))
+            }
+            Some(token) => bail!("Unexpected `{token:?}`"),
+            None => bail!("Unexpected end of expression"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn evaluate(input: &str, x: Float, y: Float) -> Float {
+        Expression::parse(input).unwrap().evaluate(x, y)
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(evaluate("1 + 2 * 3", 0.0, 0.0), 7.0);
+        assert_eq!(evaluate("(1 + 2) * 3", 0.0, 0.0), 9.0);
+        assert_eq!(evaluate("-2^2", 0.0, 0.0), -4.0);
+        assert_eq!(evaluate("2^3^2", 0.0, 0.0), 512.0);
+        assert_eq!(evaluate("x*x + y*y", 3.0, 4.0), 25.0);
+        assert_eq!(evaluate("sqrt(abs(x - 13))", 4.0, 0.0), 3.0);
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        assert!(Expression::parse("1 +").is_err());
+        assert!(Expression::parse("(1 + 2").is_err());
+        assert!(Expression::parse("foo(x)").is_err());
+        assert!(Expression::parse("1 2").is_err());
+        assert!(Expression::parse("x # y").is_err());
+    }
+}
@@ -0,0 +1,240 @@
+use std::fmt::Write;
+
+use clap::Args;
+use itertools::Itertools;
+
+use crate::{
+    direction_of_corner,
+    vec::{Float, Vec2},
+};
+
+/// Settings for the G-code output.
+#[derive(Args, Debug, Clone)]
+pub struct GcodeSettings {
+    /// Feed rate used while drawing, in mm/min.
+    #[arg(long, default_value_t = 1500.0)]
+    pub feed: Float,
+    /// Feed rate used while moving with the pen up, in mm/min.
+    #[arg(long, default_value_t = 3000.0)]
+    pub travel_feed: Float,
+    /// Z height at which the pen is lifted off the paper.
+    #[arg(long, default_value_t = 5.0)]
+    pub pen_up_z: Float,
+    /// Z height at which the pen touches the paper.
+    #[arg(long, default_value_t = 0.0)]
+    pub pen_down_z: Float,
+    /// Corners sharper than this angle (in degrees) are drawn with `--corner-feed`.
+    /// A straight line is 180 degrees, a full reversal is 0 degrees.
+    #[arg(long)]
+    pub corner_angle: Option<Float>,
+    /// Feed rate used on the segments going into and out of a sharp corner, in mm/min.
+    #[arg(long, default_value_t = 500.0)]
+    pub corner_feed: Float,
+}
+
+impl Default for GcodeSettings {
+    fn default() -> Self {
+        Self {
+            feed: 1500.0,
+            travel_feed: 3000.0,
+            pen_up_z: 5.0,
+            pen_down_z: 0.0,
+            corner_angle: None,
+            corner_feed: 500.0,
+        }
+    }
+}
+
+impl GcodeSettings {
+    /// The command line arguments that reproduce these settings.
+    pub fn arguments(&self) -> Vec<String> {
+        let mut arguments = vec![
+            format!("--feed={}", self.feed),
+            format!("--travel-feed={}", self.travel_feed),
+            format!("--pen-up-z={}", self.pen_up_z),
+            format!("--pen-down-z={}", self.pen_down_z),
+            format!("--corner-feed={}", self.corner_feed),
+        ];
+        if let Some(angle) = self.corner_angle {
+            arguments.push(format!("--corner-angle={angle}"));
+        }
+        arguments
+    }
+}
+
+/// Converts the paths to G-code.
+/// The y-axis is flipped, because svg has its origin in the top left,
+/// while plotters have it in the bottom left.
+///
+/// When a path starts within `join_tolerance` of where the previous one ended,
+/// the pen stays down and draws the gap instead of lifting.
+pub fn paths_to_gcode(
+    paths: &[Vec<Vec2>],
+    size: Vec2,
+    settings: &GcodeSettings,
+    join_tolerance: Option<Float>,
+) -> String {
+    let mut gcode = String::new();
+
+    // Millimeters, absolute positioning.
+    writeln!(gcode, "G21").unwrap();
+    writeln!(gcode, "G90").unwrap();
+    writeln!(
+        gcode,
+        "G0 Z{:.3} F{:.0}",
+        settings.pen_up_z, settings.travel_feed
+    )
+    .unwrap();
+
+    let mut previous_end: Option<Vec2> = None;
+
+    for path in paths {
+        let (Some(&first), Some(&last)) = (path.first(), path.last()) else {
+            continue;
+        };
+
+        if joins(previous_end, first, join_tolerance) {
+            writeln!(
+                gcode,
+                "G1 X{:.3} Y{:.3} F{:.0}",
+                first.x,
+                size.y - first.y,
+                settings.feed
+            )
+            .unwrap();
+        } else {
+            if previous_end.is_some() {
+                writeln!(gcode, "G0 Z{:.3}", settings.pen_up_z).unwrap();
+            }
+            writeln!(gcode, "G0 X{:.3} Y{:.3}", first.x, size.y - first.y).unwrap();
+            writeln!(gcode, "G1 Z{:.3}", settings.pen_down_z).unwrap();
+        }
+
+        let sharp_corners = sharp_corners(path, settings.corner_angle);
+
+        for (index, point) in path.iter().enumerate().skip(1) {
+            // A segment is slowed down if either of its ends is a sharp corner.
+            let feed = if sharp_corners[index - 1] || sharp_corners[index] {
+                settings.corner_feed
+            } else {
+                settings.feed
+            };
+
+            writeln!(
+                gcode,
+                "G1 X{:.3} Y{:.3} F{:.0}",
+                point.x,
+                size.y - point.y,
+                feed
+            )
+            .unwrap();
+        }
+
+        previous_end = Some(last);
+    }
+
+    if previous_end.is_some() {
+        writeln!(gcode, "G0 Z{:.3}", settings.pen_up_z).unwrap();
+    }
+
+    gcode
+}
+
+/// Whether a path starting at `start` can be drawn without lifting the pen
+/// after the previous path ended at `previous_end`.
+pub fn joins(previous_end: Option<Vec2>, start: Vec2, join_tolerance: Option<Float>) -> bool {
+    match (previous_end, join_tolerance) {
+        (Some(end), Some(tolerance)) => (start - end).len() <= tolerance,
+        _ => false,
+    }
+}
+
+/// Marks which points of the path are corners sharper than `threshold` degrees.
+/// The first and last points are never corners.
+fn sharp_corners(points: &[Vec2], threshold: Option<Float>) -> Vec<bool> {
+    let mut sharp = vec![false; points.len()];
+
+    let Some(threshold) = threshold else {
+        return sharp;
+    };
+
+    for (index, (&a, &b, &c)) in points.iter().tuple_windows().enumerate() {
+        let Some(direction) = direction_of_corner(a, b, c) else {
+            // Straight line.
+            continue;
+        };
+
+        // The direction of the corner is the bisector, so the angle between it and
+        // one of the legs is half of the corner angle.
+        let leg = (a - b).normalize();
+        let half_angle = direction.dot(leg).clamp(-1.0, 1.0).acos();
+
+        if (half_angle * 2.0).to_degrees() < threshold {
+            sharp[index + 1] = true;
+        }
+    }
+
+    sharp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec2;
+    use pretty_assertions::assert_eq;
+
+    fn pen_lifts(gcode: &str) -> usize {
+        gcode
+            .lines()
+            .filter(|line| line.starts_with("G0 Z"))
+            .count()
+    }
+
+    fn feeds(gcode: &str) -> Vec<&str> {
+        gcode
+            .lines()
+            .filter(|line| line.starts_with("G1 X"))
+            .map(|line| line.rsplit(' ').next().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn sharp_corner_is_slowed_down() {
+        let settings = GcodeSettings {
+            corner_angle: Some(45.0),
+            ..Default::default()
+        };
+        let path = vec![vec2(0.0, 0.0), vec2(10.0, 0.0), vec2(0.0, 1.0)];
+
+        let gcode = paths_to_gcode(&[path], vec2(10.0, 10.0), &settings, None);
+
+        assert_eq!(feeds(&gcode), vec!["F500", "F500"]);
+    }
+
+    #[test]
+    fn straight_run_keeps_normal_feed() {
+        let settings = GcodeSettings {
+            corner_angle: Some(45.0),
+            ..Default::default()
+        };
+        let path = vec![vec2(0.0, 0.0), vec2(5.0, 0.0), vec2(10.0, 0.0)];
+
+        let gcode = paths_to_gcode(&[path], vec2(10.0, 10.0), &settings, None);
+
+        assert_eq!(feeds(&gcode), vec!["F1500", "F1500"]);
+    }
+
+    #[test]
+    fn near_touching_paths_are_joined() {
+        let settings = GcodeSettings::default();
+        let first = vec![vec2(0.0, 0.0), vec2(5.0, 0.0)];
+        let near = vec![vec2(5.05, 0.0), vec2(10.0, 0.0)];
+        let far = vec![vec2(8.0, 5.0), vec2(10.0, 5.0)];
+        let size = vec2(10.0, 10.0);
+
+        let joined = paths_to_gcode(&[first.clone(), near], size, &settings, Some(0.1));
+        let separate = paths_to_gcode(&[first, far], size, &settings, Some(0.1));
+
+        assert_eq!(pen_lifts(&joined) + 1, pen_lifts(&separate));
+    }
+}
@@ -0,0 +1,167 @@
+use image::GrayImage;
+
+use crate::vec::{vec2, Float, Vec2};
+
+/// Hilbert curve that recurses deeper in areas of the image with a lot of detail.
+/// Every cell is recursed at least `min_iterations` times, and at most `max_iterations`.
+/// In between, a cell is only split up further if the standard deviation of the
+/// brightness (0 to 1) of the image underneath it is above `threshold`.
+///
+/// The image is stretched to cover the whole canvas, and must not be empty.
+/// The result is a single continuous line, cells of different depths are connected
+/// by the line between their neighbouring points.
+pub fn adaptive_hilbert_curve(
+    size: Vec2,
+    image: &GrayImage,
+    min_iterations: usize,
+    max_iterations: usize,
+    threshold: Float,
+) -> Vec<Vec2> {
+    let settings = Settings {
+        size,
+        image,
+        min_iterations,
+        max_iterations: max_iterations.max(min_iterations),
+        threshold,
+    };
+
+    let mut points = vec![];
+    recurse(
+        &settings,
+        &mut points,
+        vec2(0.0, 0.0),
+        vec2(size.x, 0.0),
+        vec2(0.0, size.y),
+        0,
+    );
+    points
+}
+
+struct Settings<'a> {
+    size: Vec2,
+    image: &'a GrayImage,
+    min_iterations: usize,
+    max_iterations: usize,
+    threshold: Float,
+}
+
+/// Same recursion as [`crate::hilbert_curve`], but deciding per cell when to stop.
+fn recurse(
+    settings: &Settings,
+    output: &mut Vec<Vec2>,
+    p: Vec2,
+    x_vec: Vec2,
+    y_vec: Vec2,
+    depth: usize,
+) {
+    let half_x = x_vec / 2.0;
+    let half_y = y_vec / 2.0;
+
+    let detailed = depth < settings.min_iterations
+        || (depth < settings.max_iterations
+            && brightness_deviation(settings, p, x_vec, y_vec) > settings.threshold);
+
+    if !detailed {
+        output.push(p + half_x + half_y);
+        return;
+    }
+
+    recurse(settings, output, p, half_y, half_x, depth + 1);
+    recurse(settings, output, p + half_x, half_x, half_y, depth + 1);
+    recurse(
+        settings,
+        output,
+        p + half_x + half_y,
+        half_x,
+        half_y,
+        depth + 1,
+    );
+    recurse(
+        settings,
+        output,
+        p + half_x + y_vec,
+        -half_y,
+        -half_x,
+        depth + 1,
+    );
+}
+
+/// Standard deviation of the brightness of the pixels underneath the cell.
+fn brightness_deviation(settings: &Settings, p: Vec2, x_vec: Vec2, y_vec: Vec2) -> Float {
+    let corners = [p, p + x_vec, p + y_vec, p + x_vec + y_vec];
+    let min_x = corners
+        .iter()
+        .map(|c| c.x)
+        .fold(Float::INFINITY, Float::min);
+    let max_x = corners
+        .iter()
+        .map(|c| c.x)
+        .fold(Float::NEG_INFINITY, Float::max);
+    let min_y = corners
+        .iter()
+        .map(|c| c.y)
+        .fold(Float::INFINITY, Float::min);
+    let max_y = corners
+        .iter()
+        .map(|c| c.y)
+        .fold(Float::NEG_INFINITY, Float::max);
+
+    let (width, height) = settings.image.dimensions();
+    let scale_x = width as Float / settings.size.x;
+    let scale_y = height as Float / settings.size.y;
+
+    let to_pixel = |value: Float, scale: Float, max: u32| ((value * scale) as u32).min(max - 1);
+
+    let start_x = to_pixel(min_x, scale_x, width);
+    let start_y = to_pixel(min_y, scale_y, height);
+    // Always include at least one pixel, even if the cell is smaller than a pixel.
+    let end_x = to_pixel(max_x, scale_x, width).max(start_x + 1).min(width);
+    let end_y = to_pixel(max_y, scale_y, height)
+        .max(start_y + 1)
+        .min(height);
+
+    let mut sum = 0.0;
+    let mut sum_squared = 0.0;
+    let mut count = 0.0;
+    for y in start_y..end_y {
+        for x in start_x..end_x {
+            let brightness = settings.image.get_pixel(x, y).0[0] as Float / 255.0;
+            sum += brightness;
+            sum_squared += brightness * brightness;
+            count += 1.0;
+        }
+    }
+
+    let mean = sum / count;
+    (sum_squared / count - mean * mean).max(0.0).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn flat_image_only_uses_min_iterations() {
+        let size = vec2(100.0, 100.0);
+        let image = GrayImage::from_pixel(64, 64, Luma([128]));
+
+        let points = adaptive_hilbert_curve(size, &image, 3, 6, 0.05);
+
+        assert_eq!(
+            points,
+            crate::hilbert_curve(vec2(0.0, 0.0), vec2(size.x, 0.0), vec2(0.0, size.y), 3)
+        );
+    }
+
+    #[test]
+    fn detailed_image_uses_more_iterations() {
+        let size = vec2(100.0, 100.0);
+        let image = GrayImage::from_fn(64, 64, |x, _| Luma([if x % 2 == 0 { 0 } else { 255 }]));
+
+        let points = adaptive_hilbert_curve(size, &image, 2, 4, 0.05);
+
+        assert!(points.len() > 4usize.pow(2));
+    }
+}
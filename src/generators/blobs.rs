@@ -0,0 +1,69 @@
+use rand::Rng;
+
+use crate::{
+    marching_squares::{contours, Grid},
+    noise::Perlin,
+    vec::{Float, Vec2},
+};
+
+/// Organic closed shapes: the outlines of the areas where perlin noise
+/// (scaled to 0..1) is above `threshold`.
+/// `scale` is the rough size of a blob, in canvas units, and has to be positive.
+pub fn blobs(size: Vec2, scale: Float, threshold: Float, rng: &mut impl Rng) -> Vec<Vec<Vec2>> {
+    let noise = Perlin::new(rng);
+
+    // Enough cells per blob to get smooth outlines.
+    let cell = scale / 16.0;
+    let resolution = (size.x.max(size.y) / cell).ceil() as usize;
+
+    let mut grid = Grid::sample(Vec2::ZERO, size, resolution, |p| {
+        (noise.get(p / scale) + 1.0) / 2.0
+    });
+
+    // Everything outside the edges of the grid counts as below the threshold,
+    // so blobs that touch the edge of the canvas are closed as well.
+    for row in 0..grid.rows {
+        for column in 0..grid.columns {
+            if row == 0 || column == 0 || row == grid.rows - 1 || column == grid.columns - 1 {
+                grid.values[row * grid.columns + column] = threshold.min(0.0) - 1.0;
+            }
+        }
+    }
+
+    contours(&grid, threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec2;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn total_length(paths: &[Vec<Vec2>]) -> Float {
+        paths
+            .iter()
+            .flat_map(|path| path.windows(2))
+            .map(|segment| (segment[1] - segment[0]).len())
+            .sum()
+    }
+
+    #[test]
+    fn higher_threshold_less_boundary() {
+        let size = vec2(100.0, 100.0);
+
+        let low = blobs(size, 20.0, 0.5, &mut StdRng::seed_from_u64(3));
+        let high = blobs(size, 20.0, 0.7, &mut StdRng::seed_from_u64(3));
+
+        assert!(total_length(&high) < total_length(&low));
+    }
+
+    #[test]
+    fn blobs_are_closed() {
+        let paths = blobs(vec2(100.0, 50.0), 20.0, 0.5, &mut StdRng::seed_from_u64(3));
+
+        assert!(!paths.is_empty());
+        for path in paths {
+            assert_eq!(path.first(), path.last());
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    lsystem::LSystem,
+    paths::fit_to_canvas,
+    vec::{consts::FRAC_PI_2, Float, Vec2},
+};
+
+/// Cesàro fractal, or "torn square": a square with a Koch-like curve on every side,
+/// torn inwards. An `angle` of 60 degrees gives Koch curves, angles approaching 90
+/// tear the square up to the center.
+///
+/// The result is a single closed line, fit to the canvas.
+pub fn cesaro(size: Vec2, iterations: usize, angle: Float) -> Vec<Vec2> {
+    let system = LSystem {
+        axiom: "F".to_string(),
+        rules: vec![('F', "F+F--F+F".to_string())],
+        angle,
+    };
+
+    let edge = system.draw(iterations);
+    // The rule keeps the edge going in a straight line, normalize its length to 1.
+    let length = edge.last().map_or(1.0, |end| end.len());
+    let edge: Vec<Vec2> = edge.into_iter().map(|p| p / length).collect();
+
+    // Walk clockwise around the square. The curve bulges to the left of its direction
+    // (positive angles), which is the inside of the square on screen, where y points down.
+    let mut points = vec![Vec2::ZERO];
+    let mut corner = Vec2::ZERO;
+    for side in 0..4 {
+        let rotation = FRAC_PI_2 * side as Float;
+        points.extend(edge.iter().skip(1).map(|&p| corner + p.rotate(rotation)));
+        corner = *points.last().unwrap();
+    }
+
+    fit_to_canvas(vec![points], size).remove(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec2;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn point_count_follows_koch_growth() {
+        for iterations in 0..5 {
+            let points = cesaro(vec2(100.0, 100.0), iterations, 85.0);
+
+            // Every iteration splits every segment into 4.
+            assert_eq!(points.len(), 4 * 4usize.pow(iterations as u32) + 1);
+        }
+    }
+
+    #[test]
+    fn is_closed() {
+        let points = cesaro(vec2(100.0, 100.0), 3, 85.0);
+
+        assert!((points[0] - *points.last().unwrap()).len() < 1e-3);
+    }
+}
@@ -0,0 +1,212 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use rand::Rng;
+
+use crate::{
+    geometry::wobbly_circle,
+    vec::{vec2, Float, Vec2},
+};
+
+/// Radius of the pads, as a fraction of the grid spacing.
+const PAD_RADIUS: Float = 0.3;
+/// Extra cost of a corner in a trace, in grid steps. Makes the traces prefer long
+/// straight runs over staircases.
+const CORNER_COST: u32 = 4;
+/// How far the second pad of a trace can be from the first, in grid steps along both axes.
+const MAX_REACH: i64 = 8;
+/// Amount of segments in the circle of a pad.
+const PAD_SEGMENTS: usize = 16;
+
+/// The four directions a trace can go on the grid.
+const DIRECTIONS: [(i64, i64); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// Circuit board: pairs of round pads connected by traces that only run horizontally
+/// and vertically, over a grid with cells of `spacing` units.
+///
+/// Every trace and pad takes up its grid cells, so later traces are routed around the
+/// earlier ones. Pads that can't be connected are left out. `density` is the fraction
+/// of the grid cells (0 to 1) that gets a pad. `wobble` is passed on to `wobbly_circle`
+/// for the pads.
+pub fn circuit(
+    size: Vec2,
+    spacing: Float,
+    density: Float,
+    wobble: Float,
+    rng: &mut impl Rng,
+) -> Vec<Vec<Vec2>> {
+    let columns = (size.x / spacing).floor() as i64;
+    let rows = (size.y / spacing).floor() as i64;
+    if columns < 2 || rows < 2 || spacing <= 0.0 {
+        return vec![];
+    }
+
+    let grid = Grid { columns, rows };
+    let mut used = vec![false; (columns * rows) as usize];
+
+    // Cell coordinates to the canvas, with the grid centered on the canvas.
+    let offset = (size - vec2(columns as Float, rows as Float) * spacing) / 2.0;
+    let position = |(x, y): (i64, i64)| offset + vec2(x as Float + 0.5, y as Float + 0.5) * spacing;
+    let radius = PAD_RADIUS * spacing;
+
+    let pairs = (density.clamp(0.0, 1.0) * (columns * rows) as Float / 2.0).round() as usize;
+    let mut paths = vec![];
+
+    for _ in 0..pairs {
+        let start = (rng.gen_range(0..columns), rng.gen_range(0..rows));
+        let end = (
+            (start.0 + rng.gen_range(-MAX_REACH..=MAX_REACH)).clamp(0, columns - 1),
+            (start.1 + rng.gen_range(-MAX_REACH..=MAX_REACH)).clamp(0, rows - 1),
+        );
+        if start == end || used[grid.index(start)] || used[grid.index(end)] {
+            continue;
+        }
+
+        let Some(cells) = route(&grid, &used, start, end) else {
+            continue;
+        };
+        for &cell in &cells {
+            used[grid.index(cell)] = true;
+        }
+
+        let mut trace: Vec<Vec2> = corners(&cells).into_iter().map(position).collect();
+        // Start and end at the edge of the pads instead of in the middle.
+        let last = trace.len() - 1;
+        trace[0] = trace[0] + (trace[1] - trace[0]).normalize() * radius;
+        trace[last] = trace[last] + (trace[last - 1] - trace[last]).normalize() * radius;
+
+        for pad in [start, end] {
+            paths.push(wobbly_circle(
+                position(pad),
+                radius,
+                PAD_SEGMENTS,
+                wobble,
+                rng,
+            ));
+        }
+        paths.push(trace);
+    }
+
+    paths
+}
+
+struct Grid {
+    columns: i64,
+    rows: i64,
+}
+
+impl Grid {
+    fn index(&self, (x, y): (i64, i64)) -> usize {
+        (y * self.columns + x) as usize
+    }
+
+    fn contains(&self, (x, y): (i64, i64)) -> bool {
+        (0..self.columns).contains(&x) && (0..self.rows).contains(&y)
+    }
+}
+
+/// Cheapest way from `start` to `end` over cells that are not used yet, where every step
+/// costs 1 and every corner `CORNER_COST` extra. Returns all cells along the way.
+fn route(
+    grid: &Grid,
+    used: &[bool],
+    start: (i64, i64),
+    end: (i64, i64),
+) -> Option<Vec<(i64, i64)>> {
+    // Every cell is visited once per direction, since the cost of the next step
+    // depends on the direction it was entered in.
+    let state = |cell: (i64, i64), direction: usize| grid.index(cell) * 4 + direction;
+    let mut cost = vec![u32::MAX; used.len() * 4];
+    let mut previous: Vec<Option<((i64, i64), usize)>> = vec![None; used.len() * 4];
+    let mut queue = BinaryHeap::new();
+
+    for direction in 0..4 {
+        cost[state(start, direction)] = 0;
+        queue.push(Reverse((0, start, direction)));
+    }
+
+    while let Some(Reverse((current_cost, cell, direction))) = queue.pop() {
+        if cell == end {
+            let mut cells = vec![cell];
+            let mut current = (cell, direction);
+            while let Some(before) = previous[state(current.0, current.1)] {
+                cells.push(before.0);
+                current = before;
+            }
+            cells.reverse();
+            return Some(cells);
+        }
+        if current_cost > cost[state(cell, direction)] {
+            continue;
+        }
+
+        for (next_direction, (dx, dy)) in DIRECTIONS.into_iter().enumerate() {
+            let next = (cell.0 + dx, cell.1 + dy);
+            if !grid.contains(next) || used[grid.index(next)] {
+                continue;
+            }
+
+            let corner = cell != start && next_direction != direction;
+            let next_cost = current_cost + 1 + if corner { CORNER_COST } else { 0 };
+            if next_cost < cost[state(next, next_direction)] {
+                cost[state(next, next_direction)] = next_cost;
+                previous[state(next, next_direction)] = Some((cell, direction));
+                queue.push(Reverse((next_cost, next, next_direction)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Only the cells where the direction changes, and both ends.
+fn corners(cells: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    let mut corners = vec![cells[0]];
+    for window in cells.windows(3) {
+        let [a, b, c] = window else { unreachable!() };
+        if (b.0 - a.0, b.1 - a.1) != (c.0 - b.0, c.1 - b.1) {
+            corners.push(*b);
+        }
+    }
+    corners.push(*cells.last().unwrap());
+    corners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::point_segment_distance;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn traces_are_axis_aligned_and_avoid_pads() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let paths = circuit(vec2(80.0, 60.0), 2.0, 0.1, 0.0, &mut rng);
+
+        let (pads, traces): (Vec<_>, Vec<_>) =
+            paths.iter().partition(|path| path.first() == path.last());
+        assert!(traces.len() > 10);
+        assert_eq!(pads.len(), traces.len() * 2);
+
+        let radius = PAD_RADIUS * 2.0;
+        let centers: Vec<Vec2> = pads
+            .iter()
+            .map(|pad| {
+                pad[..PAD_SEGMENTS]
+                    .iter()
+                    .fold(Vec2::ZERO, |sum, &p| sum + p)
+                    / PAD_SEGMENTS as Float
+            })
+            .collect();
+
+        for trace in traces {
+            for segment in trace.windows(2) {
+                let (a, b) = (segment[0], segment[1]);
+                assert!(a.x == b.x || a.y == b.y, "{a:?} to {b:?} is diagonal");
+                for &center in &centers {
+                    assert!(point_segment_distance(center, a, b) > radius - 1e-3);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use rand::Rng;
+
+use crate::vec::{vec2, Float, Vec2};
+
+/// Fractal coastline across the whole width of the canvas, made by midpoint displacement.
+///
+/// Starts with a straight line through the middle of the canvas. Every iteration puts a
+/// new point halfway every segment, moved up or down by a random amount. That amount is at
+/// most a quarter of the height of the canvas in the first iteration, and is multiplied
+/// by `roughness` every next iteration. A higher roughness (up to 1) gives a more
+/// jagged line.
+pub fn coastline(size: Vec2, iterations: usize, roughness: Float, rng: &mut impl Rng) -> Vec<Vec2> {
+    let mut points = vec![vec2(0.0, size.y / 2.0), vec2(size.x, size.y / 2.0)];
+    let mut amount = size.y / 4.0;
+
+    for _ in 0..iterations {
+        let mut next = vec![points[0]];
+        for pair in points.windows(2) {
+            let middle = (pair[0] + pair[1]) / 2.0;
+            next.push(middle + vec2(0.0, rng.gen_range(-1.0..=1.0) * amount));
+            next.push(pair[1]);
+        }
+        points = next;
+        amount *= roughness;
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn rougher_coastline_is_longer() {
+        let size = vec2(100.0, 60.0);
+        let length = |points: &[Vec2]| -> Float {
+            points
+                .windows(2)
+                .map(|pair| (pair[1] - pair[0]).len())
+                .sum()
+        };
+
+        let smooth = coastline(size, 8, 0.3, &mut StdRng::seed_from_u64(3));
+        let rough = coastline(size, 8, 0.7, &mut StdRng::seed_from_u64(3));
+
+        assert_eq!(smooth.len(), 2usize.pow(8) + 1);
+        assert_eq!(rough.len(), smooth.len());
+        assert_eq!(rough[0], vec2(0.0, 30.0));
+        assert_eq!(*rough.last().unwrap(), vec2(100.0, 30.0));
+        assert!(length(&rough) > length(&smooth));
+    }
+}
@@ -0,0 +1,104 @@
+use rand::Rng;
+
+use crate::vec::{consts::TAU, vec2, Float, Vec2};
+
+/// Diffusion-limited aggregation: particles wander around randomly until they touch the
+/// structure, where they stick. Starting from a single particle in the center, this grows
+/// a branching, coral like structure.
+///
+/// The particles move on a grid with cells of `step` units. Every stuck particle is drawn
+/// as a line to the particle it stuck to. To save time, particles are released on a
+/// circle just around the structure instead of at the edge of the canvas, which gives the
+/// same result, since they would have to pass that circle anyway. Particles that wander
+/// off too far are released again.
+/// It stops after `particles` particles, or when the structure reaches the edge of the canvas.
+pub fn dla(size: Vec2, particles: usize, step: Float, rng: &mut impl Rng) -> Vec<Vec<Vec2>> {
+    let columns = (size.x / step).floor() as i64;
+    let rows = (size.y / step).floor() as i64;
+    if columns < 3 || rows < 3 || step <= 0.0 {
+        return vec![];
+    }
+
+    let mut stuck = vec![false; (columns * rows) as usize];
+    let index = |(x, y): (i64, i64)| (y * columns + x) as usize;
+    let center = (columns / 2, rows / 2);
+    stuck[index(center)] = true;
+
+    // Cell coordinates to the canvas, centered in the cell like the center particle.
+    let offset = (size - vec2(columns as Float, rows as Float) * step) / 2.0;
+    let position = |(x, y): (i64, i64)| offset + vec2(x as Float + 0.5, y as Float + 0.5) * step;
+
+    let directions = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+    let mut radius: Float = 0.0;
+    let mut paths = vec![];
+
+    while paths.len() < particles {
+        let release = radius + 5.0;
+        let escape = release * 2.0 + 10.0;
+        if release >= (columns.min(rows) / 2 - 1) as Float {
+            // The structure reached the edge of the canvas.
+            break;
+        }
+
+        let angle = rng.gen_range(0.0..TAU);
+        let mut cell = (
+            center.0 + (angle.cos() * release).round() as i64,
+            center.1 + (angle.sin() * release).round() as i64,
+        );
+
+        loop {
+            let neighbour = directions
+                .iter()
+                .map(|(dx, dy)| (cell.0 + dx, cell.1 + dy))
+                .find(|&(x, y)| {
+                    x >= 0 && y >= 0 && x < columns && y < rows && stuck[index((x, y))]
+                });
+            if let Some(neighbour) = neighbour {
+                stuck[index(cell)] = true;
+                paths.push(vec![position(neighbour), position(cell)]);
+                let distance = vec2((cell.0 - center.0) as Float, (cell.1 - center.1) as Float);
+                radius = radius.max(distance.len());
+                break;
+            }
+
+            let (dx, dy) = directions[rng.gen_range(0..4)];
+            cell = (cell.0 + dx, cell.1 + dy);
+
+            let distance = vec2((cell.0 - center.0) as Float, (cell.1 - center.1) as Float);
+            let outside = cell.0 < 0 || cell.1 < 0 || cell.0 >= columns || cell.1 >= rows;
+            if outside || distance.len() > escape {
+                // Lost, release a new particle.
+                break;
+            }
+        }
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn every_particle_sticks_to_an_earlier_one() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let size = vec2(60.0, 60.0);
+
+        let paths = dla(size, 150, 1.0, &mut rng);
+
+        assert_eq!(paths.len(), 150);
+        let mut structure = vec![vec2(30.5, 30.5)];
+        for path in &paths {
+            let [parent, particle] = path[..] else {
+                panic!("Expected a single segment, got {path:?}");
+            };
+            assert!(structure.contains(&parent));
+            assert!(!structure.contains(&particle));
+            assert!(((particle - parent).len() - 1.0).abs() < 1e-5);
+            structure.push(particle);
+        }
+    }
+}
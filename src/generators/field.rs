@@ -0,0 +1,80 @@
+use crate::{
+    expression::Expression,
+    marching_squares::{contours, Grid},
+    vec::{Float, Vec2},
+};
+
+/// Contour lines of the expression, at `levels` evenly spaced values between the
+/// lowest and highest value the expression takes on the canvas.
+///
+/// `x` and `y` are centered on the canvas, and run from -1 to 1 along the shortest side.
+/// `resolution` is the amount of grid cells along the longest side of the canvas.
+pub fn field_contours(
+    size: Vec2,
+    expression: &Expression,
+    levels: usize,
+    resolution: usize,
+) -> Vec<Vec<Vec2>> {
+    let grid = sample(size, expression, resolution);
+
+    let min = grid
+        .values
+        .iter()
+        .copied()
+        .fold(Float::INFINITY, Float::min);
+    let max = grid
+        .values
+        .iter()
+        .copied()
+        .fold(Float::NEG_INFINITY, Float::max);
+
+    (1..=levels)
+        .flat_map(|level| {
+            let threshold = min + (max - min) * level as Float / (levels + 1) as Float;
+            contours(&grid, threshold)
+        })
+        .collect()
+}
+
+fn sample(size: Vec2, expression: &Expression, resolution: usize) -> Grid {
+    let center = size / 2.0;
+    let scale = size.x.min(size.y) / 2.0;
+
+    Grid::sample(Vec2::ZERO, size, resolution, |p| {
+        let p = (p - center) / scale;
+        expression.evaluate(p.x, p.y)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec2;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn circle_contour() {
+        let size = vec2(100.0, 100.0);
+        let expression = Expression::parse("x*x + y*y").unwrap();
+        // Ranges from 0 in the center to 2 in the corners, so the single level is at 1,
+        // which is a circle touching the sides of the canvas.
+        let lines = field_contours(size, &expression, 1, 200);
+
+        let center = size / 2.0;
+        let points: Vec<Vec2> = lines.into_iter().flatten().collect();
+        assert!(!points.is_empty());
+        for point in points {
+            assert!(((point - center).len() - 50.0).abs() < 0.5);
+        }
+    }
+
+    #[test]
+    fn multiple_circles() {
+        let expression = Expression::parse("x*x + y*y").unwrap();
+
+        let lines = field_contours(vec2(100.0, 100.0), &expression, 4, 100);
+
+        // The first two levels are whole circles, the others are cut off by the canvas edges.
+        assert_eq!(lines.iter().filter(|l| l.first() == l.last()).count(), 2);
+    }
+}
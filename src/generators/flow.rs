@@ -0,0 +1,138 @@
+use clap::ValueEnum;
+use rand::Rng;
+
+use crate::{
+    noise::Perlin,
+    scatter::{scatter, Distribution},
+    vec::{consts::TAU, vec2, Float, Vec2},
+};
+
+/// What happens to a line that reaches the edge of the canvas.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Boundary {
+    /// The line stops.
+    Clip,
+    /// The line bounces back, as if the edges were mirrors.
+    Reflect,
+    /// The line continues from the opposite edge.
+    Wrap,
+}
+
+/// Streamlines following a field of directions made from perlin noise.
+/// Every line starts at a random point, spread according to `distribution`, and takes
+/// `steps` steps of `step_size`. `scale` is the rough size of the swirls, in canvas units.
+#[allow(clippy::too_many_arguments)]
+pub fn flow_field(
+    size: Vec2,
+    lines: usize,
+    steps: usize,
+    step_size: Float,
+    scale: Float,
+    boundary: Boundary,
+    distribution: Distribution,
+    rng: &mut impl Rng,
+) -> Vec<Vec<Vec2>> {
+    let noise = Perlin::new(rng);
+    let direction = |p: Vec2| Vec2::from_angle(noise.get(p / scale) * TAU);
+
+    let mut paths = vec![];
+    for start in scatter(size, lines, distribution, rng) {
+        paths.extend(streamline(
+            size, start, steps, step_size, boundary, direction,
+        ));
+    }
+
+    paths.retain(|path| path.len() >= 2);
+    paths
+}
+
+/// Follows the field from `start`. Returns more than one line when it wraps around.
+///
+/// The line is traced on an unfolded, endless plane, sampling the field at the point
+/// folded back onto the canvas. For reflecting, that plane is tiled with mirrored copies
+/// of the canvas, so folding a point back is the bounce. For wrapping, the tiles are
+/// plain copies.
+fn streamline(
+    size: Vec2,
+    start: Vec2,
+    steps: usize,
+    step_size: Float,
+    boundary: Boundary,
+    direction: impl Fn(Vec2) -> Vec2,
+) -> Vec<Vec<Vec2>> {
+    let fold_point = |p: Vec2| vec2(fold(p.x, size.x, boundary), fold(p.y, size.y, boundary));
+
+    let mut lines = vec![vec![start]];
+    let mut unfolded = start;
+    let mut tile = (0, 0);
+
+    for _ in 0..steps {
+        unfolded = unfolded + direction(fold_point(unfolded)) * step_size;
+
+        let new_tile = (
+            (unfolded.x / size.x).floor() as i64,
+            (unfolded.y / size.y).floor() as i64,
+        );
+        match boundary {
+            Boundary::Clip if new_tile != (0, 0) => break,
+            // Jumping to the other side of the canvas, start a new line there.
+            Boundary::Wrap if new_tile != tile => lines.push(vec![]),
+            _ => {}
+        }
+        tile = new_tile;
+
+        lines.last_mut().unwrap().push(fold_point(unfolded));
+    }
+
+    lines
+}
+
+/// Folds a coordinate on the unfolded plane back onto the canvas, which runs from
+/// 0 to `size`.
+fn fold(value: Float, size: Float, boundary: Boundary) -> Float {
+    match boundary {
+        Boundary::Clip => value,
+        Boundary::Wrap => value.rem_euclid(size),
+        Boundary::Reflect => {
+            let folded = value.rem_euclid(2.0 * size);
+            if folded > size {
+                2.0 * size - folded
+            } else {
+                folded
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reflect_bounces_off_the_wall() {
+        let size = vec2(10.0, 10.0);
+        let to_the_right = |_| vec2(1.0, 0.0);
+
+        let lines = streamline(
+            size,
+            vec2(8.0, 5.0),
+            6,
+            1.0,
+            Boundary::Reflect,
+            to_the_right,
+        );
+
+        assert_eq!(lines.len(), 1);
+        let xs: Vec<Float> = lines[0].iter().map(|p| p.x).collect();
+        assert_eq!(xs, [8.0, 9.0, 10.0, 9.0, 8.0, 7.0, 6.0]);
+        assert!(lines[0].iter().all(|p| p.y == 5.0));
+
+        let clipped = streamline(size, vec2(8.0, 5.0), 6, 1.0, Boundary::Clip, to_the_right);
+        assert_eq!(clipped, vec![vec![vec2(8.0, 5.0), vec2(9.0, 5.0)]]);
+
+        let wrapped = streamline(size, vec2(8.0, 5.0), 3, 1.0, Boundary::Wrap, to_the_right);
+        assert_eq!(wrapped.len(), 2);
+        assert_eq!(wrapped[1], vec![vec2(0.0, 5.0), vec2(1.0, 5.0)]);
+    }
+}
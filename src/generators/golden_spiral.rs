@@ -0,0 +1,73 @@
+use crate::{
+    paths::fit_to_canvas,
+    vec::{consts::PI, Float, Vec2},
+};
+
+/// Amount of straight segments in every quarter circle of the spiral.
+const ARC_SEGMENTS: usize = 16;
+
+/// Golden spiral made of quarter circles, each in its own square, with the sides of the
+/// squares following the Fibonacci sequence. Every full turn takes four squares.
+///
+/// Returns the spiral as a single line, and the outlines of the squares as closed lines.
+/// Together they are as large as fits the canvas.
+pub fn golden_spiral(size: Vec2, turns: usize) -> (Vec<Vec2>, Vec<Vec<Vec2>>) {
+    let mut spiral = vec![Vec2::ZERO];
+    let mut squares = vec![];
+
+    let mut position = Vec2::ZERO;
+    let mut heading = Vec2::from_angle(0.0);
+    let (mut side, mut next_side): (Float, Float) = (1.0, 1.0);
+
+    for _ in 0..turns * 4 {
+        // The quarter circle turns left, around the corner of the square on that side.
+        let left = heading.perp();
+        let center = position + left * side;
+        spiral.extend((1..=ARC_SEGMENTS).map(|i| {
+            let angle = PI / 2.0 * i as Float / ARC_SEGMENTS as Float;
+            center - left.rotate(angle) * side
+        }));
+
+        let far = position + heading * side;
+        squares.push(vec![position, far, far + left * side, center, position]);
+
+        position = far + left * side;
+        heading = left;
+        (side, next_side) = (next_side, side + next_side);
+    }
+
+    let mut paths = fit_to_canvas([vec![spiral], squares].concat(), size);
+    let spiral = paths.remove(0);
+    (spiral, paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec2;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn radii_follow_the_fibonacci_sequence() {
+        let (spiral, squares) = golden_spiral(vec2(100.0, 100.0), 2);
+
+        assert_eq!(squares.len(), 8);
+        assert_eq!(spiral.len(), 8 * ARC_SEGMENTS + 1);
+        // The start and end of a quarter circle are a diagonal of its square apart.
+        let radii: Vec<Float> = spiral
+            .iter()
+            .step_by(ARC_SEGMENTS)
+            .collect::<Vec<_>>()
+            .windows(2)
+            .map(|pair| (*pair[1] - *pair[0]).len() / Float::sqrt(2.0))
+            .collect();
+        let unit = radii[0];
+        for (radius, fibonacci) in radii.iter().zip([1, 1, 2, 3, 5, 8, 13, 21]) {
+            assert!((radius / unit - fibonacci as Float).abs() < 1e-3);
+        }
+        for (square, &radius) in squares.iter().zip(&radii) {
+            assert!(((square[1] - square[0]).len() - radius).abs() < 1e-3);
+        }
+        assert!((radii[7] / radii[6] - (1.0 + Float::sqrt(5.0)) / 2.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    lsystem::LSystem,
+    paths::{bounding_box, mask_paths},
+    vec::{consts::TAU, Float, Vec2},
+};
+
+/// Gosper curve (flowsnake), a space-filling curve on a hexagonal grid, cut off by
+/// the `mask` polygon so it only fills that region. Every part of the curve inside the
+/// mask becomes its own line.
+///
+/// The curve is scaled to cover the bounding box of the mask. Every iteration makes
+/// the curve 7 times longer.
+pub fn gosper(iterations: usize, mask: &[Vec2]) -> Vec<Vec<Vec2>> {
+    let Some((min, max)) = bounding_box(&[mask.to_vec()]) else {
+        return vec![];
+    };
+
+    let system = LSystem {
+        axiom: "F".to_string(),
+        rules: vec![
+            ('F', "F-G--G+F++FF+G-".to_string()),
+            ('G', "+F-FF++F+G--G-F".to_string()),
+        ],
+        angle: 60.0,
+    };
+    let curve = system.draw(iterations);
+
+    let Some((curve_min, curve_max)) = bounding_box(std::slice::from_ref(&curve)) else {
+        return vec![];
+    };
+    let (extent, curve_extent) = (max - min, curve_max - curve_min);
+    // Without iterations the curve is a single horizontal step.
+    let scale = match extent.y / curve_extent.y {
+        scale_y if scale_y.is_finite() => Float::max(extent.x / curve_extent.x, scale_y),
+        _ => extent.x / curve_extent.x,
+    };
+    let center = (min + max) / 2.0;
+    let curve_center = (curve_min + curve_max) / 2.0;
+    let curve = curve
+        .into_iter()
+        .map(|p| center + (p - curve_center) * scale)
+        .collect();
+
+    mask_paths(vec![curve], mask)
+}
+
+/// Regular hexagon with a flat top, as large as fits the canvas, as a closed line.
+pub fn canvas_hexagon(size: Vec2) -> Vec<Vec2> {
+    let radius = Float::min(size.x / 2.0, size.y / Float::sqrt(3.0));
+    (0..=6)
+        .map(|i| size / 2.0 + Vec2::from_angle(TAU * (i % 6) as Float / 6.0) * radius)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geometry::point_in_polygon, vec::vec2};
+
+    #[test]
+    fn stays_inside_the_mask() {
+        let hexagon = canvas_hexagon(vec2(100.0, 80.0));
+
+        let paths = gosper(4, &hexagon);
+
+        assert!(paths.len() > 1);
+        let points: usize = paths.iter().map(Vec::len).sum();
+        assert!(points > 7usize.pow(4) / 2);
+        for point in paths.iter().flatten() {
+            assert!(point_in_polygon(*point, &hexagon));
+        }
+    }
+}
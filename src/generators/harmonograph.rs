@@ -0,0 +1,94 @@
+use crate::vec::{
+    consts::{PI, TAU},
+    vec2, Float, Vec2,
+};
+
+/// Amount of points in every figure.
+const POINTS: usize = 2000;
+/// How many full periods of the slowest pendulum every figure draws.
+const PERIODS: Float = 20.0;
+
+/// A grid of harmonograph figures. A harmonograph is a pair of slowly decaying pendulums,
+/// one moving the pen horizontally and the other vertically.
+///
+/// Across the grid the frequencies change: the horizontal pendulum speeds up from left to
+/// right, and the vertical one from top to bottom. The phase shifts a little in every cell,
+/// so the figures on the diagonal aren't all plain circles.
+/// Every figure is scaled to fill its cell, leaving a small gap between the cells.
+pub fn harmonograph_grid(size: Vec2, columns: usize, rows: usize, decay: Float) -> Vec<Vec<Vec2>> {
+    if columns == 0 || rows == 0 {
+        return vec![];
+    }
+    let cell = vec2(size.x / columns as Float, size.y / rows as Float);
+
+    let mut paths = vec![];
+    for row in 0..rows {
+        for column in 0..columns {
+            let frequency = vec2(1.0 + column as Float, 1.0 + row as Float);
+            let phase = PI / 2.0 + PI / 8.0 * (column + row) as Float;
+            let figure = harmonograph(frequency, phase, decay);
+
+            let center = vec2(column as Float + 0.5, row as Float + 0.5);
+            let center = vec2(center.x * cell.x, center.y * cell.y);
+            // Nine tenths of the cell, the rest is a gap between the figures.
+            let scale = cell * 0.45;
+            paths.push(
+                figure
+                    .into_iter()
+                    .map(|p| center + vec2(p.x * scale.x, p.y * scale.y))
+                    .collect(),
+            );
+        }
+    }
+
+    paths
+}
+
+/// A figure from -1 to 1 on both axes. `decay` is how much smaller the figure gets
+/// every period of the slowest pendulum, as a fraction.
+fn harmonograph(frequency: Vec2, phase: Float, decay: Float) -> Vec<Vec2> {
+    let slowest = frequency.x.min(frequency.y);
+    let duration = PERIODS * TAU / slowest;
+    let damping = -(1.0 - decay.clamp(0.0, 0.99)).ln() * slowest / TAU;
+
+    (0..POINTS)
+        .map(|i| {
+            let t = duration * i as Float / (POINTS - 1) as Float;
+            let amplitude = (-damping * t).exp();
+            vec2((frequency.x * t + phase).sin(), (frequency.y * t).sin()) * amplitude
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn one_figure_per_cell() {
+        let size = vec2(120.0, 80.0);
+
+        let paths = harmonograph_grid(size, 3, 2, 0.05);
+
+        assert_eq!(paths.len(), 6);
+        for (index, path) in paths.iter().enumerate() {
+            let (column, row) = (index % 3, index / 3);
+            let min = vec2(column as Float * 40.0, row as Float * 40.0);
+            let max = min + vec2(40.0, 40.0);
+            for point in path {
+                assert!(point.x > min.x && point.x < max.x);
+                assert!(point.y > min.y && point.y < max.y);
+            }
+
+            // All figures are different, even after moving them to the same cell.
+            for other in &paths[..index] {
+                let shift = other[0] - path[0];
+                assert!(path
+                    .iter()
+                    .zip(other)
+                    .any(|(&a, &b)| (a + shift - b).len() > 1e-2));
+            }
+        }
+    }
+}
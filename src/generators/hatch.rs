@@ -0,0 +1,124 @@
+use crate::{
+    geometry::scanline_intersections,
+    paths::bounding_box,
+    vec::{vec2, Float, Vec2},
+};
+
+/// Parallel lines `spacing` apart, at `angle` degrees, covering the whole canvas.
+/// All lines run in the same direction, and are ordered from one side of the canvas
+/// to the other.
+pub fn hatch(size: Vec2, spacing: Float, angle: Float) -> Vec<Vec<Vec2>> {
+    if spacing <= 0.0 {
+        return vec![];
+    }
+
+    let direction = Vec2::from_angle(angle.to_radians());
+    let normal = vec2(-direction.y, direction.x);
+    let center = size / 2.0;
+    // Half the width of the canvas, measured across the lines.
+    let reach = (size.x * normal.x.abs() + size.y * normal.y.abs()) / 2.0;
+    let count = (reach / spacing).floor() as i64;
+
+    (-count..=count)
+        .filter_map(|i| clip_line(center + normal * (i as Float * spacing), direction, size))
+        .collect()
+}
+
+/// Parallel lines `spacing` apart, at `angle` degrees, filling the inside of the
+/// polygon. Lines that cross a gap in the polygon are split up.
+pub fn hatch_shape(polygon: &[Vec2], spacing: Float, angle: Float) -> Vec<Vec<Vec2>> {
+    let angle = angle.to_radians();
+    // Turned so the lines are horizontal.
+    let turned = polygon.iter().map(|p| p.rotate(-angle)).collect::<Vec<_>>();
+    let Some((min, max)) = bounding_box(std::slice::from_ref(&turned)) else {
+        return vec![];
+    };
+    if spacing <= 0.0 {
+        return vec![];
+    }
+
+    let mut lines = vec![];
+    let mut y = min.y;
+    while y <= max.y {
+        // Lines touching a corner have no length.
+        for pair in scanline_intersections(&turned, y)
+            .chunks_exact(2)
+            .filter(|pair| pair[1] > pair[0])
+        {
+            lines.push(vec![
+                vec2(pair[0], y).rotate(angle),
+                vec2(pair[1], y).rotate(angle),
+            ]);
+        }
+        y += spacing;
+    }
+    lines
+}
+
+/// The part of the endless line through `origin` along `direction` that is on the canvas.
+fn clip_line(origin: Vec2, direction: Vec2, size: Vec2) -> Option<Vec<Vec2>> {
+    let (mut start, mut end) = (Float::NEG_INFINITY, Float::INFINITY);
+
+    for (origin, direction, max) in [
+        (origin.x, direction.x, size.x),
+        (origin.y, direction.y, size.y),
+    ] {
+        if direction == 0.0 {
+            if !(0.0..=max).contains(&origin) {
+                return None;
+            }
+        } else {
+            let (a, b) = (-origin / direction, (max - origin) / direction);
+            start = start.max(a.min(b));
+            end = end.min(a.max(b));
+        }
+    }
+
+    (end > start).then(|| vec![origin + direction * start, origin + direction * end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::point_in_polygon;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn horizontal_lines_span_the_canvas() {
+        let lines = hatch(vec2(10.0, 4.0), 1.0, 0.0);
+
+        assert_eq!(lines.len(), 5);
+        for (line, y) in lines.iter().zip([0.0, 1.0, 2.0, 3.0, 4.0]) {
+            assert_eq!(line, &vec![vec2(0.0, y), vec2(10.0, y)]);
+        }
+    }
+
+    #[test]
+    fn shape_lines_stay_inside() {
+        // Square with a notch cut out of the right side.
+        let polygon = [
+            vec2(0.0, 0.0),
+            vec2(4.0, 0.0),
+            vec2(4.0, 1.5),
+            vec2(2.0, 2.0),
+            vec2(4.0, 2.5),
+            vec2(4.0, 4.0),
+            vec2(0.0, 4.0),
+        ];
+
+        let lines = hatch_shape(&polygon, 1.0, 0.0);
+
+        assert_eq!(
+            lines,
+            vec![
+                vec![vec2(0.0, 0.0), vec2(4.0, 0.0)],
+                vec![vec2(0.0, 1.0), vec2(4.0, 1.0)],
+                vec![vec2(0.0, 2.0), vec2(2.0, 2.0)],
+                vec![vec2(0.0, 3.0), vec2(4.0, 3.0)],
+            ]
+        );
+        for line in hatch_shape(&polygon, 0.3, 30.0) {
+            assert!(point_in_polygon((line[0] + line[1]) / 2.0, &polygon));
+        }
+    }
+}
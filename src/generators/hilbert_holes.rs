@@ -0,0 +1,45 @@
+use rand::Rng;
+
+use crate::vec::{Float, Vec2};
+
+/// Knocks random holes in a curve, like the hilbert curve, for a worn look.
+/// Every point of the curve is the center of a base cell, and each cell is left out with
+/// a chance of `hole_probability`. The curve is split into separate lines around the holes.
+pub fn with_holes(curve: &[Vec2], hole_probability: Float, rng: &mut impl Rng) -> Vec<Vec<Vec2>> {
+    let mut paths = vec![];
+    let mut current = vec![];
+
+    for &point in curve {
+        if rng.gen::<Float>() < hole_probability {
+            paths.push(std::mem::take(&mut current));
+        } else {
+            current.push(point);
+        }
+    }
+    paths.push(current);
+
+    // A single cell between two holes has no line to draw.
+    paths.retain(|path| path.len() >= 2);
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec2;
+    use pretty_assertions::assert_eq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn probability_zero_is_the_plain_curve_and_one_is_empty() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let curve = crate::hilbert_curve(vec2(0.0, 0.0), vec2(100.0, 0.0), vec2(0.0, 100.0), 4);
+
+        assert_eq!(with_holes(&curve, 0.0, &mut rng), vec![curve.clone()]);
+        assert_eq!(with_holes(&curve, 1.0, &mut rng), Vec::<Vec<Vec2>>::new());
+
+        let worn = with_holes(&curve, 0.3, &mut rng);
+        assert!(worn.len() > 1);
+        assert!(worn.iter().map(Vec::len).sum::<usize>() < curve.len());
+    }
+}
@@ -0,0 +1,40 @@
+use crate::vec::Vec2;
+
+/// Makes a line look like a raised ridge: the line itself, a copy moved by `depth`, and
+/// a short segment from every point of the line to the same point of the copy. `depth`
+/// is the direction the ridge seems to stick out in, and how far.
+///
+/// Returns the line, then the copy, then the connecting segments.
+pub fn relief(line: &[Vec2], depth: Vec2) -> Vec<Vec<Vec2>> {
+    let copy: Vec<Vec2> = line.iter().map(|&p| p + depth).collect();
+    let connections = line.iter().zip(&copy).map(|(&a, &b)| vec![a, b]);
+
+    [line.to_vec(), copy.clone()]
+        .into_iter()
+        .chain(connections)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec2;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn line_copy_and_connections() {
+        let curve = crate::hilbert_curve(vec2(0.0, 0.0), vec2(10.0, 0.0), vec2(0.0, 10.0), 2);
+        let depth = vec2(1.0, -0.5);
+
+        let paths = relief(&curve, depth);
+
+        assert_eq!(paths.len(), 2 + curve.len());
+        assert_eq!(paths[0], curve);
+        for (original, moved) in curve.iter().zip(&paths[1]) {
+            assert_eq!(*moved, *original + depth);
+        }
+        for (connection, &point) in paths[2..].iter().zip(&curve) {
+            assert_eq!(connection, &vec![point, point + depth]);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+
+use crate::{
+    geometry::line_intersection,
+    vec::{consts::TAU, vec2, Float, Vec2},
+};
+
+/// Islamic star pattern, made with the polygons in contact method, on a grid of `cols`
+/// by `rows` square cells.
+///
+/// Every cell gets the largest regular polygon with `n` sides that fits in it, and an
+/// `n` pointed star inside that polygon. The star is made of the lines from the middle
+/// of every side to the middle of the side after the next. With `n` a multiple of 4 the
+/// polygons touch all sides of their cell and share sides with the neighbouring cells,
+/// so neighbouring stars meet in the middle of those sides, like the classic 8-fold
+/// rosette pattern. Shared sides are only drawn once. `n` is at least 5.
+pub fn islamic_star(size: Vec2, n: usize, cols: usize, rows: usize) -> Vec<Vec<Vec2>> {
+    let (n, cols, rows) = (n.max(5), cols.max(1), rows.max(1));
+    let cell = Float::min(size.x / cols as Float, size.y / rows as Float);
+    let offset = (size - vec2(cols as Float, rows as Float) * cell) / 2.0;
+
+    // How far the corners of a polygon with an inradius of 1 reach along the axes.
+    let step = TAU / n as Float;
+    let reach = (0..n)
+        .map(|i| Vec2::from_angle(step * (i as Float + 0.5)))
+        .map(|corner| corner.x.abs().max(corner.y.abs()))
+        .fold(0.0, Float::max)
+        / (step / 2.0).cos();
+    let inradius = cell / 2.0 / reach;
+
+    // Sides are compared rounded, because the same corner can come out slightly
+    // different from two neighbouring cells.
+    let key = |p: Vec2| ((p.x * 1e3).round() as i64, (p.y * 1e3).round() as i64);
+    let mut seen = HashSet::new();
+    let mut paths = vec![];
+
+    for row in 0..rows {
+        for column in 0..cols {
+            let center = offset + vec2(column as Float + 0.5, row as Float + 0.5) * cell;
+            let (sides, star) = rosette(center, inradius, n);
+            for side in sides {
+                let (a, b) = (key(side[0]), key(side[1]));
+                if seen.insert((a.min(b), a.max(b))) {
+                    paths.push(side);
+                }
+            }
+            paths.push(star);
+        }
+    }
+
+    paths
+}
+
+/// The sides of a regular polygon with `n` sides around `center`, at `inradius` from
+/// it, with the middle of the first side to the right, and the star inside it as a
+/// closed line.
+fn rosette(center: Vec2, inradius: Float, n: usize) -> (Vec<Vec<Vec2>>, Vec<Vec2>) {
+    let step = TAU / n as Float;
+    let circumradius = inradius / (step / 2.0).cos();
+    let middle = |i: usize| center + Vec2::from_angle(step * i as Float) * inradius;
+    let corner = |i: usize| center + Vec2::from_angle(step * (i as Float + 0.5)) * circumradius;
+
+    let sides = (0..n).map(|i| vec![corner(i), corner(i + 1)]).collect();
+
+    let mut star = vec![];
+    for i in 0..n {
+        star.push(middle(i));
+        // The point of the star towards the corner between this side and the next.
+        let point = line_intersection(
+            (middle(i), middle(i + 2)),
+            (middle(i + 1), middle(i + n - 1)),
+        );
+        star.extend(point);
+    }
+    star.push(middle(0));
+
+    (sides, star)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn every_cell_is_n_fold_symmetric() {
+        let size = vec2(60.0, 40.0);
+
+        let paths = islamic_star(size, 8, 3, 2);
+
+        // The octagons share a side with every neighbour.
+        assert_eq!(paths.len(), 6 * 8 - 7 + 6);
+
+        let center = vec2(30.0, 30.0);
+        let in_cell = |path: &Vec<Vec2>| {
+            path.iter()
+                .all(|p| (p.x - center.x).abs() <= 10.001 && (p.y - center.y).abs() <= 10.001)
+        };
+        let cell: Vec<&Vec<Vec2>> = paths.iter().filter(|path| in_cell(path)).collect();
+        let segments: Vec<(Vec2, Vec2)> = cell
+            .iter()
+            .flat_map(|path| path.windows(2).map(|pair| (pair[0], pair[1])))
+            .collect();
+        assert_eq!(segments.len(), 8 + 16);
+
+        let turn = |p: Vec2| center + (p - center).rotate(TAU / 8.0);
+        let close = |p: Vec2, q: Vec2| (p - q).len() < 1e-3;
+        for &(a, b) in &segments {
+            let (a, b) = (turn(a), turn(b));
+            assert!(segments
+                .iter()
+                .any(|&(c, d)| (close(a, c) && close(b, d)) || (close(a, d) && close(b, c))));
+        }
+    }
+
+    #[test]
+    fn polygons_without_a_multiple_of_4_sides_fit_in_the_canvas() {
+        let size = vec2(100.0, 100.0);
+
+        for n in [5, 6, 7, 9, 10] {
+            let paths = islamic_star(size, n, 4, 4);
+
+            assert_eq!(paths.len(), 4 * 4 * (n + 1));
+            let (min, max) = crate::paths::bounding_box(&paths).unwrap();
+            assert!(min.x >= -1e-4 && min.y >= -1e-4, "{n}: {min:?}");
+            assert!(
+                max.x <= 100.0 + 1e-4 && max.y <= 100.0 + 1e-4,
+                "{n}: {max:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+
+use crate::{
+    paths::fit_to_canvas,
+    vec::{vec2, Float, Vec2},
+};
+
+/// Corners of a cube, as seen in isometric view, relative to its front corner in the
+/// middle. In units of half an edge vertically, and half the width of a cube horizontally,
+/// so all corners of the grid have whole coordinates.
+const TOP: (i64, i64) = (0, -2);
+const UPPER_RIGHT: (i64, i64) = (1, -1);
+const LOWER_RIGHT: (i64, i64) = (1, 1);
+const BOTTOM: (i64, i64) = (0, 2);
+const LOWER_LEFT: (i64, i64) = (-1, 1);
+const UPPER_LEFT: (i64, i64) = (-1, -1);
+const CENTER: (i64, i64) = (0, 0);
+
+/// The edges of a single cube: the outline of the hexagon it looks like, and the three
+/// edges that meet at the front corner.
+const CUBE_EDGES: [((i64, i64), (i64, i64)); 9] = [
+    (TOP, UPPER_RIGHT),
+    (UPPER_RIGHT, LOWER_RIGHT),
+    (LOWER_RIGHT, BOTTOM),
+    (BOTTOM, LOWER_LEFT),
+    (LOWER_LEFT, UPPER_LEFT),
+    (UPPER_LEFT, TOP),
+    (CENTER, UPPER_RIGHT),
+    (CENTER, BOTTOM),
+    (CENTER, UPPER_LEFT),
+];
+
+/// Grid of isometric cubes, each showing its top, left and right face. Every other row
+/// is shifted by half a cube, so the cubes fit together without gaps. Edges shared by
+/// neighbouring cubes are only drawn once. Every edge is its own line.
+pub fn iso_grid(size: Vec2, cols: usize, rows: usize) -> Vec<Vec<Vec2>> {
+    let mut seen = HashSet::new();
+    let mut edges = vec![];
+
+    for row in 0..rows as i64 {
+        for col in 0..cols as i64 {
+            let center = (col * 2 + row % 2, row * 3);
+            for (a, b) in CUBE_EDGES {
+                let a = (center.0 + a.0, center.1 + a.1);
+                let b = (center.0 + b.0, center.1 + b.1);
+                if seen.insert((a.min(b), a.max(b))) {
+                    edges.push([a, b]);
+                }
+            }
+        }
+    }
+
+    let half_width = Float::sqrt(3.0) / 2.0;
+    let paths = edges
+        .into_iter()
+        .map(|edge| {
+            edge.into_iter()
+                .map(|(x, y)| vec2(x as Float * half_width, y as Float / 2.0))
+                .collect()
+        })
+        .collect();
+
+    fit_to_canvas(paths, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn shared_edges_are_merged() {
+        let size = vec2(100.0, 100.0);
+
+        assert_eq!(iso_grid(size, 1, 1).len(), 9);
+        // Side by side, the cubes share a vertical edge.
+        assert_eq!(iso_grid(size, 2, 1).len(), 17);
+        // Two shifted rows: 5 pairs of neighbours.
+        let edges = iso_grid(size, 2, 2);
+        assert_eq!(edges.len(), 4 * 9 - 5);
+
+        for (i, a) in edges.iter().enumerate() {
+            assert_eq!(a.len(), 2);
+            for b in &edges[i + 1..] {
+                let same = |p: Vec2, q: Vec2| (p - q).len() < 1e-3;
+                assert!(!(same(a[0], b[0]) && same(a[1], b[1])));
+                assert!(!(same(a[0], b[1]) && same(a[1], b[0])));
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{
+    lsystem::LSystem,
+    vec::{vec2, Float, Vec2},
+};
+
+/// A single open Koch curve across the whole width of the canvas, with its bumps pointing up.
+/// It is centered vertically.
+pub fn koch_line(size: Vec2, iterations: usize) -> Vec<Vec2> {
+    // Turning right first bulges upwards on screen, where y points down.
+    let system = LSystem {
+        axiom: "F".to_string(),
+        rules: vec![('F', "F-F++F-F".to_string())],
+        angle: 60.0,
+    };
+
+    let scale = size.x / (3 as Float).powi(iterations as i32);
+    // The highest bump is a sixth of the width times the square root of 3.
+    let height = size.x * Float::sqrt(3.0) / 6.0;
+    let start = vec2(0.0, (size.y + height) / 2.0);
+
+    system
+        .draw(iterations)
+        .into_iter()
+        .map(|p| start + p * scale)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn spans_the_width() {
+        let size = vec2(90.0, 50.0);
+
+        for iterations in 0..5 {
+            let points = koch_line(size, iterations);
+
+            assert_eq!(points.len(), 4usize.pow(iterations as u32) + 1);
+
+            let first = points[0];
+            let last = *points.last().unwrap();
+            assert!(first.x.abs() < 1e-4);
+            assert!((last.x - size.x).abs() < 1e-3);
+            assert!((first.y - last.y).abs() < 1e-3);
+            for point in &points {
+                assert!(point.y <= first.y + 1e-3 && point.y >= 0.0);
+            }
+        }
+    }
+}
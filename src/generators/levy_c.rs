@@ -0,0 +1,37 @@
+use crate::{lsystem::LSystem, paths::fit_to_canvas, vec::Vec2};
+
+/// Lévy C curve: every iteration replaces each segment by the two short sides of a right
+/// isosceles triangle on it, doubling the amount of segments.
+///
+/// The result is a single line, fit to the canvas.
+pub fn levy_c(size: Vec2, iterations: usize) -> Vec<Vec2> {
+    let system = LSystem {
+        axiom: "F".to_string(),
+        rules: vec![('F', "+F--F+".to_string())],
+        angle: 45.0,
+    };
+
+    fit_to_canvas(vec![system.draw(iterations)], size).remove(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec2;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn doubles_the_segments_and_fits_the_canvas() {
+        let size = vec2(120.0, 80.0);
+
+        for iterations in 1..10 {
+            let points = levy_c(size, iterations);
+
+            assert_eq!(points.len() - 1, 2usize.pow(iterations as u32));
+            for point in &points {
+                assert!(point.x >= -1e-3 && point.x <= size.x + 1e-3);
+                assert!(point.y >= -1e-3 && point.y <= size.y + 1e-3);
+            }
+        }
+    }
+}
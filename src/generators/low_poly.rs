@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+
+use crate::{
+    generators::hatch::hatch_shape,
+    vec::{vec2, Float, Vec2},
+};
+
+/// Spacing of the hatching of the triangles at the left and right of the canvas, as a
+/// fraction of the shortest side of the canvas.
+const HATCH_SPACING: (Float, Float) = (0.005, 0.03);
+
+/// Triangles that share their corners.
+pub struct Triangulation {
+    points: Vec<Vec2>,
+    /// Indices into `points`.
+    triangles: Vec<[usize; 3]>,
+}
+
+impl Triangulation {
+    /// The canvas split into two triangles, and every triangle `subdivisions` times into
+    /// four, by connecting the middles of its sides.
+    pub fn subdivided(size: Vec2, subdivisions: usize) -> Self {
+        let mut triangulation = Self {
+            points: vec![Vec2::ZERO, vec2(size.x, 0.0), size, vec2(0.0, size.y)],
+            triangles: vec![[0, 1, 2], [0, 2, 3]],
+        };
+
+        for _ in 0..subdivisions {
+            // Neighbouring triangles share the middle of their shared side.
+            let mut middles = HashMap::new();
+            let mut middle = |points: &mut Vec<Vec2>, a: usize, b: usize| {
+                *middles.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                    points.push((points[a] + points[b]) / 2.0);
+                    points.len() - 1
+                })
+            };
+
+            let mut triangles = vec![];
+            for [a, b, c] in std::mem::take(&mut triangulation.triangles) {
+                let points = &mut triangulation.points;
+                let (ab, bc, ca) = (
+                    middle(points, a, b),
+                    middle(points, b, c),
+                    middle(points, c, a),
+                );
+                triangles.extend([[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]]);
+            }
+            triangulation.triangles = triangles;
+        }
+
+        triangulation
+    }
+
+    /// Flips every side shared by two triangles with a chance of a half: the two triangles
+    /// are replaced by the two with the other diagonal of the four corners. Only done when
+    /// those four corners form a convex shape, and at most once per triangle.
+    pub fn flip_edges(&mut self, rng: &mut impl Rng) {
+        let mut flipped = vec![false; self.triangles.len()];
+
+        for (_, sharing) in self.sides() {
+            let [first, second] = sharing[..] else {
+                continue;
+            };
+            if flipped[first] || flipped[second] || !rng.gen_bool(0.5) {
+                continue;
+            }
+
+            let (a, b, c) = self.around_side(first, second);
+            let (_, _, d) = self.around_side(second, first);
+            // The new diagonal from c to d has to cross the old one from a to b.
+            let cross = |o: Vec2, p: Vec2, q: Vec2| (p - o).x * (q - o).y - (p - o).y * (q - o).x;
+            let [pa, pb, pc, pd] = [a, b, c, d].map(|i| self.points[i]);
+            let convex = cross(pc, pd, pa) * cross(pc, pd, pb) < 0.0
+                && cross(pa, pb, pc) * cross(pa, pb, pd) < 0.0;
+            if !convex {
+                continue;
+            }
+
+            self.triangles[first] = [c, a, d];
+            self.triangles[second] = [d, b, c];
+            flipped[first] = true;
+            flipped[second] = true;
+        }
+    }
+
+    /// Every side, with the triangles it belongs to, sorted so flipping is reproducible.
+    fn sides(&self) -> Vec<((usize, usize), Vec<usize>)> {
+        let mut sides: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (index, &[a, b, c]) in self.triangles.iter().enumerate() {
+            for (p, q) in [(a, b), (b, c), (c, a)] {
+                sides.entry((p.min(q), p.max(q))).or_default().push(index);
+            }
+        }
+        let mut sides: Vec<_> = sides.into_iter().collect();
+        sides.sort();
+        sides
+    }
+
+    /// The corners of the side that `triangle` shares with `other`, and its third corner.
+    fn around_side(&self, triangle: usize, other: usize) -> (usize, usize, usize) {
+        let (corners, other) = (self.triangles[triangle], self.triangles[other]);
+        let shared: Vec<usize> = corners.into_iter().filter(|i| other.contains(i)).collect();
+        let third = corners.into_iter().find(|i| !other.contains(i)).unwrap();
+        (shared[0], shared[1], third)
+    }
+
+    /// Every side once, as a line.
+    pub fn edges(&self) -> Vec<Vec<Vec2>> {
+        self.sides()
+            .into_iter()
+            .map(|((a, b), _)| vec![self.points[a], self.points[b]])
+            .collect()
+    }
+
+    /// Every triangle as a closed line.
+    pub fn triangles(&self) -> Vec<Vec<Vec2>> {
+        self.triangles
+            .iter()
+            .map(|&[a, b, c]| [a, b, c, a].map(|i| self.points[i]).to_vec())
+            .collect()
+    }
+}
+
+/// Low poly look: the canvas subdivided into triangles, with random sides flipped so the
+/// triangles don't all line up. Returns the sides of the triangles, every side once, and
+/// with `hatch`, the hatching of every triangle, from dense at the left of the canvas to
+/// sparse at the right.
+pub fn low_poly(
+    size: Vec2,
+    subdivisions: usize,
+    hatch: bool,
+    rng: &mut impl Rng,
+) -> (Vec<Vec<Vec2>>, Option<Vec<Vec<Vec2>>>) {
+    let mut triangulation = Triangulation::subdivided(size, subdivisions);
+    triangulation.flip_edges(rng);
+
+    let shortest = size.x.min(size.y);
+    let hatching = hatch.then(|| {
+        triangulation
+            .triangles()
+            .into_iter()
+            .flat_map(|triangle| {
+                let center = (triangle[0] + triangle[1] + triangle[2]) / 3.0;
+                let t = center.x / size.x;
+                let spacing =
+                    (HATCH_SPACING.0 + (HATCH_SPACING.1 - HATCH_SPACING.0) * t) * shortest;
+                hatch_shape(&triangle, spacing, 45.0)
+            })
+            .collect()
+    });
+
+    (triangulation.edges(), hatching)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn flips_keep_the_amount_of_triangles() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut triangulation = Triangulation::subdivided(vec2(80.0, 60.0), 3);
+        let before = triangulation.edges();
+
+        triangulation.flip_edges(&mut rng);
+
+        assert_eq!(triangulation.triangles().len(), 2 * 4usize.pow(3));
+        let after = triangulation.edges();
+        assert_eq!(after.len(), before.len());
+        assert_ne!(after, before);
+
+        // The triangles still cover the canvas without overlapping.
+        let area: Float = triangulation
+            .triangles()
+            .iter()
+            .map(|t| {
+                let (u, v) = (t[1] - t[0], t[2] - t[0]);
+                (u.x * v.y - u.y * v.x).abs() / 2.0
+            })
+            .sum();
+        assert!((area - 80.0 * 60.0).abs() < 1e-2);
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::VecDeque;
+
+use rand::Rng;
+
+use crate::vec::{vec2, Float, Vec2};
+
+/// A maze on a grid of square cells, with exactly one way between any two cells.
+pub struct Maze {
+    columns: usize,
+    rows: usize,
+    /// For every cell, whether there is a passage to the cell to the right of it.
+    open_right: Vec<bool>,
+    /// For every cell, whether there is a passage to the cell below it.
+    open_down: Vec<bool>,
+}
+
+impl Maze {
+    /// Carves the passages with a randomized depth first search, which gives long winding
+    /// corridors with few dead ends.
+    pub fn generate(columns: usize, rows: usize, rng: &mut impl Rng) -> Self {
+        let (columns, rows) = (columns.max(1), rows.max(1));
+        let mut maze = Self {
+            columns,
+            rows,
+            open_right: vec![false; columns * rows],
+            open_down: vec![false; columns * rows],
+        };
+
+        let mut visited = vec![false; columns * rows];
+        visited[0] = true;
+        let mut stack = vec![(0, 0)];
+
+        while let Some(&cell) = stack.last() {
+            let unvisited: Vec<(usize, usize)> = maze
+                .neighbours(cell)
+                .into_iter()
+                .filter(|&next| !visited[maze.index(next)])
+                .collect();
+            if unvisited.is_empty() {
+                stack.pop();
+                continue;
+            }
+
+            let next = unvisited[rng.gen_range(0..unvisited.len())];
+            maze.open(cell, next);
+            visited[maze.index(next)] = true;
+            stack.push(next);
+        }
+
+        maze
+    }
+
+    fn index(&self, (x, y): (usize, usize)) -> usize {
+        y * self.columns + x
+    }
+
+    /// The cells next to the cell, whether there is a wall in between or not.
+    fn neighbours(&self, (x, y): (usize, usize)) -> Vec<(usize, usize)> {
+        let mut neighbours = vec![];
+        if x > 0 {
+            neighbours.push((x - 1, y));
+        }
+        if x + 1 < self.columns {
+            neighbours.push((x + 1, y));
+        }
+        if y > 0 {
+            neighbours.push((x, y - 1));
+        }
+        if y + 1 < self.rows {
+            neighbours.push((x, y + 1));
+        }
+        neighbours
+    }
+
+    /// Removes the wall between two neighbouring cells.
+    fn open(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let (first, second) = (a.min(b), a.max(b));
+        let index = self.index(first);
+        if first.1 == second.1 {
+            self.open_right[index] = true;
+        } else {
+            self.open_down[index] = true;
+        }
+    }
+
+    fn is_open(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        let (first, second) = (a.min(b), a.max(b));
+        if first.1 == second.1 {
+            self.open_right[self.index(first)]
+        } else {
+            self.open_down[self.index(first)]
+        }
+    }
+
+    /// Size of a cell and the position of the top left corner of the maze, to fit it
+    /// centered on the canvas.
+    fn layout(&self, size: Vec2) -> (Float, Vec2) {
+        let cell = Float::min(size.x / self.columns as Float, size.y / self.rows as Float);
+        let offset = (size - vec2(self.columns as Float, self.rows as Float) * cell) / 2.0;
+        (cell, offset)
+    }
+
+    /// The walls of the maze. Walls that continue in a straight line are a single line.
+    /// There are openings in the top wall of the top left cell, where the maze starts,
+    /// and in the bottom wall of the bottom right cell, where it ends.
+    pub fn walls(&self, size: Vec2) -> Vec<Vec<Vec2>> {
+        let (cell, offset) = self.layout(size);
+        let point = |x: usize, y: usize| offset + vec2(x as Float, y as Float) * cell;
+        let mut walls = vec![];
+
+        // Goes along a grid line, turning every run of walls into a single line.
+        let mut runs =
+            |length: usize, has_wall: &dyn Fn(usize) -> bool, line: &dyn Fn(usize) -> Vec2| {
+                let mut start = None;
+                for i in 0..=length {
+                    match (start, i < length && has_wall(i)) {
+                        (None, true) => start = Some(i),
+                        (Some(first), false) => {
+                            walls.push(vec![line(first), line(i)]);
+                            start = None;
+                        }
+                        _ => {}
+                    }
+                }
+            };
+
+        for y in 0..=self.rows {
+            let has_wall = |x: usize| match y {
+                0 => x != 0,
+                _ if y == self.rows => x != self.columns - 1,
+                _ => !self.open_down[self.index((x, y - 1))],
+            };
+            runs(self.columns, &has_wall, &|x| point(x, y));
+        }
+        for x in 0..=self.columns {
+            let has_wall =
+                |y: usize| x == 0 || x == self.columns || !self.open_right[self.index((x - 1, y))];
+            runs(self.rows, &has_wall, &|y| point(x, y));
+        }
+
+        walls
+    }
+
+    /// The shortest way through the maze, from the top left cell to the bottom right
+    /// cell, found with a breadth first search.
+    pub fn solve(&self) -> Vec<(usize, usize)> {
+        let end = (self.columns - 1, self.rows - 1);
+        let mut previous: Vec<Option<(usize, usize)>> = vec![None; self.columns * self.rows];
+        let mut queue = VecDeque::from([(0, 0)]);
+
+        while let Some(cell) = queue.pop_front() {
+            if cell == end {
+                break;
+            }
+            for next in self.neighbours(cell) {
+                if next != (0, 0)
+                    && previous[self.index(next)].is_none()
+                    && self.is_open(cell, next)
+                {
+                    previous[self.index(next)] = Some(cell);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let mut cells = vec![end];
+        while let Some(before) = previous[self.index(*cells.last().unwrap())] {
+            cells.push(before);
+        }
+        cells.reverse();
+        cells
+    }
+
+    /// The solution as a line through the middle of the cells, from the opening at the
+    /// start to the opening at the end.
+    pub fn solution(&self, size: Vec2) -> Vec<Vec2> {
+        let (cell, offset) = self.layout(size);
+        let center =
+            |(x, y): (usize, usize)| offset + vec2(x as Float + 0.5, y as Float + 0.5) * cell;
+
+        let cells = self.solve();
+        let start = center(cells[0]) - vec2(0.0, cell / 2.0);
+        let end = center(*cells.last().unwrap()) + vec2(0.0, cell / 2.0);
+        [start]
+            .into_iter()
+            .chain(cells.into_iter().map(center))
+            .chain([end])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn solution_runs_through_passages_from_corner_to_corner() {
+        let mut rng = StdRng::seed_from_u64(3);
+
+        let maze = Maze::generate(12, 8, &mut rng);
+        let cells = maze.solve();
+
+        assert_eq!(cells[0], (0, 0));
+        assert_eq!(*cells.last().unwrap(), (11, 7));
+        for pair in cells.windows(2) {
+            assert!(maze.neighbours(pair[0]).contains(&pair[1]));
+            assert!(maze.is_open(pair[0], pair[1]));
+        }
+
+        // Every cell is reachable, with exactly one way between any two cells.
+        let passages = maze
+            .open_right
+            .iter()
+            .chain(&maze.open_down)
+            .filter(|&&open| open);
+        assert_eq!(passages.count(), 12 * 8 - 1);
+
+        let line = maze.solution(vec2(120.0, 80.0));
+        assert_eq!(line.len(), cells.len() + 2);
+        assert_eq!(line[0], vec2(5.0, 0.0));
+        assert_eq!(*line.last().unwrap(), vec2(115.0, 80.0));
+    }
+}
@@ -0,0 +1,37 @@
+pub mod adaptive_hilbert;
+pub mod blobs;
+pub mod cesaro;
+pub mod circuit;
+pub mod coastline;
+pub mod dla;
+pub mod field;
+pub mod flow;
+pub mod golden_spiral;
+pub mod gosper;
+pub mod harmonograph;
+pub mod hatch;
+pub mod hilbert_holes;
+pub mod hilbert_relief;
+pub mod islamic_star;
+pub mod iso_grid;
+pub mod koch_line;
+pub mod levy_c;
+pub mod low_poly;
+pub mod maze;
+pub mod motif;
+pub mod nested_circles;
+pub mod nested_polygons;
+pub mod pythagoras_tree;
+pub mod room_maze;
+pub mod shape;
+pub mod sierpinski_knopp;
+pub mod smith_labyrinth;
+pub mod snowflake;
+pub mod spiral_image;
+pub mod square_spiral;
+pub mod squircle_frames;
+pub mod string_art;
+pub mod t_square;
+pub mod tile_motif;
+pub mod vicsek;
+pub mod wave_collapse;
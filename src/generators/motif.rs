@@ -0,0 +1,117 @@
+use clap::ValueEnum;
+
+use crate::{
+    geometry::{path_normals, sample_along},
+    vec::{consts::TAU, vec2, Float, Vec2},
+};
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum MotifShape {
+    Circle,
+    Star,
+    Arrow,
+}
+
+impl MotifShape {
+    /// The shape with a size of 1, centered on the origin.
+    /// Shapes with a direction point along the positive x-axis.
+    pub fn points(&self) -> Vec<Vec2> {
+        match self {
+            MotifShape::Circle => (0..=32)
+                .map(|i| vec2(0.5, 0.0).rotate(TAU * i as Float / 32.0))
+                .collect(),
+            MotifShape::Star => (0..=10)
+                .map(|i| {
+                    let radius = if i % 2 == 0 { 0.5 } else { 0.2 };
+                    vec2(radius, 0.0).rotate(TAU * i as Float / 10.0)
+                })
+                .collect(),
+            MotifShape::Arrow => vec![vec2(-0.5, -0.3), vec2(0.5, 0.0), vec2(-0.5, 0.3)],
+        }
+    }
+}
+
+/// Places a copy of the motif every `spacing` units along the path, moved `offset` units
+/// to the side along the normal of the path (see `path_normals`). On a closed path the
+/// normal points outward, so the copies move out of the shape.
+/// Each copy is rotated so that its x-axis follows the direction of the path, also at
+/// the start of a closed path, where the normal is the average of the sides at the corner.
+pub fn place_along(path: &[Vec2], motif: &[Vec2], spacing: Float, offset: Float) -> Vec<Vec<Vec2>> {
+    sample_along(path, spacing)
+        .into_iter()
+        .zip(path_normals(path, spacing))
+        .map(|((position, direction), (_, normal))| {
+            let angle = direction.y.atan2(direction.x);
+            motif
+                .iter()
+                .map(|&point| position + normal * offset + point.rotate(angle))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn place_along_straight_line() {
+        let path = [vec2(0.0, 0.0), vec2(100.0, 0.0)];
+        let motif = [vec2(0.0, 0.0), vec2(1.0, 0.0)];
+
+        let placed = place_along(&path, &motif, 10.0, 0.0);
+
+        assert_eq!(placed.len(), 11);
+        for (index, copy) in placed.iter().enumerate() {
+            assert_eq!(copy[0], vec2(index as Float * 10.0, 0.0));
+        }
+    }
+
+    #[test]
+    fn place_along_follows_direction() {
+        let path = [vec2(0.0, 0.0), vec2(0.0, 10.0)];
+        let motif = [vec2(1.0, 0.0)];
+
+        let placed = place_along(&path, &motif, 10.0, 0.0);
+
+        assert!((placed[0][0] - vec2(0.0, 1.0)).len() < 1e-6);
+
+        // Moved to the left of the path, on a y-up canvas.
+        let placed = place_along(&path, &motif, 10.0, 2.0);
+        assert!((placed[0][0] - vec2(-2.0, 1.0)).len() < 1e-6);
+    }
+
+    #[test]
+    fn place_along_closed_path() {
+        // Counter-clockwise on a y-up canvas.
+        let square = [
+            vec2(0.0, 0.0),
+            vec2(10.0, 0.0),
+            vec2(10.0, 10.0),
+            vec2(0.0, 10.0),
+            vec2(0.0, 0.0),
+        ];
+        let motif = [vec2(1.0, 0.0)];
+
+        let placed = place_along(&square, &motif, 5.0, 0.0);
+
+        // Every copy points along the side it is on, also at the starting corner.
+        let expected = [
+            (0, vec2(1.0, 0.0)),
+            (1, vec2(6.0, 0.0)),
+            (3, vec2(10.0, 6.0)),
+            (5, vec2(4.0, 10.0)),
+            (7, vec2(0.0, 4.0)),
+        ];
+        for (index, expected) in expected {
+            let copy = placed[index][0];
+            assert!((copy - expected).len() < 1e-5, "{copy:?} != {expected:?}");
+        }
+
+        // Moved out of the square.
+        let placed = place_along(&square, &motif, 5.0, 2.0);
+        assert!((placed[1][0] - vec2(6.0, -2.0)).len() < 1e-5);
+        assert!((placed[3][0] - vec2(12.0, 6.0)).len() < 1e-5);
+    }
+}
@@ -0,0 +1,70 @@
+use rand::Rng;
+
+use crate::{
+    geometry::wobbly_circle,
+    vec::{consts::TAU, Float, Vec2},
+};
+
+/// Amount of segments in every circle.
+const CIRCLE_SEGMENTS: usize = 64;
+
+/// A circle as large as fits the canvas, with three equal circles inside it that touch
+/// it and each other, and so on for `depth` levels. Level `n` has 3 to the power `n`
+/// circles. `wobble` is passed on to `wobbly_circle`.
+pub fn nested_circles(
+    size: Vec2,
+    depth: usize,
+    wobble: Float,
+    rng: &mut impl Rng,
+) -> Vec<Vec<Vec2>> {
+    // The three inner circles touch each other when their centers form a triangle
+    // with sides of two radii.
+    let inner = 1.0 / (1.0 + 2.0 / Float::sqrt(3.0));
+
+    let mut level = vec![(size / 2.0, size.x.min(size.y) / 2.0)];
+    let mut circles = vec![];
+    for _ in 0..=depth {
+        let mut next = vec![];
+        for &(center, radius) in &level {
+            circles.push(wobbly_circle(center, radius, CIRCLE_SEGMENTS, wobble, rng));
+
+            let small = radius * inner;
+            for i in 0..3 {
+                // The first one at the top.
+                let direction = Vec2::from_angle(TAU * (i as Float / 3.0 - 0.25));
+                next.push((center + direction * (radius - small), small));
+            }
+        }
+        level = next;
+    }
+
+    circles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec2;
+    use pretty_assertions::assert_eq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn three_times_as_many_circles_every_level() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let size = vec2(100.0, 100.0);
+
+        for depth in 0..5 {
+            let circles = nested_circles(size, depth, 0.0, &mut rng);
+            let levels: usize = (0..=depth).map(|level| 3usize.pow(level as u32)).sum();
+            assert_eq!(circles.len(), levels);
+        }
+
+        // The inner circles touch the outer one.
+        let circles = nested_circles(size, 1, 0.0, &mut rng);
+        let top = circles[1]
+            .iter()
+            .map(|p| p.y)
+            .fold(Float::INFINITY, Float::min);
+        assert!(top.abs() < 1e-3);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::vec::{
+    consts::{PI, TAU},
+    Float, Vec2,
+};
+
+/// Concentric regular polygons with `sides` sides, each turned `rotation` degrees further
+/// than the one around it, and just small enough that its corners touch the sides of that
+/// one. Together they form the well known spiral of squares.
+///
+/// The outer polygon is as large as fits the canvas. Every polygon is a closed line.
+pub fn nested_polygons(size: Vec2, sides: usize, count: usize, rotation: Float) -> Vec<Vec<Vec2>> {
+    let sides = sides.max(3);
+    let half_corner = PI / sides as Float;
+    // Keep the turn within a single side, where the corners can touch the sides.
+    let turn = rotation.to_radians().rem_euclid(2.0 * half_corner);
+    // Distance from the center to the sides, divided by the distance to the corners.
+    let shrink = half_corner.cos() / (half_corner - turn).cos();
+
+    let center = size / 2.0;
+    let mut radius = size.x.min(size.y) / 2.0;
+    // Start with a flat side at the bottom.
+    let mut angle = PI / 2.0 - half_corner;
+
+    let mut polygons = vec![];
+    for _ in 0..count {
+        let mut polygon: Vec<Vec2> = (0..sides)
+            .map(|i| center + Vec2::from_angle(angle + TAU * i as Float / sides as Float) * radius)
+            .collect();
+        polygon.push(polygon[0]);
+        polygons.push(polygon);
+
+        radius *= shrink;
+        angle += turn;
+    }
+
+    polygons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geometry::point_segment_distance, vec::vec2};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn every_polygon_is_smaller_and_turned_further() {
+        let size = vec2(100.0, 80.0);
+        let center = size / 2.0;
+
+        let polygons = nested_polygons(size, 4, 12, 5.0);
+
+        assert_eq!(polygons.len(), 12);
+        let radius = |polygon: &[Vec2]| (polygon[0] - center).len();
+        let angle = |polygon: &[Vec2]| {
+            let corner = polygon[0] - center;
+            corner.y.atan2(corner.x)
+        };
+        assert!((radius(&polygons[0]) - 40.0).abs() < 1e-4);
+        for pair in polygons.windows(2) {
+            let (outer, inner) = (&pair[0], &pair[1]);
+            assert_eq!(inner.len(), 5);
+            assert!(radius(inner) < radius(outer));
+            assert!((angle(inner) - angle(outer) - (5.0 as Float).to_radians()).abs() < 1e-4);
+
+            // The corners of the inner polygon touch the sides of the outer one.
+            let on_side = outer
+                .windows(2)
+                .map(|side| point_segment_distance(inner[0], side[0], side[1]))
+                .fold(Float::INFINITY, Float::min);
+            assert!(on_side < 1e-3);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::vec::{vec2, Float, Vec2};
+
+/// Pythagoras tree: every square spawns two child squares on its top edge,
+/// which together with that edge form a right triangle.
+/// `angle` (in degrees) is the angle of the left child, 45 gives a symmetric tree.
+///
+/// The trunk is rooted at the bottom center of the canvas,
+/// and the tree is scaled to fit inside the canvas.
+pub fn pythagoras_tree(size: Vec2, depth: usize, angle: Float) -> Vec<Vec<Vec2>> {
+    let mut squares = vec![];
+    // Build the tree with a trunk of size 1, rooted at the origin.
+    add_square(
+        &mut squares,
+        vec2(-0.5, 0.0),
+        vec2(0.5, 0.0),
+        depth,
+        angle.to_radians(),
+    );
+
+    let mut max_x: Float = 0.0;
+    let mut min_y: Float = 0.0;
+    for point in squares.iter().flatten() {
+        max_x = max_x.max(point.x.abs());
+        min_y = min_y.min(point.y);
+    }
+
+    // Scale so that the widest side of the tree still fits when the trunk is centered.
+    let scale = (size.x / (2.0 * max_x)).min(size.y / -min_y);
+    let root = vec2(size.x / 2.0, size.y);
+
+    squares
+        .into_iter()
+        .map(|square| square.into_iter().map(|p| root + p * scale).collect())
+        .collect()
+}
+
+/// Adds the square standing on the base `a` to `b`, and its children.
+/// The square is built on the left side of the base, which is "up" when
+/// going from left to right.
+fn add_square(squares: &mut Vec<Vec<Vec2>>, a: Vec2, b: Vec2, depth: usize, angle: Float) {
+    let base = b - a;
+    let up = vec2(base.y, -base.x);
+
+    let top_left = a + up;
+    let top_right = b + up;
+
+    squares.push(vec![a, b, top_right, top_left, a]);
+
+    if depth == 0 {
+        return;
+    }
+
+    // The apex of the right triangle on top of the square.
+    let apex = top_left + base.rotate(-angle) * angle.cos();
+
+    add_square(squares, top_left, apex, depth - 1, angle);
+    add_square(squares, apex, top_right, depth - 1, angle);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn square_count() {
+        for depth in 0..6 {
+            let squares = pythagoras_tree(vec2(100.0, 100.0), depth, 45.0);
+
+            assert_eq!(squares.len(), 2usize.pow(depth as u32 + 1) - 1);
+        }
+    }
+}
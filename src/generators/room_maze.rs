@@ -0,0 +1,111 @@
+use crate::vec::{vec2, Float, Vec2};
+
+/// Width of a doorway, as a fraction of the side of a room.
+const DOOR_WIDTH: Float = 0.4;
+
+/// Dungeon map: the canvas split into four rooms, each of those again into four, and so
+/// on for `depth` levels. A single corridor goes through every room in the order of the
+/// hilbert curve, so there is a doorway between every room and the next one.
+///
+/// Returns the walls and the corridor, which goes through the middle of the rooms.
+pub fn room_maze(size: Vec2, depth: usize) -> (Vec<Vec<Vec2>>, Vec<Vec2>) {
+    let rooms = 1 << depth;
+    let room_size = vec2(size.x / rooms as Float, size.y / rooms as Float);
+
+    // On a grid with rooms of size 1, the hilbert curve goes through the middle of the rooms.
+    let order: Vec<(usize, usize)> = crate::hilbert_curve(
+        Vec2::ZERO,
+        vec2(rooms as Float, 0.0),
+        vec2(0.0, rooms as Float),
+        depth,
+    )
+    .into_iter()
+    .map(|p| (p.x as usize, p.y as usize))
+    .collect();
+
+    let mut doors = vec![];
+    for pair in order.windows(2) {
+        doors.push((pair[0].min(pair[1]), pair[0].max(pair[1])));
+    }
+    let has_door = |a: (usize, usize), b: (usize, usize)| doors.contains(&(a, b));
+
+    let corner = |x: Float, y: Float| vec2(x * room_size.x, y * room_size.y);
+    let door_start = (1.0 - DOOR_WIDTH) / 2.0;
+    let door_end = (1.0 + DOOR_WIDTH) / 2.0;
+
+    let mut walls = vec![vec![
+        corner(0.0, 0.0),
+        corner(rooms as Float, 0.0),
+        corner(rooms as Float, rooms as Float),
+        corner(0.0, rooms as Float),
+        corner(0.0, 0.0),
+    ]];
+
+    // Walls between the rooms, as long as possible, only interrupted by the doorways.
+    for line in 1..rooms {
+        let line_at = line as Float;
+        let mut vertical = vec![(line_at, 0.0)];
+        let mut horizontal = vec![(0.0, line_at)];
+        for cell in 0..rooms {
+            let (x, y) = (line_at, cell as Float);
+            if has_door((line - 1, cell), (line, cell)) {
+                vertical.extend([(x, y + door_start), (x, y + door_end)]);
+            }
+            if has_door((cell, line - 1), (cell, line)) {
+                horizontal.extend([(y + door_start, x), (y + door_end, x)]);
+            }
+        }
+        vertical.push((line_at, rooms as Float));
+        horizontal.push((rooms as Float, line_at));
+
+        for ends in [vertical, horizontal] {
+            for run in ends.chunks(2) {
+                walls.push(run.iter().map(|&(x, y)| corner(x, y)).collect());
+            }
+        }
+    }
+
+    let corridor = order
+        .into_iter()
+        .map(|(x, y)| corner(x as Float + 0.5, y as Float + 0.5))
+        .collect();
+
+    (walls, corridor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn corridor_visits_every_room_once() {
+        let size = vec2(80.0, 80.0);
+
+        let (walls, corridor) = room_maze(size, 3);
+
+        assert_eq!(corridor.len(), 64);
+        let mut rooms: Vec<(usize, usize)> = corridor
+            .iter()
+            .map(|p| ((p.x / 10.0) as usize, (p.y / 10.0) as usize))
+            .collect();
+        for pair in corridor.windows(2) {
+            assert!(((pair[1] - pair[0]).len() - 10.0).abs() < 1e-4);
+        }
+        rooms.sort();
+        rooms.dedup();
+        assert_eq!(rooms.len(), 64);
+
+        // Every step of the corridor goes through a doorway instead of a wall.
+        for pair in corridor.windows(2) {
+            let middle = (pair[0] + pair[1]) / 2.0;
+            for wall in &walls {
+                for segment in wall.windows(2) {
+                    let distance =
+                        crate::geometry::point_segment_distance(middle, segment[0], segment[1]);
+                    assert!(distance > 1.0);
+                }
+            }
+        }
+    }
+}
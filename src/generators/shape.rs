@@ -0,0 +1,86 @@
+use clap::ValueEnum;
+
+use crate::{
+    paths::fit_to_canvas,
+    vec::{consts::TAU, vec2, Float, Vec2},
+};
+
+/// Well known curves given by an equation.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum NamedShape {
+    Heart,
+    /// Lemniscate of Bernoulli.
+    Infinity,
+    /// Butterfly curve by Temple H. Fay.
+    Butterfly,
+    Cardioid,
+}
+
+impl NamedShape {
+    /// The point at `t`, with y pointing up like in the usual formulas.
+    fn point(&self, t: Float) -> Vec2 {
+        match self {
+            NamedShape::Heart => vec2(
+                16.0 * t.sin().powi(3),
+                13.0 * t.cos() - 5.0 * (2.0 * t).cos() - 2.0 * (3.0 * t).cos() - (4.0 * t).cos(),
+            ),
+            NamedShape::Infinity => {
+                let scale = 1.0 / (1.0 + t.sin().powi(2));
+                vec2(t.cos(), t.sin() * t.cos()) * scale
+            }
+            NamedShape::Butterfly => {
+                let radius = t.cos().exp() - 2.0 * (4.0 * t).cos() - (t / 12.0).sin().powi(5);
+                vec2(t.sin(), t.cos()) * radius
+            }
+            NamedShape::Cardioid => vec2(t.cos(), t.sin()) * (1.0 - t.cos()),
+        }
+    }
+
+    /// Range of `t` that goes around the curve once.
+    fn period(&self) -> Float {
+        match self {
+            NamedShape::Butterfly => 6.0 * TAU,
+            _ => TAU,
+        }
+    }
+}
+
+/// The named curve as a closed line of `samples` points spread evenly over its equation,
+/// as large as fits the canvas.
+pub fn shape(size: Vec2, name: NamedShape, samples: usize) -> Vec<Vec2> {
+    let samples = samples.max(3);
+    let mut points: Vec<Vec2> = (0..samples)
+        .map(|i| name.point(name.period() * i as Float / samples as Float))
+        // Y points down on the canvas.
+        .map(|p| vec2(p.x, -p.y))
+        .collect();
+    points.push(points[0]);
+
+    fit_to_canvas(vec![points], size).remove(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paths::bounding_box;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn every_shape_is_closed_and_fits_the_canvas() {
+        let size = vec2(100.0, 60.0);
+
+        for name in NamedShape::value_variants() {
+            let points = shape(size, *name, 500);
+
+            assert_eq!(points.len(), 501);
+            assert_eq!(points[0], *points.last().unwrap());
+            let (min, max) = bounding_box(std::slice::from_ref(&points)).unwrap();
+            assert!(min.x >= -1e-3 && min.y >= -1e-3, "{name:?}");
+            assert!(max.x <= size.x + 1e-3 && max.y <= size.y + 1e-3, "{name:?}");
+            // Touches two opposite sides of the canvas.
+            let fits_width = min.x.abs() < 1e-3 && (max.x - size.x).abs() < 1e-3;
+            let fits_height = min.y.abs() < 1e-3 && (max.y - size.y).abs() < 1e-3;
+            assert!(fits_width || fits_height, "{name:?}");
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::vec::{vec2, Vec2};
+
+/// Sierpiński–Knopp curve, a space-filling curve like the Hilbert curve, but filling
+/// a right isosceles triangle instead of a square.
+///
+/// The triangle is as large as fits the canvas, with its long side at the bottom and
+/// the curve running from its left to its right corner. Every iteration doubles the
+/// amount of points.
+pub fn sierpinski_knopp(size: Vec2, iterations: usize) -> Vec<Vec2> {
+    let [left, top, right] = bounding_triangle(size);
+    let mut points = vec![];
+    triangle_curve(left, top, right, iterations, &mut points);
+    points
+}
+
+/// The corners of the triangle that the curve fills.
+pub fn bounding_triangle(size: Vec2) -> [Vec2; 3] {
+    let width = size.x.min(size.y * 2.0);
+    let center = size / 2.0;
+    let bottom = center.y + width / 4.0;
+
+    [
+        vec2(center.x - width / 2.0, bottom),
+        vec2(center.x, bottom - width / 2.0),
+        vec2(center.x + width / 2.0, bottom),
+    ]
+}
+
+/// Fills the triangle with the right angle at `corner`, from the `start` side to the `end` side.
+/// Cutting the triangle in half from the right angle gives two smaller triangles of the
+/// same shape, that are filled one after the other.
+fn triangle_curve(start: Vec2, corner: Vec2, end: Vec2, n: usize, points: &mut Vec<Vec2>) {
+    if n == 0 {
+        points.push((start + corner + end) / 3.0);
+    } else {
+        let middle = (start + end) / 2.0;
+        triangle_curve(start, middle, corner, n - 1, points);
+        triangle_curve(corner, middle, end, n - 1, points);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::point_in_polygon;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn stays_within_the_triangle() {
+        let size = vec2(100.0, 80.0);
+        let triangle = bounding_triangle(size);
+
+        let points = sierpinski_knopp(size, 8);
+
+        assert_eq!(points.len(), 256);
+        for &point in &points {
+            assert!(point_in_polygon(point, &triangle));
+        }
+        // Neighbouring points are close together, the curve doesn't jump around.
+        for pair in points.windows(2) {
+            assert!((pair[1] - pair[0]).len() < 10.0);
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+
+use crate::vec::{
+    consts::{PI, TAU},
+    vec2, Float, Vec2,
+};
+
+/// Amount of straight segments in a quarter circle.
+const QUARTER_SEGMENTS: usize = 8;
+
+/// A point on the grid, in units of half a cell, so the corners and the middles of the
+/// sides of the cells all have whole coordinates.
+type Node = (i64, i64);
+
+/// A quarter, half or three quarter circle between two nodes.
+struct Arc {
+    from: Node,
+    to: Node,
+    points: Vec<Vec2>,
+}
+
+/// Labyrinth of Truchet tiles in the style of Smith: every cell has two quarter circles
+/// connecting the middles of its sides, around two opposite corners. Which two corners
+/// is chosen at random for every cell.
+///
+/// Around the edges of the grid, the ends of the quarter circles are connected in pairs
+/// by half circles, and by three quarter circles around the corners of the grid, so
+/// every line is a closed loop. There is a margin of half a cell around the grid for
+/// them. Every loop is a single closed line.
+pub fn smith_labyrinth(
+    size: Vec2,
+    columns: usize,
+    rows: usize,
+    rng: &mut impl Rng,
+) -> Vec<Vec<Vec2>> {
+    if columns == 0 || rows == 0 {
+        return vec![];
+    }
+    let (width, height) = (columns as i64 * 2, rows as i64 * 2);
+
+    let mut arcs = vec![];
+    for y in (0..height).step_by(2) {
+        for x in (0..width).step_by(2) {
+            let (top, right, bottom, left) =
+                ((x + 1, y), (x + 2, y + 1), (x + 1, y + 2), (x, y + 1));
+            let pairs = if rng.gen::<bool>() {
+                [((x, y), top, left), ((x + 2, y + 2), right, bottom)]
+            } else {
+                [((x + 2, y), top, right), ((x, y + 2), left, bottom)]
+            };
+            for (center, from, to) in pairs {
+                arcs.push(arc(center, from, to, |_| true));
+            }
+        }
+    }
+
+    // The ends at the edge of the grid, going around it clockwise from the top left.
+    let edge: Vec<Node> = (0..columns as i64)
+        .map(|i| (i * 2 + 1, 0))
+        .chain((0..rows as i64).map(|i| (width, i * 2 + 1)))
+        .chain((0..columns as i64).rev().map(|i| (i * 2 + 1, height)))
+        .chain((0..rows as i64).rev().map(|i| (0, i * 2 + 1)))
+        .collect();
+    let outside = |p: Vec2| p.x < 0.0 || p.y < 0.0 || p.x > width as Float || p.y > height as Float;
+    for i in (0..edge.len()).step_by(2) {
+        let (from, to) = (edge[i], edge[(i + 1) % edge.len()]);
+        // The grid corner between the two, either halfway along a side or at a corner.
+        let center = if from.0 == to.0 || from.1 == to.1 {
+            ((from.0 + to.0) / 2, (from.1 + to.1) / 2)
+        } else {
+            (
+                if from.0 % 2 == 0 { from.0 } else { to.0 },
+                if from.1 % 2 == 0 { from.1 } else { to.1 },
+            )
+        };
+        arcs.push(arc(center, from, to, outside));
+    }
+
+    let cell = Float::min(
+        size.x / (columns + 1) as Float,
+        size.y / (rows + 1) as Float,
+    );
+    let offset = (size - vec2(columns as Float, rows as Float) * cell) / 2.0;
+    loops(arcs)
+        .into_iter()
+        .map(|path| {
+            path.into_iter()
+                .map(|p| offset + p * (cell / 2.0))
+                .collect()
+        })
+        .collect()
+}
+
+/// Circle arc with a radius of 1 around `center`, from `from` to `to`. Of the two ways
+/// around, it takes the one with its middle `on_side`, or the shortest one when both are.
+fn arc(center: Node, from: Node, to: Node, on_side: impl Fn(Vec2) -> bool) -> Arc {
+    let center_point = vec2(center.0 as Float, center.1 as Float);
+    let angle = |(x, y): Node| ((y - center.1) as Float).atan2((x - center.0) as Float);
+    let start = angle(from);
+
+    let mut sweep = (angle(to) - start).rem_euclid(TAU);
+    if sweep > PI {
+        sweep -= TAU;
+    }
+    let longer = sweep - TAU * sweep.signum();
+    if !on_side(center_point + Vec2::from_angle(start + sweep / 2.0))
+        && on_side(center_point + Vec2::from_angle(start + longer / 2.0))
+    {
+        sweep = longer;
+    }
+
+    let segments = (QUARTER_SEGMENTS as Float * sweep.abs() / (PI / 2.0)).round() as usize;
+    let mut points: Vec<Vec2> = (0..segments)
+        .map(|i| center_point + Vec2::from_angle(start + sweep * i as Float / segments as Float))
+        .collect();
+    // End exactly on the node, so the arcs connect.
+    points.push(vec2(to.0 as Float, to.1 as Float));
+    points[0] = vec2(from.0 as Float, from.1 as Float);
+
+    Arc { from, to, points }
+}
+
+/// Joins the arcs into closed loops. Every node has to be the end of exactly two arcs.
+fn loops(arcs: Vec<Arc>) -> Vec<Vec<Vec2>> {
+    let mut ends: HashMap<Node, Vec<usize>> = HashMap::new();
+    for (index, arc) in arcs.iter().enumerate() {
+        ends.entry(arc.from).or_default().push(index);
+        ends.entry(arc.to).or_default().push(index);
+    }
+
+    let mut used = vec![false; arcs.len()];
+    let mut loops = vec![];
+    for first in 0..arcs.len() {
+        if used[first] {
+            continue;
+        }
+
+        let mut path = arcs[first].points.clone();
+        let mut current = first;
+        let mut node = arcs[first].to;
+        used[first] = true;
+        while let Some(&next) = ends[&node].iter().find(|&&arc| arc != current) {
+            if used[next] {
+                break;
+            }
+            used[next] = true;
+
+            let arc = &arcs[next];
+            if arc.from == node {
+                path.extend(&arc.points[1..]);
+                node = arc.to;
+            } else {
+                path.extend(arc.points.iter().rev().skip(1));
+                node = arc.from;
+            }
+            current = next;
+        }
+        loops.push(path);
+    }
+
+    loops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn every_line_is_a_closed_loop() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let size = vec2(120.0, 90.0);
+
+        let loops = smith_labyrinth(size, 7, 5, &mut rng);
+
+        assert!(loops.len() > 1);
+        let mut quarters = 0;
+        for path in &loops {
+            assert_eq!(path.first(), path.last());
+            for point in path {
+                assert!((0.0..=size.x).contains(&point.x) && (0.0..=size.y).contains(&point.y));
+            }
+            quarters += (path.len() - 1) / QUARTER_SEGMENTS;
+        }
+        // Two quarter circles in every cell. Around the edge, the pairs of ends go around
+        // the top right and bottom left corners with three quarter circles, and the other
+        // 10 pairs are connected by half circles.
+        assert_eq!(quarters, 7 * 5 * 2 + 10 * 2 + 2 * 3);
+    }
+}
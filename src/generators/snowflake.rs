@@ -0,0 +1,64 @@
+use crate::{
+    geometry::reflect,
+    lsystem::LSystem,
+    paths::radial_copies,
+    vec::{consts::TAU, Float, Vec2},
+};
+
+/// Snowflake with `arms` arms around the center of the canvas, as large as fits.
+///
+/// Every arm is a closed line: out from the center along a Koch curve, and back along
+/// its mirror image, so it has bumps on both sides.
+pub fn snowflake(size: Vec2, arms: usize, iterations: usize) -> Vec<Vec<Vec2>> {
+    let center = size / 2.0;
+    let radius = size.x.min(size.y) / 2.0;
+
+    let system = LSystem {
+        axiom: "F".to_string(),
+        rules: vec![('F', "F-F++F-F".to_string())],
+        angle: 60.0,
+    };
+    let scale = radius / (3 as Float).powi(iterations as i32);
+    // The first arm points up.
+    let direction = Vec2::from_angle(-TAU / 4.0);
+    let side: Vec<Vec2> = system
+        .draw(iterations)
+        .into_iter()
+        .map(|p| center + (p * scale).rotate(-TAU / 4.0))
+        .collect();
+
+    let mut arm = side.clone();
+    arm.extend(reflect(&side, center, direction).into_iter().rev().skip(1));
+
+    radial_copies(&[arm], center, arms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec2;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn six_arms_are_six_fold_symmetric() {
+        let size = vec2(100.0, 100.0);
+        let center = vec2(50.0, 50.0);
+
+        let arms = snowflake(size, 6, 3);
+
+        assert_eq!(arms.len(), 6);
+        for arm in &arms {
+            assert_eq!(arm.len(), 2 * 4usize.pow(3) + 1);
+            assert!((arm[0] - center).len() < 1e-4);
+            assert!((*arm.last().unwrap() - center).len() < 1e-4);
+        }
+
+        // The second set of copies is turned by 60°.
+        let turned = radial_copies(&arms, center, 6);
+        for path in &turned[6..12] {
+            let matches =
+                |other: &Vec<Vec2>| path.iter().zip(other).all(|(&p, &q)| (p - q).len() < 1e-3);
+            assert!(arms.iter().any(matches));
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use image::GrayImage;
+
+use crate::vec::{consts::TAU, Float, Vec2};
+
+/// Points per wave of the wiggle.
+const POINTS_PER_WAVE: Float = 8.0;
+
+/// A single Archimedean spiral from the center of the canvas outward, that wiggles where
+/// the image is dark. The wiggle goes in and out radially, and its amplitude follows the
+/// darkness of the image, so the spiral draws the image in a single line.
+///
+/// The waves of the wiggle are as long as the distance between the windings, and at their
+/// largest almost reach the neighbouring windings.
+/// The image is stretched to cover the whole canvas.
+pub fn spiral_image(size: Vec2, image: &GrayImage, turns: usize) -> Vec<Vec2> {
+    let center = size / 2.0;
+    let turns = turns.max(1) as Float;
+    let spacing = size.x.min(size.y) / 2.0 / turns;
+    let amplitude = spacing * 0.45;
+    let step = spacing / POINTS_PER_WAVE;
+
+    let mut points = vec![];
+    let mut angle: Float = 0.0;
+    // Distance travelled along the spiral, the wiggle is based on it so its waves
+    // are equally long everywhere.
+    let mut travelled: Float = 0.0;
+
+    while angle <= turns * TAU {
+        let radius = spacing * angle / TAU;
+        let direction = Vec2::from_angle(angle);
+
+        let darkness = 1.0 - brightness_at(image, size, center + direction * radius);
+        let wiggle = amplitude * darkness * (travelled / spacing * TAU).sin();
+        points.push(center + direction * (radius + wiggle));
+
+        // Close to the center the spiral is tight, don't take huge angular steps there.
+        angle += step / radius.max(spacing);
+        travelled += step;
+    }
+
+    points
+}
+
+/// Brightness from 0 to 1 of the pixel underneath the point on the canvas.
+fn brightness_at(image: &GrayImage, size: Vec2, point: Vec2) -> Float {
+    let (width, height) = image.dimensions();
+    let x = ((point.x / size.x * width as Float).max(0.0) as u32).min(width - 1);
+    let y = ((point.y / size.y * height as Float).max(0.0) as u32).min(height - 1);
+
+    image.get_pixel(x, y).0[0] as Float / 255.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec2;
+    use image::Luma;
+
+    #[test]
+    fn dark_areas_wiggle_more() {
+        let size = vec2(100.0, 100.0);
+        // Dark on the left, light on the right.
+        let image = GrayImage::from_fn(64, 64, |x, _| Luma([if x < 32 { 0 } else { 255 }]));
+
+        let points = spiral_image(size, &image, 10);
+
+        // How far the points move towards or away from the center, from one to the next.
+        // On a plain spiral that is tiny, the wiggles make it large.
+        let radial_movement = |left: bool| {
+            points
+                .windows(2)
+                .filter(|pair| (pair[0].x < 45.0) == left && (pair[0].x - 50.0).abs() > 5.0)
+                .map(|pair| ((pair[1] - size / 2.0).len() - (pair[0] - size / 2.0).len()).abs())
+                .sum::<Float>()
+        };
+
+        assert!(radial_movement(true) > radial_movement(false) * 5.0);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::vec::{vec2, Float, Vec2};
+
+/// A square spiral, winding outward from the center of the canvas as a single line.
+/// Every turn consists of 4 straight sides, and neighbouring windings are `spacing`
+/// apart. When the spiral doesn't fit the canvas, the spacing is reduced until it does.
+pub fn square_spiral(size: Vec2, turns: usize, spacing: Float) -> Vec<Vec2> {
+    // Right, down, left, up. Every two sides the length grows by one step.
+    let directions = [
+        vec2(1.0, 0.0),
+        vec2(0.0, 1.0),
+        vec2(-1.0, 0.0),
+        vec2(0.0, -1.0),
+    ];
+
+    let mut points = vec![Vec2::ZERO];
+    let mut position = Vec2::ZERO;
+    for side in 0..turns * 4 {
+        let length = (side / 2 + 1) as Float;
+        position = position + directions[side % 4] * length;
+        points.push(position);
+    }
+
+    let extent = points.iter().fold(Vec2::ZERO, |max, p| {
+        vec2(max.x.max(p.x.abs()), max.y.max(p.y.abs()))
+    });
+    let fitting = Float::min(
+        size.x / 2.0 / extent.x.max(1.0),
+        size.y / 2.0 / extent.y.max(1.0),
+    );
+    let scale = spacing.min(fitting);
+
+    let center = size / 2.0;
+    points.into_iter().map(|p| center + p * scale).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_continuously_within_the_canvas() {
+        let size = vec2(100.0, 60.0);
+        let points = square_spiral(size, 20, 5.0);
+
+        assert_eq!(points.len(), 20 * 4 + 1);
+        assert_eq!(points[0], size / 2.0);
+
+        let mut previous_distance = 0.0;
+        for (index, &point) in points.iter().enumerate() {
+            assert!(point.x >= 0.0 && point.x <= size.x);
+            assert!(point.y >= 0.0 && point.y <= size.y);
+
+            let from_center = point - size / 2.0;
+            let distance = from_center.x.abs().max(from_center.y.abs());
+            assert!(distance >= previous_distance);
+            previous_distance = distance;
+
+            // Every side is horizontal or vertical, without gaps or jumps.
+            if index > 0 {
+                let side = point - points[index - 1];
+                assert!(side.x == 0.0 || side.y == 0.0);
+                assert!(side.len() > 0.0);
+            }
+        }
+    }
+}
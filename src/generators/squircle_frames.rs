@@ -0,0 +1,55 @@
+use crate::{
+    geometry::superellipse,
+    vec::{vec2, Float, Vec2},
+};
+
+/// Amount of segments in every frame.
+const SEGMENTS: usize = 256;
+
+/// Nested superellipses with exponent `n`, `spacing` apart, as closed lines. The outer one
+/// touches the sides of the canvas. With an `n` around 4 they look like squircles, rounded
+/// squares that make nice frames.
+///
+/// Frames that would have no size left are not drawn, so with a large `spacing` there can
+/// be fewer than `count` frames.
+pub fn squircle_frames(size: Vec2, count: usize, n: Float, spacing: Float) -> Vec<Vec<Vec2>> {
+    let center = size / 2.0;
+
+    (0..count)
+        .map(|i| center - vec2(1.0, 1.0) * (i as Float * spacing))
+        .take_while(|radii| radii.x > 0.0 && radii.y > 0.0)
+        .map(|radii| superellipse(center, radii, n, SEGMENTS))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paths::bounding_box;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn every_frame_is_inside_the_one_before() {
+        let size = vec2(100.0, 60.0);
+
+        let frames = squircle_frames(size, 5, 4.0, 3.0);
+
+        assert_eq!(frames.len(), 5);
+        let boxes: Vec<(Vec2, Vec2)> = frames
+            .iter()
+            .map(|frame| bounding_box(std::slice::from_ref(frame)).unwrap())
+            .collect();
+        assert_eq!(boxes[0], (vec2(0.0, 0.0), vec2(100.0, 60.0)));
+        for (frame, (min, max)) in frames.iter().zip(&boxes) {
+            assert_eq!(frame.first(), frame.last());
+            assert!(min.x >= 0.0 && min.y >= 0.0 && max.x <= size.x && max.y <= size.y);
+        }
+        for pair in boxes.windows(2) {
+            let ((outer_min, outer_max), (inner_min, inner_max)) = (pair[0], pair[1]);
+            assert!((inner_min - outer_min - vec2(3.0, 3.0)).len() < 1e-4);
+            assert!((outer_max - inner_max - vec2(3.0, 3.0)).len() < 1e-4);
+        }
+
+        assert_eq!(squircle_frames(size, 50, 4.0, 3.0).len(), 10);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    geometry,
+    vec::{consts::TAU, vec2, Float, Vec2},
+};
+
+/// String art: `curves` families of straight strings around the center of the canvas,
+/// like the spokes of a wheel. Every family is stretched between two neighbouring spokes,
+/// and together the strings of a family seem to form a curve. Spokes only enclose a
+/// corner for 3 or more families, a single family is stretched between the left and the
+/// bottom side of the largest square that fits on the canvas instead. 2 families give
+/// nothing, as every string would lie on the same straight line.
+///
+/// With `envelope`, that curve is drawn instead of the strings, as a line for every family.
+pub fn string_art(size: Vec2, pins: usize, curves: usize, envelope: bool) -> Vec<Vec<Vec2>> {
+    let center = size / 2.0;
+    let radius = size.x.min(size.y) / 2.0;
+    let spoke = |k: usize| {
+        // The first spoke points up.
+        center + Vec2::from_angle(TAU * k as Float / curves as Float - TAU / 4.0) * radius
+    };
+
+    let families: Vec<Vec<Vec<Vec2>>> = match curves {
+        1 => {
+            let corner = center + vec2(-radius, radius);
+            vec![string_family(
+                corner,
+                center + vec2(-radius, -radius),
+                center + vec2(radius, radius),
+                pins,
+            )]
+        }
+        2 => vec![],
+        _ => (0..curves)
+            .map(|k| string_family(center, spoke(k), spoke(k + 1), pins))
+            .collect(),
+    };
+    if envelope {
+        families
+            .iter()
+            .map(|strings| geometry::envelope(strings))
+            .filter(|curve| curve.len() >= 2)
+            .collect()
+    } else {
+        families.into_iter().flatten().collect()
+    }
+}
+
+/// Strings between `pins` pins evenly spread along the line from `corner` to `a`, and as
+/// many along the line from `corner` to `b`. The pin furthest from the corner on one
+/// line is connected to the closest one on the other, and so on, so the strings outline
+/// a curve from `a` to `b` that bends towards the corner.
+pub fn string_family(corner: Vec2, a: Vec2, b: Vec2, pins: usize) -> Vec<Vec<Vec2>> {
+    if pins < 2 {
+        return vec![];
+    }
+
+    (0..pins)
+        .map(|i| {
+            let t = i as Float / (pins - 1) as Float;
+            vec![corner + (a - corner) * t, corner + (b - corner) * (1.0 - t)]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn strings_touch_the_curve() {
+        let (corner, a, b) = (vec2(0.0, 0.0), vec2(10.0, 0.0), vec2(2.0, 8.0));
+
+        let strings = string_family(corner, a, b, 11);
+
+        assert_eq!(strings.len(), 11);
+        assert_eq!(strings[0], vec![corner, b]);
+        assert_eq!(strings[10], vec![a, corner]);
+        // Every string is a tangent of the quadratic bezier curve from `b` to `a`, with
+        // the corner as control point, and touches it as far along as the string is
+        // along the lines.
+        for (i, string) in strings.iter().enumerate() {
+            let t = i as Float / 10.0;
+            let touching = string[1] + (string[0] - string[1]) * t;
+            let curve = b * (1.0 - t).powi(2) + corner * (2.0 * t * (1.0 - t)) + a * t.powi(2);
+            assert!((touching - curve).len() < 1e-4);
+        }
+
+        assert_eq!(string_art(vec2(100.0, 100.0), 20, 4, false).len(), 4 * 20);
+        assert_eq!(string_art(vec2(100.0, 100.0), 20, 4, true).len(), 4);
+    }
+
+    #[test]
+    fn single_family_between_perpendicular_sides() {
+        let size = vec2(120.0, 100.0);
+        let (corner, a, b) = (vec2(10.0, 100.0), vec2(10.0, 0.0), vec2(110.0, 100.0));
+
+        let strings = string_art(size, 11, 1, false);
+
+        assert_eq!(strings, string_family(corner, a, b, 11));
+        // The curve bends towards the corner, instead of lying on a single line.
+        let curve = string_art(size, 20, 1, true);
+        assert_eq!(curve.len(), 1);
+        let middle = (a + corner * 2.0 + b) / 4.0;
+        let closest = curve[0]
+            .iter()
+            .map(|&p| (p - middle).len())
+            .fold(Float::INFINITY, Float::min);
+        assert!(closest < 0.5, "{closest}");
+
+        assert!(string_art(size, 11, 2, false).is_empty());
+    }
+}
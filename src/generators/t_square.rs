@@ -0,0 +1,78 @@
+use crate::vec::{vec2, Float, Vec2};
+
+/// T-square fractal: a square with a half sized square centered on each of its corners,
+/// which in turn get smaller squares on their free corners, and so on.
+///
+/// Every square is drawn as a closed outline. The squares overlap, but never share an
+/// edge, since every square is centered on a corner of its parent.
+pub fn t_square(size: Vec2, iterations: usize) -> Vec<Vec<Vec2>> {
+    squares(size, iterations)
+        .into_iter()
+        .map(|(center, half)| {
+            let corners = [
+                center + vec2(-half, -half),
+                center + vec2(half, -half),
+                center + vec2(half, half),
+                center + vec2(-half, half),
+            ];
+            vec![corners[0], corners[1], corners[2], corners[3], corners[0]]
+        })
+        .collect()
+}
+
+/// Center and half of the side of every square, the largest first.
+/// The first square is half as wide as the canvas, so the whole fractal just fits.
+fn squares(size: Vec2, iterations: usize) -> Vec<(Vec2, Float)> {
+    let corners = [
+        vec2(-1.0, -1.0),
+        vec2(1.0, -1.0),
+        vec2(1.0, 1.0),
+        vec2(-1.0, 1.0),
+    ];
+
+    let mut squares = vec![(size / 2.0, size.x.min(size.y) / 4.0)];
+    // The newest squares, with the corner that their parent sits on.
+    let mut generation: Vec<(Vec2, Float, Option<Vec2>)> = vec![(squares[0].0, squares[0].1, None)];
+
+    for _ in 0..iterations {
+        let mut next = vec![];
+        for &(center, half, parent) in &generation {
+            for &corner in &corners {
+                if Some(corner) == parent {
+                    continue;
+                }
+                let child = (center + corner * half, half / 2.0);
+                squares.push(child);
+                next.push((child.0, child.1, Some(-corner)));
+            }
+        }
+        generation = next;
+    }
+
+    squares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn square_count_per_iteration() {
+        // One square, then four around it, and after that three on every new square.
+        let expected = [1, 5, 17, 53, 161];
+
+        for (iterations, &count) in expected.iter().enumerate() {
+            let size = vec2(100.0, 100.0);
+            assert_eq!(squares(size, iterations).len(), count);
+
+            let paths = t_square(size, iterations);
+            assert_eq!(paths.len(), count);
+            let edges: usize = paths.iter().map(|path| path.len() - 1).sum();
+            assert_eq!(edges, count * 4);
+            for point in paths.iter().flatten() {
+                assert!(point.x > 0.0 && point.x < 100.0 && point.y > 0.0 && point.y < 100.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use rand::Rng;
+
+use crate::{
+    paths::fit_to_canvas,
+    vec::{vec2, Float, Vec2},
+};
+
+/// Repeats the motif in a grid of `columns` by `rows` cells. The motif is scaled to fit
+/// a cell and centered in it.
+///
+/// Every copy is rotated around the center of its cell by a random angle of up to
+/// `rotation` degrees either way, and moved by up to `jitter` along both axes.
+pub fn tile_motif(
+    size: Vec2,
+    motif: Vec<Vec<Vec2>>,
+    columns: usize,
+    rows: usize,
+    rotation: Float,
+    jitter: Float,
+    rng: &mut impl Rng,
+) -> Vec<Vec<Vec2>> {
+    if columns == 0 || rows == 0 {
+        return vec![];
+    }
+
+    let cell = vec2(size.x / columns as Float, size.y / rows as Float);
+    let motif = fit_to_canvas(motif, cell);
+
+    let mut paths = vec![];
+    for row in 0..rows {
+        for column in 0..columns {
+            let origin = vec2(column as Float * cell.x, row as Float * cell.y);
+            let angle = random_up_to(rng, rotation).to_radians();
+            let offset = vec2(random_up_to(rng, jitter), random_up_to(rng, jitter));
+
+            paths.extend(motif.iter().map(|path| {
+                path.iter()
+                    .map(|&p| origin + cell / 2.0 + (p - cell / 2.0).rotate(angle) + offset)
+                    .collect()
+            }));
+        }
+    }
+
+    paths
+}
+
+/// A random value between `-max` and `max`.
+fn random_up_to(rng: &mut impl Rng, max: Float) -> Float {
+    if max > 0.0 {
+        rng.gen_range(-max..=max)
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn copies_in_a_grid() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let motif = vec![vec![vec2(0.0, 0.0), vec2(2.0, 2.0)]];
+
+        let paths = tile_motif(vec2(30.0, 20.0), motif, 3, 2, 0.0, 0.0, &mut rng);
+
+        assert_eq!(paths.len(), 6);
+        for row in 0..2 {
+            for column in 0..3 {
+                let origin = vec2(column as Float * 10.0, row as Float * 10.0);
+                assert_eq!(
+                    paths[row * 3 + column],
+                    vec![origin, origin + vec2(10.0, 10.0)]
+                );
+            }
+        }
+    }
+}
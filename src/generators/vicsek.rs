@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+
+use crate::{
+    paths::fit_to_canvas,
+    vec::{vec2, Float, Vec2},
+};
+
+/// Which of the 3 by 3 sub squares are kept, in the plus shaped variant.
+const PLUS: [(i64, i64); 5] = [(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)];
+/// Which of the 3 by 3 sub squares are kept, in the saltire (X shaped) variant.
+const SALTIRE: [(i64, i64); 5] = [(0, 0), (2, 0), (1, 1), (0, 2), (2, 2)];
+
+/// Vicsek fractal: a square is split into 3 by 3 smaller squares, of which only the
+/// middle one and either the four at the sides (plus shape) or the four at the corners
+/// (`cross`, X shape) are kept, and so on for every kept square.
+///
+/// Draws the outlines of the remaining squares. Edges shared by neighbouring squares
+/// are only drawn once. Every edge is its own line.
+pub fn vicsek(size: Vec2, iterations: usize, cross: bool) -> Vec<Vec<Vec2>> {
+    let mut seen = HashSet::new();
+    let mut edges = vec![];
+
+    for (x, y) in vicsek_squares(iterations, cross) {
+        let corners = [(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)];
+        for i in 0..4 {
+            let (a, b) = (corners[i], corners[(i + 1) % 4]);
+            if seen.insert((a.min(b), a.max(b))) {
+                edges.push([a, b]);
+            }
+        }
+    }
+
+    let paths = edges
+        .into_iter()
+        .map(|edge| {
+            edge.into_iter()
+                .map(|(x, y)| vec2(x as Float, y as Float))
+                .collect()
+        })
+        .collect();
+
+    fit_to_canvas(paths, size)
+}
+
+/// The top left corners of the squares that are left after the iterations, on a grid
+/// of 3 to the power `iterations` squares wide.
+fn vicsek_squares(iterations: usize, cross: bool) -> Vec<(i64, i64)> {
+    let kept = if cross { SALTIRE } else { PLUS };
+
+    let mut squares = vec![(0, 0)];
+    for _ in 0..iterations {
+        squares = squares
+            .into_iter()
+            .flat_map(|(x, y)| kept.map(|(dx, dy)| (x * 3 + dx, y * 3 + dy)))
+            .collect();
+    }
+    squares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn five_squares_for_every_square() {
+        for cross in [false, true] {
+            for iterations in 0..4 {
+                let squares = vicsek_squares(iterations, cross);
+                assert_eq!(squares.len(), 5usize.pow(iterations as u32));
+                assert_eq!(squares.iter().collect::<HashSet<_>>().len(), squares.len());
+            }
+        }
+
+        let size = vec2(100.0, 100.0);
+        assert_eq!(vicsek(size, 0, false).len(), 4);
+        // The middle square shares an edge with each of the others.
+        assert_eq!(vicsek(size, 1, false).len(), 5 * 4 - 4);
+        // The corner squares only touch the middle one at a corner.
+        assert_eq!(vicsek(size, 1, true).len(), 5 * 4);
+    }
+}
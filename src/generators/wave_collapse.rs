@@ -0,0 +1,220 @@
+use rand::Rng;
+
+use crate::vec::{
+    consts::{PI, TAU},
+    vec2, Float, Vec2,
+};
+
+/// Amount of straight segments in a quarter circle.
+const QUARTER_SEGMENTS: usize = 8;
+/// How often the collapse is started over when it runs into a cell without options.
+const ATTEMPTS: usize = 100;
+
+/// The sides of a cell, in the order of the sockets of a tile.
+const SIDES: [(i64, i64); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Whether a line leaves the tile through the middle of its top, right, bottom and
+/// left side.
+type Tile = [bool; 4];
+
+/// All tiles with none, or two or more sockets: empty, straight, corners, T-junctions and
+/// the cross. Tiles with a single socket would leave loose line ends.
+fn tiles() -> Vec<Tile> {
+    (0..16u8)
+        .map(|bits| [0, 1, 2, 3].map(|side| bits & (1 << side) != 0))
+        .filter(|tile| tile.iter().filter(|&&socket| socket).count() != 1)
+        .collect()
+}
+
+/// Minimal wave function collapse on a grid of `columns` by `rows` cells. Every cell
+/// starts out with all tiles as options. Over and over, one of the cells with the fewest
+/// options left is fixed to one of them at random, and the options of the other cells are
+/// narrowed down to the tiles that fit their neighbours. Neighbouring tiles always match:
+/// a line leaving one cell enters the next. No lines leave the grid.
+///
+/// Returns the tile of every cell, row by row, or `None` when every attempt ran into a
+/// cell where no tile fits.
+pub fn collapse(columns: usize, rows: usize, rng: &mut impl Rng) -> Option<Vec<Tile>> {
+    let tiles = tiles();
+    let (width, height) = (columns as i64, rows as i64);
+    let index = |(x, y): (i64, i64)| (y * width + x) as usize;
+    let inside = |(x, y): (i64, i64)| (0..width).contains(&x) && (0..height).contains(&y);
+
+    'attempt: for _ in 0..ATTEMPTS {
+        // The options of every cell, as indices into `tiles`. Tiles with lines leaving
+        // the grid are left out from the start.
+        let mut options: Vec<Vec<usize>> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|cell| {
+                (0..tiles.len())
+                    .filter(|&tile| {
+                        SIDES.iter().zip(tiles[tile]).all(|(&(dx, dy), socket)| {
+                            !socket || inside((cell.0 + dx, cell.1 + dy))
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        while let Some(fewest) = options
+            .iter()
+            .map(Vec::len)
+            .filter(|&count| count > 1)
+            .min()
+        {
+            let undecided: Vec<usize> = (0..options.len())
+                .filter(|&cell| options[cell].len() == fewest)
+                .collect();
+            let cell = undecided[rng.gen_range(0..undecided.len())];
+            let tile = options[cell][rng.gen_range(0..fewest)];
+            options[cell] = vec![tile];
+
+            // Narrow down the neighbours, and their neighbours, as long as anything changes.
+            let mut changed = vec![(cell as i64 % width, cell as i64 / width)];
+            while let Some(cell) = changed.pop() {
+                for (side, &(dx, dy)) in SIDES.iter().enumerate() {
+                    let neighbour = (cell.0 + dx, cell.1 + dy);
+                    if !inside(neighbour) {
+                        continue;
+                    }
+                    // The neighbour sees this side from the opposite direction.
+                    let possible = |socket: bool| {
+                        options[index(cell)]
+                            .iter()
+                            .any(|&tile| tiles[tile][side] == socket)
+                    };
+                    let (with, without) = (possible(true), possible(false));
+                    let before = options[index(neighbour)].len();
+                    options[index(neighbour)].retain(|&tile| {
+                        if tiles[tile][(side + 2) % 4] {
+                            with
+                        } else {
+                            without
+                        }
+                    });
+                    match options[index(neighbour)].len() {
+                        0 => continue 'attempt,
+                        after if after < before => changed.push(neighbour),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        if options.iter().all(|options| options.len() == 1) {
+            return Some(
+                options
+                    .into_iter()
+                    .map(|options| tiles[options[0]])
+                    .collect(),
+            );
+        }
+    }
+
+    None
+}
+
+/// Pattern of line tiles that connect across the cells, laid out by wave function
+/// collapse (see `collapse`), on a grid of `columns` by `rows` square cells centered on
+/// the canvas. Two sockets at neighbouring sides are connected by a quarter circle, all
+/// others by straight lines through the middle of the cell.
+pub fn wave_collapse(
+    size: Vec2,
+    columns: usize,
+    rows: usize,
+    rng: &mut impl Rng,
+) -> Vec<Vec<Vec2>> {
+    if columns == 0 || rows == 0 {
+        return vec![];
+    }
+    let Some(tiles) = collapse(columns, rows, rng) else {
+        return vec![];
+    };
+
+    let cell = Float::min(size.x / columns as Float, size.y / rows as Float);
+    let offset = (size - vec2(columns as Float, rows as Float) * cell) / 2.0;
+
+    let mut paths = vec![];
+    for (index, tile) in tiles.into_iter().enumerate() {
+        let (x, y) = ((index % columns) as Float, (index / columns) as Float);
+        let center = offset + vec2(x + 0.5, y + 0.5) * cell;
+        let socket = |side: usize| {
+            let (dx, dy) = SIDES[side];
+            center + vec2(dx as Float, dy as Float) * (cell / 2.0)
+        };
+
+        let sockets: Vec<usize> = (0..4).filter(|&side| tile[side]).collect();
+        if let [first, second] = sockets[..] {
+            if second - first != 2 {
+                // Around the corner between the two sides.
+                let corner = socket(first) + socket(second) - center;
+                let angle = |p: Vec2| (p - corner).y.atan2((p - corner).x);
+                let (start, end) = (angle(socket(first)), angle(socket(second)));
+                // The short way around, a quarter turn.
+                let turn = (end - start + PI).rem_euclid(TAU) - PI;
+                paths.push(
+                    (0..=QUARTER_SEGMENTS)
+                        .map(|i| {
+                            let angle = start + turn * i as Float / QUARTER_SEGMENTS as Float;
+                            corner + Vec2::from_angle(angle) * (cell / 2.0)
+                        })
+                        .collect(),
+                );
+                continue;
+            }
+        }
+
+        for side in sockets {
+            let opposite = (side + 2) % 4;
+            if !tile[opposite] {
+                paths.push(vec![socket(side), center]);
+            } else if side < opposite {
+                paths.push(vec![socket(side), socket(opposite)]);
+            }
+        }
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn neighbouring_tiles_connect() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (columns, rows) = (12, 9);
+
+        let tiles = collapse(columns, rows, &mut rng).unwrap();
+
+        assert_eq!(tiles.len(), columns * rows);
+        let tile = |x: usize, y: usize| tiles[y * columns + x];
+        for y in 0..rows {
+            for x in 0..columns {
+                let [top, right, bottom, left] = tile(x, y);
+                assert_eq!(right, x + 1 < columns && tile(x + 1, y)[3]);
+                assert_eq!(bottom, y + 1 < rows && tile(x, y + 1)[0]);
+                assert!(!(top && y == 0 || left && x == 0));
+            }
+        }
+        assert!(tiles.iter().any(|tile| tile.contains(&true)));
+
+        // Every line end on a side of a cell meets a line end of the neighbouring cell.
+        let paths = wave_collapse(vec2(120.0, 90.0), columns, rows, &mut rng);
+        let ends: Vec<Vec2> = paths
+            .iter()
+            .flat_map(|path| [path[0], *path.last().unwrap()])
+            .collect();
+        for &end in &ends {
+            let on_grid = |v: Float| ((v / 10.0).round() * 10.0 - v).abs() < 1e-3;
+            let on_side = on_grid(end.x) || on_grid(end.y);
+            if on_side {
+                let meeting = ends.iter().filter(|&&other| (other - end).len() < 1e-3);
+                assert_eq!(meeting.count(), 2, "{end:?}");
+            }
+        }
+    }
+}
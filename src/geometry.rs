@@ -0,0 +1,881 @@
+use itertools::Itertools;
+
+use rand::Rng;
+
+use crate::vec::{
+    consts::{self, TAU},
+    vec2, Float, Vec2,
+};
+
+/// Fraction of the maximum deviation of `adaptive_resample` within which a point counts
+/// as lying on a straight stretch.
+const STRAIGHT_FRACTION: Float = 0.01;
+/// Turn, in radians, above which `adaptive_resample` keeps a corner sharp.
+const SHARP_CORNER: Float = consts::FRAC_PI_4;
+/// Maximum amount of times a segment is halved by `adaptive_resample`.
+const MAX_SUBDIVISIONS: usize = 8;
+
+/// Whether the point lies inside the polygon, using the even-odd rule.
+/// The polygon is implicitly closed, the last point does not need to repeat the first.
+pub fn point_in_polygon(point: Vec2, polygon: &[Vec2]) -> bool {
+    let mut inside = false;
+
+    for (index, &a) in polygon.iter().enumerate() {
+        let b = polygon[(index + 1) % polygon.len()];
+
+        // Cast a ray to the right, and count the edges it crosses.
+        if (a.y > point.y) != (b.y > point.y) {
+            let crossing_x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            if point.x < crossing_x {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+/// The x coordinates, from left to right, where the horizontal line at height `y` crosses
+/// the edges of the polygon. The polygon is implicitly closed.
+///
+/// Every edge counts as containing its lower end but not its upper end, the same as in
+/// `point_in_polygon`. A line through a corner where the polygon goes on to the other
+/// side crosses it once, and where the polygon turns back it crosses twice or not at all.
+/// The crossings therefore always come in pairs, with the inside of the polygon between
+/// the two of a pair.
+pub fn scanline_intersections(polygon: &[Vec2], y: Float) -> Vec<Float> {
+    let mut crossings = vec![];
+
+    for (index, &a) in polygon.iter().enumerate() {
+        let b = polygon[(index + 1) % polygon.len()];
+
+        if (a.y > y) != (b.y > y) {
+            crossings.push(a.x + (y - a.y) / (b.y - a.y) * (b.x - a.x));
+        }
+    }
+
+    crossings.sort_by(Float::total_cmp);
+    crossings
+}
+
+/// The shortest distance from the point to the segment from `a` to `b`.
+pub fn point_segment_distance(point: Vec2, a: Vec2, b: Vec2) -> Float {
+    let ab = b - a;
+    let length_squared = ab.dot(ab);
+    if length_squared == 0.0 {
+        return (point - a).len();
+    }
+
+    let t = ((point - a).dot(ab) / length_squared).clamp(0.0, 1.0);
+    (point - (a + ab * t)).len()
+}
+
+/// How many times the polygon winds around the point, counter-clockwise is positive.
+/// The polygon is implicitly closed, the last point does not need to repeat the first.
+pub fn winding_number(point: Vec2, polygon: &[Vec2]) -> i32 {
+    let mut winding = 0;
+
+    for (index, &a) in polygon.iter().enumerate() {
+        let b = polygon[(index + 1) % polygon.len()];
+        // Which side of the edge the point is on.
+        let side = (b.x - a.x) * (point.y - a.y) - (point.x - a.x) * (b.y - a.y);
+
+        if a.y <= point.y && b.y > point.y && side > 0.0 {
+            winding += 1;
+        } else if a.y > point.y && b.y <= point.y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+
+    winding
+}
+
+/// Signed distance from the point to the path. Paths that end where they start are
+/// closed, and the distance is negative inside of them (non-zero winding rule).
+/// For open paths it is the plain distance to the nearest segment.
+pub fn polyline_sdf(point: Vec2, path: &[Vec2]) -> Float {
+    let distance = match path {
+        [] => return Float::INFINITY,
+        [single] => return (point - *single).len(),
+        _ => path
+            .iter()
+            .tuple_windows()
+            .map(|(&a, &b)| point_segment_distance(point, a, b))
+            .fold(Float::INFINITY, Float::min),
+    };
+
+    let closed = path.len() > 2 && path.first() == path.last();
+    if closed && winding_number(point, path) != 0 {
+        -distance
+    } else {
+        distance
+    }
+}
+
+/// Sum of how much the direction changes at every corner of the path, in radians,
+/// regardless of the direction of the turns. Paths that end where they start are closed,
+/// and also turn at that point. Going around a convex polygon once gives 2 pi.
+pub fn total_turning(points: &[Vec2]) -> Float {
+    let closed = points.len() > 3 && points.first() == points.last();
+    let mut corners: Vec<(Vec2, Vec2, Vec2)> = points.iter().copied().tuple_windows().collect();
+    if closed {
+        corners.push((points[points.len() - 2], points[0], points[1]));
+    }
+
+    corners
+        .into_iter()
+        .filter(|&(a, b, c)| a != b && b != c)
+        .map(|(a, b, c)| {
+            let (incoming, outgoing) = (b - a, c - b);
+            let cross = incoming.x * outgoing.y - incoming.y * outgoing.x;
+            cross.atan2(incoming.dot(outgoing)).abs()
+        })
+        // Not `sum`, which gives -0 for a path without corners.
+        .fold(0.0, |total, turn| total + turn)
+}
+
+/// Walks along the path, and returns a sample every `spacing` units of arc length,
+/// starting at the first point. Every sample is a position and the unit tangent
+/// of the segment it lies on.
+pub fn sample_along(points: &[Vec2], spacing: Float) -> Vec<(Vec2, Vec2)> {
+    let mut samples = vec![];
+    if spacing <= 0.0 {
+        return samples;
+    }
+
+    // Distance along the current segment where the next sample goes.
+    let mut next = 0.0;
+
+    for (&a, &b) in points.iter().tuple_windows() {
+        let length = (b - a).len();
+        if length == 0.0 {
+            continue;
+        }
+        let tangent = (b - a) / length;
+
+        // Small tolerance, so a sample that falls exactly on the end of the path
+        // isn't lost to rounding errors.
+        while next <= length + spacing * 1e-4 {
+            samples.push((a + tangent * next.min(length), tangent));
+            next += spacing;
+        }
+        next -= length;
+    }
+
+    samples
+}
+
+/// Like `sample_along`, but with the unit normal at every sample instead of the tangent.
+///
+/// For a closed path (ending where it starts) the normals point outward, and the normal
+/// at the start and end is the average of the first and last segment. For an open path
+/// they are the tangents turned a quarter counter-clockwise, using `perp`.
+pub fn path_normals(points: &[Vec2], spacing: Float) -> Vec<(Vec2, Vec2)> {
+    let mut samples = sample_along(points, spacing);
+    let closed = points.len() > 3 && points.first() == points.last();
+    if !closed {
+        return samples
+            .into_iter()
+            .map(|(position, tangent)| (position, tangent.perp()))
+            .collect();
+    }
+
+    // The inside is on the left of a path going around counter-clockwise.
+    let area: Float = points
+        .iter()
+        .tuple_windows()
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum();
+    let outward = if area > 0.0 { -1.0 } else { 1.0 };
+
+    let start = points[0];
+    let first = points.iter().find(|&&p| p != start).map(|&p| p - start);
+    let last = points
+        .iter()
+        .rev()
+        .find(|&&p| p != start)
+        .map(|&p| start - p);
+    if let (Some(first), Some(last)) = (first, last) {
+        let corner = first.normalize() + last.normalize();
+        if corner != Vec2::ZERO {
+            for (position, tangent) in samples.iter_mut() {
+                if (*position - start).len() < spacing * 1e-3 {
+                    *tangent = corner.normalize();
+                }
+            }
+        }
+    }
+
+    samples
+        .into_iter()
+        .map(|(position, tangent)| (position, tangent.perp() * outward))
+        .collect()
+}
+
+/// Bends points from a strip onto the `spine` path. The strip runs along the x-axis with
+/// a width of 1: `x` from 0 to 1 goes from the start of the spine to its end, and `y`
+/// from 0 to 1 goes across it, `strip_width` wide and centered on the spine.
+///
+/// Across the corners of the spine the direction is blended, so the strip bends smoothly.
+/// Only the points themselves are moved, long straight segments stay straight.
+pub fn warp_to_spine(points: &[Vec2], spine: &[Vec2], strip_width: Float) -> Vec<Vec2> {
+    let spine = spine.iter().copied().dedup().collect_vec();
+    if spine.len() < 2 {
+        return points.to_vec();
+    }
+
+    // Distance along the spine to every point of it.
+    let mut distances = vec![0.0];
+    for (&a, &b) in spine.iter().tuple_windows() {
+        distances.push(distances.last().unwrap() + (b - a).len());
+    }
+    let length = *distances.last().unwrap();
+
+    // Normals at the points of the spine, halfway between the segments around them.
+    let tangents = spine
+        .iter()
+        .tuple_windows()
+        .map(|(&a, &b)| (b - a).normalize())
+        .collect_vec();
+    let normals = (0..spine.len())
+        .map(|i| {
+            let before = tangents[i.saturating_sub(1)];
+            let after = tangents[i.min(tangents.len() - 1)];
+            let blended = before + after;
+            if blended == Vec2::ZERO {
+                before.perp()
+            } else {
+                blended.normalize().perp()
+            }
+        })
+        .collect_vec();
+
+    points
+        .iter()
+        .map(|p| {
+            let distance = p.x * length;
+            let segment = distances[1..]
+                .iter()
+                .position(|&end| distance <= end)
+                .unwrap_or(tangents.len() - 1);
+            let t = (distance - distances[segment]) / (distances[segment + 1] - distances[segment]);
+
+            let on_spine = spine[segment] + tangents[segment] * (distance - distances[segment]);
+            let normal = normals[segment] + (normals[segment + 1] - normals[segment]) * t;
+            on_spine + normal.normalize() * (p.y - 0.5) * strip_width
+        })
+        .collect()
+}
+
+/// Resamples the line to follow its curvature: points in the middle of straight
+/// stretches are left out, and where the line bends, points are added on the smooth
+/// curve through its points (a Catmull-Rom spline), until every piece of that curve is
+/// within `max_deviation` of the straight segment that replaces it. Straight stretches
+/// end up with only their ends, tight curves with many points.
+///
+/// Sharp corners, that turn more than `SHARP_CORNER`, are kept as they are, instead of
+/// being rounded off by the curve.
+pub fn adaptive_resample(points: &[Vec2], max_deviation: Float) -> Vec<Vec2> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    // Leave out the points that lie on the segment between their neighbours, also when
+    // the points before them were left out already.
+    let straight = max_deviation * STRAIGHT_FRACTION;
+    let mut kept = vec![points[0]];
+    let mut skipped: Vec<Vec2> = vec![];
+    for (&point, &next) in points.iter().skip(1).tuple_windows() {
+        let start = *kept.last().unwrap();
+        let on_segment = skipped
+            .iter()
+            .chain([&point])
+            .all(|&p| point_segment_distance(p, start, next) <= straight);
+        if on_segment {
+            skipped.push(point);
+        } else {
+            kept.push(point);
+            skipped.clear();
+        }
+    }
+    kept.push(*points.last().unwrap());
+
+    // Whether the curve goes smoothly through `kept[index]`, instead of it being an end
+    // of the line or a sharp corner.
+    let smooth = |index: usize| {
+        if index == 0 || index + 1 >= kept.len() {
+            return false;
+        }
+        let (before, after) = (kept[index] - kept[index - 1], kept[index + 1] - kept[index]);
+        let cross = before.x * after.y - before.y * after.x;
+        cross.atan2(before.dot(after)).abs() < SHARP_CORNER
+    };
+
+    let mut resampled = vec![kept[0]];
+    for index in 0..kept.len() - 1 {
+        let (p1, p2) = (kept[index], kept[index + 1]);
+        let before = smooth(index).then(|| kept[index - 1]);
+        let after = smooth(index + 1).then(|| kept[index + 2]);
+        // Without a neighbour, the curve continues the parabola through the other three
+        // points, so it doesn't flatten out towards the ends.
+        let p0 = before.unwrap_or_else(|| match after {
+            Some(p3) => p1 * 3.0 - p2 * 3.0 + p3,
+            None => p1,
+        });
+        let p3 = after.unwrap_or_else(|| match before {
+            Some(p0) => p2 * 3.0 - p1 * 3.0 + p0,
+            None => p2,
+        });
+        let curve = |t: Float| {
+            (p1 * 2.0
+                + (p2 - p0) * t
+                + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * (t * t)
+                + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * (t * t * t))
+                * 0.5
+        };
+        subdivide(
+            &curve,
+            (0.0, p1),
+            (1.0, p2),
+            max_deviation,
+            0,
+            &mut resampled,
+        );
+    }
+
+    resampled
+}
+
+/// Adds the points of the `curve` between `start` and `end`, given as the parameter and
+/// the point, until the middle of every piece is within `max_deviation` of the segment
+/// between its ends. Adds `end`, but not `start`.
+fn subdivide(
+    curve: &impl Fn(Float) -> Vec2,
+    start: (Float, Vec2),
+    end: (Float, Vec2),
+    max_deviation: Float,
+    depth: usize,
+    output: &mut Vec<Vec2>,
+) {
+    let t = (start.0 + end.0) / 2.0;
+    let middle = (t, curve(t));
+    if depth < MAX_SUBDIVISIONS && point_segment_distance(middle.1, start.1, end.1) > max_deviation
+    {
+        subdivide(curve, start, middle, max_deviation, depth + 1, output);
+        subdivide(curve, middle, end, max_deviation, depth + 1, output);
+    } else {
+        output.push(end.1);
+    }
+}
+
+/// Thins out the line by only keeping the `keep_fraction` (0 to 1) of its points where it
+/// turns the most, and both ends. Sharp corners are kept even when very few points are
+/// left, which keeps the character of the line.
+///
+/// The turn of every point is measured with its neighbours in the original line.
+pub fn simplify_by_curvature(points: &[Vec2], keep_fraction: Float) -> Vec<Vec2> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let mut turns: Vec<(usize, Float)> = points
+        .iter()
+        .tuple_windows()
+        .enumerate()
+        .map(|(i, (&a, &b, &c))| {
+            let (incoming, outgoing) = (b - a, c - b);
+            let cross = incoming.x * outgoing.y - incoming.y * outgoing.x;
+            (i + 1, cross.atan2(incoming.dot(outgoing)).abs())
+        })
+        .collect();
+    let count = (turns.len() as Float * keep_fraction.clamp(0.0, 1.0)).round() as usize;
+    turns.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    for &(index, _) in &turns[..count] {
+        keep[index] = true;
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(&point, keep)| keep.then_some(point))
+        .collect()
+}
+
+/// `count` points evenly spread along the path by arc length, including both ends.
+pub fn resample(points: &[Vec2], count: usize) -> Vec<Vec2> {
+    let (Some(&first), Some(&last)) = (points.first(), points.last()) else {
+        return vec![];
+    };
+    let length: Float = points
+        .iter()
+        .tuple_windows()
+        .map(|(&a, &b)| (b - a).len())
+        .sum();
+    if count < 2 || length == 0.0 {
+        return vec![first; count];
+    }
+
+    let mut samples: Vec<Vec2> = sample_along(points, length / (count - 1) as Float)
+        .into_iter()
+        .map(|(position, _)| position)
+        .take(count)
+        .collect();
+    // Rounding errors can lose the sample at the very end.
+    samples.resize(count, last);
+    samples
+}
+
+/// Interpolates between two point sets, for morphing one drawing into another.
+/// Both are resampled to the larger of their point counts, then every point moves
+/// in a straight line: `t` = 0 gives `a`, and `t` = 1 gives `b`.
+pub fn morph_pointsets(a: &[Vec2], b: &[Vec2], t: Float) -> Vec<Vec2> {
+    let count = a.len().max(b.len());
+    resample(a, count)
+        .into_iter()
+        .zip(resample(b, count))
+        .map(|(from, to)| from * (1.0 - t) + to * t)
+        .collect()
+}
+
+/// Closed circle made of `segments` straight segments. With a `wobble` the radius changes
+/// slowly around the circle, by up to that fraction of the radius, like a hand drawn circle.
+pub fn wobbly_circle(
+    center: Vec2,
+    radius: Float,
+    segments: usize,
+    wobble: Float,
+    rng: &mut impl Rng,
+) -> Vec<Vec2> {
+    // A few slow waves around the circle, with weights adding up to 1, so together
+    // they stay between -1 and 1. Without wobble the random generator is left alone,
+    // so the rest of the drawing doesn't change.
+    let waves: Vec<(Float, Float)> = if wobble > 0.0 {
+        (0..3)
+            .map(|_| (rng.gen_range(0.0..1.0), rng.gen_range(0.0..TAU)))
+            .collect()
+    } else {
+        vec![]
+    };
+    let total: Float = waves.iter().map(|(weight, _)| weight).sum();
+
+    let mut points: Vec<Vec2> = (0..segments)
+        .map(|i| {
+            let angle = TAU * i as Float / segments as Float;
+            let offset: Float = waves
+                .iter()
+                .zip(1..)
+                .map(|(&(weight, phase), frequency)| {
+                    weight / total * (angle * frequency as Float + phase).sin()
+                })
+                .sum();
+            center + Vec2::from_angle(angle) * radius * (1.0 + wobble * offset)
+        })
+        .collect();
+    if let Some(&first) = points.first() {
+        points.push(first);
+    }
+    points
+}
+
+/// Where the endless lines through `a` and `b` cross, or `None` when they are parallel.
+pub fn line_intersection(a: (Vec2, Vec2), b: (Vec2, Vec2)) -> Option<Vec2> {
+    let cross = |u: Vec2, v: Vec2| u.x * v.y - u.y * v.x;
+    let (direction_a, direction_b) = (a.1 - a.0, b.1 - b.0);
+
+    let denominator = cross(direction_a, direction_b);
+    if denominator.abs() < Float::EPSILON {
+        return None;
+    }
+    let t = cross(b.0 - a.0, direction_b) / denominator;
+    Some(a.0 + direction_a * t)
+}
+
+/// The envelope of a family of lines, the curve that touches every one of them, as
+/// the points where every line crosses the next one. The lines are given by two points
+/// each, and should change gradually from one to the next. Parallel neighbours are skipped.
+pub fn envelope(lines: &[Vec<Vec2>]) -> Vec<Vec2> {
+    lines
+        .iter()
+        .filter(|line| line.len() >= 2)
+        .tuple_windows()
+        .filter_map(|(a, b)| line_intersection((a[0], a[1]), (b[0], b[1])))
+        .collect()
+}
+
+/// The points mirrored in the line through `axis_point` along `axis_dir`.
+pub fn reflect(points: &[Vec2], axis_point: Vec2, axis_dir: Vec2) -> Vec<Vec2> {
+    let direction = axis_dir.normalize();
+    points
+        .iter()
+        .map(|&p| {
+            let along = axis_point + direction * (p - axis_point).dot(direction);
+            along * 2.0 - p
+        })
+        .collect()
+}
+
+/// Closed superellipse, `|x / radii.x|^n + |y / radii.y|^n = 1` around `center`, made of
+/// `segments` straight segments. An `n` of 2 gives an ellipse, higher values get closer
+/// to a rectangle, and values below 1 give a star with four pointy arms.
+pub fn superellipse(center: Vec2, radii: Vec2, n: Float, segments: usize) -> Vec<Vec2> {
+    let power = |value: Float| value.signum() * value.abs().powf(2.0 / n);
+
+    let mut points: Vec<Vec2> = (0..segments)
+        .map(|i| {
+            let angle = TAU * i as Float / segments as Float;
+            let (sin, cos) = angle.sin_cos();
+            center + vec2(radii.x * power(cos), radii.y * power(sin))
+        })
+        .collect();
+    if let Some(&first) = points.first() {
+        points.push(first);
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::consts;
+    use pretty_assertions::assert_eq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn point_in_polygon_concave() {
+        // U-shape, open at the top.
+        let polygon = [
+            vec2(0.0, 0.0),
+            vec2(1.0, 0.0),
+            vec2(1.0, 3.0),
+            vec2(2.0, 3.0),
+            vec2(2.0, 0.0),
+            vec2(3.0, 0.0),
+            vec2(3.0, 4.0),
+            vec2(0.0, 4.0),
+        ];
+
+        assert!(point_in_polygon(vec2(0.5, 1.0), &polygon));
+        assert!(point_in_polygon(vec2(1.5, 3.5), &polygon));
+        assert!(!point_in_polygon(vec2(1.5, 1.0), &polygon));
+        assert!(!point_in_polygon(vec2(4.0, 1.0), &polygon));
+    }
+
+    #[test]
+    fn scanline_intersections_square() {
+        let square = [
+            vec2(0.0, 0.0),
+            vec2(4.0, 0.0),
+            vec2(4.0, 4.0),
+            vec2(0.0, 4.0),
+            vec2(0.0, 0.0),
+        ];
+
+        assert_eq!(scanline_intersections(&square, 1.0), vec![0.0, 4.0]);
+        // Along the bottom and top edges.
+        assert_eq!(scanline_intersections(&square, 0.0), vec![0.0, 4.0]);
+        assert_eq!(scanline_intersections(&square, 4.0), Vec::<Float>::new());
+        assert_eq!(scanline_intersections(&square, 5.0), Vec::<Float>::new());
+    }
+
+    #[test]
+    fn scanline_intersections_concave() {
+        // U-shape, open at the top, with a point at the bottom of the gap.
+        let polygon = [
+            vec2(0.0, 0.0),
+            vec2(3.0, 0.0),
+            vec2(3.0, 4.0),
+            vec2(2.0, 4.0),
+            vec2(1.5, 2.0),
+            vec2(1.0, 4.0),
+            vec2(0.0, 4.0),
+        ];
+
+        assert_eq!(scanline_intersections(&polygon, 1.0), vec![0.0, 3.0]);
+        assert_eq!(
+            scanline_intersections(&polygon, 3.0),
+            vec![0.0, 1.25, 1.75, 3.0]
+        );
+        // Touching the point at the bottom of the gap.
+        assert_eq!(
+            scanline_intersections(&polygon, 2.0),
+            vec![0.0, 1.5, 1.5, 3.0]
+        );
+        // Through the corners at the top of the gap, which are not crossed.
+        assert_eq!(scanline_intersections(&polygon, 4.0), Vec::<Float>::new());
+    }
+
+    #[test]
+    fn envelope_of_string_art_is_a_parabola() {
+        let (corner, a, b) = (vec2(0.0, 0.0), vec2(10.0, 0.0), vec2(0.0, 10.0));
+        let strings = crate::generators::string_art::string_family(corner, a, b, 41);
+
+        let curve = envelope(&strings);
+
+        assert_eq!(curve.len(), 40);
+        // The parabola touching the strings, a quadratic bezier curve with the corner
+        // as control point.
+        let parabola: Vec<Vec2> = (0..=1000)
+            .map(|i| {
+                let t = i as Float / 1000.0;
+                b * (1.0 - t).powi(2) + a * t.powi(2)
+            })
+            .collect();
+        for point in curve {
+            let distance = parabola
+                .iter()
+                .map(|&p| (p - point).len())
+                .fold(Float::INFINITY, Float::min);
+            assert!(distance < 0.05);
+        }
+    }
+
+    #[test]
+    fn reflect_in_the_vertical_center_line() {
+        let points = [vec2(10.0, 20.0), vec2(50.0, 5.0), vec2(90.0, 0.0)];
+
+        let mirrored = reflect(&points, vec2(50.0, 0.0), vec2(0.0, 3.0));
+        let diagonal = reflect(&points[..1], Vec2::ZERO, vec2(1.0, 1.0));
+
+        let expected = [vec2(90.0, 20.0), vec2(50.0, 5.0), vec2(10.0, 0.0)];
+        assert_eq!(mirrored.len(), expected.len());
+        for (point, expected) in mirrored.iter().zip(expected) {
+            assert!(
+                (*point - expected).len() < 1e-4,
+                "{point:?} != {expected:?}"
+            );
+        }
+        // Diagonal line, swapping x and y.
+        assert!((diagonal[0] - vec2(20.0, 10.0)).len() < 1e-4);
+    }
+
+    #[test]
+    fn simplify_by_curvature_keeps_sharp_corners() {
+        // A gentle arc, with a sharp corner halfway.
+        let mut points: Vec<Vec2> = (0..=10)
+            .map(|i| vec2(i as Float, 0.01 * (i as Float).powi(2)))
+            .collect();
+        points.push(vec2(10.0, 10.0));
+        points.extend((0..=10).map(|i| vec2(9.0 - i as Float, 10.0 + 0.1 * i as Float)));
+
+        let simplified = simplify_by_curvature(&points, 0.1);
+
+        assert_eq!(
+            simplified,
+            vec![points[0], points[10], points[11], points[22]]
+        );
+        assert_eq!(simplify_by_curvature(&points, 1.0), points);
+        assert_eq!(
+            simplify_by_curvature(&points, 0.0),
+            vec![points[0], points[22]]
+        );
+    }
+
+    #[test]
+    fn polyline_sdf_closed_square() {
+        let square = [
+            vec2(0.0, 0.0),
+            vec2(4.0, 0.0),
+            vec2(4.0, 4.0),
+            vec2(0.0, 4.0),
+            vec2(0.0, 0.0),
+        ];
+
+        assert_eq!(polyline_sdf(vec2(1.0, 2.0), &square), -1.0);
+        assert_eq!(polyline_sdf(vec2(2.0, 2.0), &square), -2.0);
+        assert_eq!(polyline_sdf(vec2(7.0, 2.0), &square), 3.0);
+        assert_eq!(polyline_sdf(vec2(7.0, 8.0), &square), 5.0);
+        assert_eq!(polyline_sdf(vec2(4.0, 1.0), &square), 0.0);
+        assert_eq!(polyline_sdf(vec2(0.0, 0.0), &square), 0.0);
+
+        // The same square, but open, has no inside.
+        assert_eq!(polyline_sdf(vec2(1.0, 2.0), &square[..4]), 2.0);
+    }
+
+    #[test]
+    fn total_turning_of_convex_polygon() {
+        let mut hexagon: Vec<Vec2> = (0..6)
+            .map(|i| Vec2::from_angle(i as Float * consts::TAU / 6.0) * 3.0)
+            .collect();
+        hexagon.push(hexagon[0]);
+
+        assert!((total_turning(&hexagon) - consts::TAU).abs() < 1e-4);
+        // Open, it misses the turn at the start.
+        assert!((total_turning(&hexagon[..6]) - consts::TAU * 4.0 / 6.0).abs() < 1e-4);
+        // A zigzag turns both ways, which doesn't cancel out.
+        let zigzag = [
+            vec2(0.0, 0.0),
+            vec2(1.0, 1.0),
+            vec2(2.0, 0.0),
+            vec2(3.0, 1.0),
+        ];
+        assert!((total_turning(&zigzag) - consts::PI).abs() < 1e-4);
+    }
+
+    #[test]
+    fn morph_pointsets_interpolates_resampled_inputs() {
+        let a = [vec2(0.0, 0.0), vec2(4.0, 0.0)];
+        let b = [
+            vec2(0.0, 2.0),
+            vec2(2.0, 2.0),
+            vec2(2.0, 4.0),
+            vec2(4.0, 4.0),
+            vec2(4.0, 6.0),
+        ];
+
+        assert_eq!(morph_pointsets(&a, &b, 0.0), resample(&a, 5));
+        assert_eq!(
+            resample(&a, 5),
+            vec![
+                vec2(0.0, 0.0),
+                vec2(1.0, 0.0),
+                vec2(2.0, 0.0),
+                vec2(3.0, 0.0),
+                vec2(4.0, 0.0),
+            ]
+        );
+        assert_eq!(morph_pointsets(&a, &b, 1.0), b.to_vec());
+
+        let middle = morph_pointsets(&a, &b, 0.5);
+        for ((m, p), q) in middle.iter().zip(resample(&a, 5)).zip(b) {
+            assert!((*m - (p + q) / 2.0).len() < 1e-5);
+        }
+
+        // Both ends are exact, also for coordinates that can't be represented exactly.
+        let a = [vec2(0.1, 0.7), vec2(3.3, 1.9), vec2(7.77, -2.3)];
+        let b = [
+            vec2(-1.3, 0.2),
+            vec2(0.3, 9.1),
+            vec2(5.7, 3.3),
+            vec2(6.1, 0.9),
+        ];
+        assert_eq!(morph_pointsets(&a, &b, 0.0), resample(&a, 4));
+        assert_eq!(morph_pointsets(&a, &b, 1.0), resample(&b, 4));
+    }
+
+    #[test]
+    fn wobbly_circle_stays_within_the_wobble() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let center = vec2(5.0, 5.0);
+
+        let exact = wobbly_circle(center, 2.0, 64, 0.0, &mut rng);
+        assert_eq!(exact.len(), 65);
+        assert_eq!(exact.first(), exact.last());
+        assert!(exact
+            .iter()
+            .all(|&p| ((p - center).len() - 2.0).abs() < 1e-5));
+
+        let wobbly = wobbly_circle(center, 2.0, 64, 0.1, &mut rng);
+        assert_eq!(wobbly.first(), wobbly.last());
+        assert!(wobbly
+            .iter()
+            .all(|&p| ((p - center).len() - 2.0).abs() <= 0.2 + 1e-5));
+        assert!(wobbly
+            .iter()
+            .any(|&p| ((p - center).len() - 2.0).abs() > 0.01));
+    }
+
+    #[test]
+    fn path_normals_of_straight_line_are_equal() {
+        let line = [vec2(0.0, 5.0), vec2(3.0, 5.0), vec2(10.0, 5.0)];
+
+        let normals = path_normals(&line, 1.0);
+
+        assert_eq!(normals.len(), 11);
+        for (index, &(position, normal)) in normals.iter().enumerate() {
+            assert!((position - vec2(index as Float, 5.0)).len() < 1e-5);
+            assert_eq!(normal, vec2(0.0, 1.0));
+        }
+    }
+
+    #[test]
+    fn path_normals_of_closed_square_point_outward() {
+        let square = [
+            vec2(0.0, 0.0),
+            vec2(0.0, 4.0),
+            vec2(4.0, 4.0),
+            vec2(4.0, 0.0),
+            vec2(0.0, 0.0),
+        ];
+        let center = vec2(2.0, 2.0);
+
+        for path in [square.to_vec(), square.iter().rev().copied().collect()] {
+            let normals = path_normals(&path, 1.0);
+            assert_eq!(normals.len(), 17);
+            for &(position, normal) in &normals {
+                assert!(normal.dot(position - center) > 0.0);
+            }
+            // The start is a corner, with a diagonal normal.
+            assert!((normals[0].1 - vec2(-1.0, -1.0).normalize()).len() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn warp_to_straight_spine_keeps_the_points() {
+        let points = [
+            vec2(0.0, 0.0),
+            vec2(0.25, 0.9),
+            vec2(0.6, 0.3),
+            vec2(1.0, 1.0),
+        ];
+        let spine = [vec2(0.0, 0.5), vec2(0.4, 0.5), vec2(1.0, 0.5)];
+
+        let warped = warp_to_spine(&points, &spine, 1.0);
+
+        for (warped, point) in warped.iter().zip(points) {
+            assert!((*warped - point).len() < 1e-5, "{warped:?} != {point:?}");
+        }
+
+        // A longer, wider spine scales the strip.
+        let warped = warp_to_spine(&points, &[vec2(0.0, 0.0), vec2(10.0, 0.0)], 4.0);
+        assert!((warped[1] - vec2(2.5, 1.6)).len() < 1e-5);
+    }
+
+    #[test]
+    fn adaptive_resample_adds_points_on_curves() {
+        let straight: Vec<Vec2> = (0..=50).map(|i| vec2(i as Float, 3.0)).collect();
+        assert_eq!(
+            adaptive_resample(&straight, 0.01),
+            vec![vec2(0.0, 3.0), vec2(50.0, 3.0)]
+        );
+
+        // A half circle, and a straight line after a sharp corner.
+        let arc: Vec<Vec2> = (0..=8)
+            .map(|i| Vec2::from_angle(i as Float / 8.0 * consts::PI) * 10.0)
+            .collect();
+        let line = [arc[8], vec2(5.0, 0.0), vec2(20.0, 0.0)];
+        let resampled = adaptive_resample(&[&arc[..], &line[1..]].concat(), 0.01);
+
+        // The curve gains points, and keeps the ones it had.
+        assert!(resampled.len() > arc.len() * 4);
+        for point in &arc {
+            assert!(resampled.contains(point), "{point:?}");
+        }
+        for point in &resampled[..resampled.len() - 1] {
+            let distance = (point.len() - 10.0).abs();
+            assert!(distance < 0.02, "{point:?} is {distance} from the circle");
+        }
+        // The line only keeps its ends.
+        assert_eq!(resampled[resampled.len() - 2..], [line[0], line[2]]);
+    }
+
+    #[test]
+    fn sample_along_around_corner() {
+        let points = [vec2(0.0, 0.0), vec2(3.0, 0.0), vec2(3.0, 3.0)];
+
+        let samples = sample_along(&points, 2.0);
+
+        assert_eq!(
+            samples,
+            vec![
+                (vec2(0.0, 0.0), vec2(1.0, 0.0)),
+                (vec2(2.0, 0.0), vec2(1.0, 0.0)),
+                (vec2(3.0, 1.0), vec2(0.0, 1.0)),
+                (vec2(3.0, 3.0), vec2(0.0, 1.0)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt::Write;
+
+use crate::{
+    gcode::joins,
+    vec::{Float, Vec2},
+};
+
+/// HPGL plotter units per millimeter.
+const UNITS_PER_MM: Float = 40.0;
+
+/// Converts the paths to HPGL, with the canvas units as millimeters.
+/// The y-axis is flipped, because svg has its origin in the top left,
+/// while plotters have it in the bottom left.
+///
+/// When a path starts within `join_tolerance` of where the previous one ended,
+/// the pen stays down and draws the gap instead of lifting.
+pub fn paths_to_hpgl(paths: &[Vec<Vec2>], size: Vec2, join_tolerance: Option<Float>) -> String {
+    let mut hpgl = String::new();
+    let coordinate = |p: Vec2| {
+        format!(
+            "{:.0},{:.0}",
+            p.x * UNITS_PER_MM,
+            (size.y - p.y) * UNITS_PER_MM
+        )
+    };
+
+    writeln!(hpgl, "IN;SP1;").unwrap();
+
+    let mut previous_end: Option<Vec2> = None;
+
+    for path in paths {
+        let (Some(&first), Some(&last)) = (path.first(), path.last()) else {
+            continue;
+        };
+
+        if !joins(previous_end, first, join_tolerance) {
+            writeln!(hpgl, "PU{};", coordinate(first)).unwrap();
+        }
+
+        let points = path.iter().map(|&p| coordinate(p)).collect::<Vec<_>>();
+        writeln!(hpgl, "PD{};", points.join(",")).unwrap();
+
+        previous_end = Some(last);
+    }
+
+    writeln!(hpgl, "PU;SP0;").unwrap();
+
+    hpgl
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec2;
+    use pretty_assertions::assert_eq;
+
+    fn pen_lifts(hpgl: &str) -> usize {
+        hpgl.matches("PU").count()
+    }
+
+    #[test]
+    fn coordinates_are_flipped_plotter_units() {
+        let hpgl = paths_to_hpgl(
+            &[vec![vec2(0.0, 0.0), vec2(1.0, 2.0)]],
+            vec2(10.0, 10.0),
+            None,
+        );
+
+        assert_eq!(hpgl, "IN;SP1;\nPU0,400;\nPD0,400,40,320;\nPU;SP0;\n");
+    }
+
+    #[test]
+    fn near_touching_paths_are_joined() {
+        let first = vec![vec2(0.0, 0.0), vec2(5.0, 0.0)];
+        let near = vec![vec2(5.05, 0.0), vec2(10.0, 0.0)];
+        let far = vec![vec2(8.0, 5.0), vec2(10.0, 5.0)];
+        let size = vec2(10.0, 10.0);
+
+        let joined = paths_to_hpgl(&[first.clone(), near], size, Some(0.1));
+        let separate = paths_to_hpgl(&[first, far], size, Some(0.1));
+
+        assert_eq!(pen_lifts(&joined) + 1, pen_lifts(&separate));
+    }
+}
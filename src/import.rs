@@ -0,0 +1,671 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+use log::warn;
+use svg::{
+    node::{
+        element::{
+            path::{Command, Data, Position},
+            tag,
+        },
+        Attributes,
+    },
+    parser::Event,
+};
+
+use crate::{
+    color::Color,
+    layer::Layer,
+    vec::{consts::TAU, vec2, Float, Vec2},
+};
+
+/// Amount of straight segments a single curve command is split into.
+const CURVE_SEGMENTS: usize = 16;
+
+/// Imports every file as its own layer, named after the file.
+///
+/// With `split_colors`, every file is split further into a layer per stroke color, named
+/// after the file and the color, so each color can be plotted with its own pen. With a
+/// `palette`, every line goes to the layer of the closest palette color instead of its
+/// exact color, so similar colors end up together.
+pub fn merge_files(
+    files: &[Utf8PathBuf],
+    split_colors: bool,
+    palette: &[Color],
+) -> Result<Vec<Layer>> {
+    let mut layers: Vec<Layer> = vec![];
+
+    for file in files {
+        let stem = file.file_stem().unwrap_or("layer");
+        let content =
+            std::fs::read_to_string(file).wrap_err_with(|| format!("Could not read `{file}`"))?;
+        let paths = import_svg_with_colors(&content)
+            .wrap_err_with(|| format!("Could not import `{file}`"))?;
+
+        let file_layers = if split_colors {
+            split_by_color(paths, palette)
+                .into_iter()
+                .map(|(color, paths)| {
+                    let hex = color.to_string();
+                    (format!("{stem}-{}", hex.trim_start_matches('#')), paths)
+                })
+                .collect()
+        } else {
+            vec![(
+                stem.to_string(),
+                paths.into_iter().map(|(_, path)| path).collect(),
+            )]
+        };
+
+        for (base, paths) in file_layers {
+            // Layer names have to be unique, so they can be ordered.
+            let mut name = base.clone();
+            let mut count = 1;
+            while layers.iter().any(|layer| layer.name == name) {
+                count += 1;
+                name = format!("{base}-{count}");
+            }
+
+            layers.push(Layer::new(name, paths));
+        }
+    }
+
+    Ok(layers)
+}
+
+/// Groups the lines by color, in the order the colors first appear. With a `palette`,
+/// every line goes to the closest palette color instead of its exact color.
+pub fn split_by_color(
+    paths: Vec<(Color, Vec<Vec2>)>,
+    palette: &[Color],
+) -> Vec<(Color, Vec<Vec<Vec2>>)> {
+    let distance =
+        |a: Color, b: Color| (a.r - b.r).powi(2) + (a.g - b.g).powi(2) + (a.b - b.b).powi(2);
+    let mut groups: Vec<(Color, Vec<Vec<Vec2>>)> = vec![];
+
+    for (color, path) in paths {
+        let color = palette
+            .iter()
+            .copied()
+            .min_by(|&a, &b| distance(a, color).total_cmp(&distance(b, color)))
+            .unwrap_or(color);
+
+        match groups.iter_mut().find(|(group, _)| *group == color) {
+            Some((_, group)) => group.push(path),
+            None => groups.push((color, vec![path])),
+        }
+    }
+
+    groups
+}
+
+pub fn import_file(path: &Utf8Path) -> Result<Vec<Vec<Vec2>>> {
+    let content =
+        std::fs::read_to_string(path).wrap_err_with(|| format!("Could not read `{path}`"))?;
+    import_svg(&content).wrap_err_with(|| format!("Could not import `{path}`"))
+}
+
+/// Size of the `viewBox` of the svg file, the canvas it was drawn on.
+pub fn file_size(path: &Utf8Path) -> Result<Vec2> {
+    let content =
+        std::fs::read_to_string(path).wrap_err_with(|| format!("Could not read `{path}`"))?;
+    let (_, size) =
+        view_box(&content).wrap_err_with(|| format!("Could not get the size of `{path}`"))?;
+    Ok(size)
+}
+
+/// Top left corner and size of the `viewBox` of the svg, the canvas it was drawn on.
+pub fn view_box(content: &str) -> Result<(Vec2, Vec2)> {
+    for event in svg::read(content)? {
+        match event {
+            Event::Tag(tag::SVG, tag::Type::Start, attributes) => {
+                let view_box = attributes.get("viewBox").ok_or_else(|| {
+                    eyre!("The svg has no `viewBox`, use `--width` and `--height`")
+                })?;
+                return parse_view_box(view_box);
+            }
+            Event::Error(error) => return Err(eyre!("Invalid svg: {error}")),
+            _ => {}
+        }
+    }
+
+    Err(eyre!("Missing `svg` element"))
+}
+
+fn parse_view_box(view_box: &str) -> Result<(Vec2, Vec2)> {
+    let numbers = parse_numbers(view_box)?;
+    let [x, y, width, height] = numbers[..] else {
+        return Err(eyre!(
+            "Expected 4 numbers in the `viewBox`, got `{view_box}`"
+        ));
+    };
+    if width <= 0.0 || height <= 0.0 {
+        return Err(eyre!("The `viewBox` has no area: `{view_box}`"));
+    }
+    Ok((vec2(x, y), vec2(width, height)))
+}
+
+/// Reads all the `path`, `line`, `polyline` and `polygon` elements as lines.
+/// Curves are approximated with straight segments. Transforms are not applied.
+/// The points are relative to the top left corner of the `viewBox`, if there is one.
+pub fn import_svg(content: &str) -> Result<Vec<Vec<Vec2>>> {
+    Ok(import_svg_with_colors(content)?
+        .into_iter()
+        .map(|(_, path)| path)
+        .collect())
+}
+
+/// Like `import_svg`, with the stroke color of every line. The color comes from the
+/// `stroke` attribute or `style` of the element, or else of the group it is in.
+/// Lines without a stroke color that can be read are black.
+pub fn import_svg_with_colors(content: &str) -> Result<Vec<(Color, Vec<Vec2>)>> {
+    let mut paths = vec![];
+    // The stroke color of every group the elements are in, from the outside in.
+    let mut group_strokes: Vec<Option<Color>> = vec![];
+    // Top left corner of the canvas, from the outermost `svg` element.
+    let mut origin = None;
+
+    for event in svg::read(content)? {
+        let Event::Tag(name, tag_type, attributes) = event else {
+            if let Event::Error(error) = event {
+                return Err(eyre!("Invalid svg: {error}"));
+            }
+            continue;
+        };
+        if name == tag::SVG {
+            if tag_type == tag::Type::Start && origin.is_none() {
+                origin = Some(match attributes.get("viewBox") {
+                    Some(view_box) => parse_view_box(view_box)?.0,
+                    None => Vec2::ZERO,
+                });
+            }
+            continue;
+        }
+        if name == tag::Group {
+            match tag_type {
+                tag::Type::Start => group_strokes.push(stroke_color(&attributes)),
+                tag::Type::End => {
+                    group_strokes.pop();
+                }
+                tag::Type::Empty => {}
+            }
+            continue;
+        }
+        if tag_type == tag::Type::End {
+            continue;
+        }
+        let color = stroke_color(&attributes)
+            .or_else(|| group_strokes.iter().rev().find_map(|&stroke| stroke))
+            .unwrap_or(Color::BLACK);
+        let mut element_paths = vec![];
+
+        let number = |name: &str| -> Result<Float> {
+            let value = attributes
+                .get(name)
+                .ok_or_else(|| eyre!("Missing attribute `{name}`"))?;
+            value
+                .trim()
+                .parse()
+                .wrap_err_with(|| format!("Invalid number `{}`", &**value))
+        };
+
+        match name {
+            tag::Path => {
+                if let Some(d) = attributes.get("d") {
+                    let data = Data::parse(d).map_err(|e| eyre!("Invalid path data: {e}"))?;
+                    element_paths = data_to_paths(&data);
+                }
+            }
+            tag::Line => {
+                element_paths.push(vec![
+                    vec2(number("x1")?, number("y1")?),
+                    vec2(number("x2")?, number("y2")?),
+                ]);
+            }
+            tag::Polyline | tag::Polygon => {
+                let Some(points) = attributes.get("points") else {
+                    continue;
+                };
+                let mut points = parse_points(points)?;
+                if name == tag::Polygon {
+                    if let Some(&first) = points.first() {
+                        points.push(first);
+                    }
+                }
+                element_paths.push(points);
+            }
+            _ => {}
+        }
+        paths.extend(element_paths.into_iter().map(|path| (color, path)));
+    }
+
+    paths.retain(|(_, path)| path.len() >= 2);
+    let origin = origin.unwrap_or(Vec2::ZERO);
+    for point in paths.iter_mut().flat_map(|(_, path)| path) {
+        *point = *point - origin;
+    }
+    Ok(paths)
+}
+
+/// The stroke color from the `stroke` attribute, or from `stroke:` in the `style` attribute.
+/// `none`, and colors that can't be read, count as no stroke color, so the color of
+/// the group is used.
+fn stroke_color(attributes: &Attributes) -> Option<Color> {
+    let from_style = || {
+        attributes.get("style")?.split(';').find_map(|declaration| {
+            let (property, value) = declaration.split_once(':')?;
+            (property.trim() == "stroke").then(|| value.trim().to_string())
+        })
+    };
+    let stroke = attributes
+        .get("stroke")
+        .map(|value| value.to_string())
+        .or_else(from_style)?;
+    match stroke.trim() {
+        "none" | "inherit" => None,
+        stroke => match stroke.parse() {
+            Ok(color) => Some(color),
+            Err(error) => {
+                warn!("{error}, using the color of the group or black instead");
+                None
+            }
+        },
+    }
+}
+
+/// Parses `x,y x,y ...` (or any other mix of commas and whitespace).
+fn parse_points(points: &str) -> Result<Vec<Vec2>> {
+    Ok(parse_numbers(points)?
+        .chunks_exact(2)
+        .map(|pair| vec2(pair[0], pair[1]))
+        .collect())
+}
+
+/// Parses numbers separated by any mix of commas and whitespace.
+fn parse_numbers(numbers: &str) -> Result<Vec<Float>> {
+    numbers
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            value
+                .parse::<Float>()
+                .wrap_err_with(|| format!("Invalid number `{value}`"))
+        })
+        .collect()
+}
+
+fn data_to_paths(data: &Data) -> Vec<Vec<Vec2>> {
+    let mut paths = vec![];
+    let mut current: Vec<Vec2> = vec![];
+    let mut position = Vec2::ZERO;
+    let mut start = Vec2::ZERO;
+    // Last control point of the previous curve, for the smooth curve commands.
+    let mut last_control: Option<Vec2> = None;
+
+    for command in data.iter() {
+        let (relative, parameters) = match command {
+            Command::Move(p, params)
+            | Command::Line(p, params)
+            | Command::HorizontalLine(p, params)
+            | Command::VerticalLine(p, params)
+            | Command::QuadraticCurve(p, params)
+            | Command::SmoothQuadraticCurve(p, params)
+            | Command::CubicCurve(p, params)
+            | Command::SmoothCubicCurve(p, params)
+            | Command::EllipticalArc(p, params) => (
+                *p == Position::Relative,
+                params.iter().map(|&n| n as Float).collect::<Vec<_>>(),
+            ),
+            Command::Close => {
+                if current.len() > 1 {
+                    current.push(start);
+                }
+                paths.push(std::mem::take(&mut current));
+                position = start;
+                last_control = None;
+                continue;
+            }
+        };
+
+        let point = |x: Float, y: Float, from: Vec2| {
+            if relative {
+                from + vec2(x, y)
+            } else {
+                vec2(x, y)
+            }
+        };
+
+        if current.is_empty() && !matches!(command, Command::Move(..)) {
+            // Drawing straight after a close continues from the start of the closed path.
+            current.push(position);
+        }
+
+        match command {
+            Command::Move(..) => {
+                for (index, pair) in parameters.chunks_exact(2).enumerate() {
+                    position = point(pair[0], pair[1], position);
+                    if index == 0 {
+                        paths.push(std::mem::take(&mut current));
+                        start = position;
+                    }
+                    // Extra pairs after a move are implicit line commands.
+                    current.push(position);
+                }
+                last_control = None;
+            }
+            Command::Line(..) => {
+                for pair in parameters.chunks_exact(2) {
+                    position = point(pair[0], pair[1], position);
+                    current.push(position);
+                }
+                last_control = None;
+            }
+            Command::HorizontalLine(..) => {
+                for &x in &parameters {
+                    position.x = if relative { position.x + x } else { x };
+                    current.push(position);
+                }
+                last_control = None;
+            }
+            Command::VerticalLine(..) => {
+                for &y in &parameters {
+                    position.y = if relative { position.y + y } else { y };
+                    current.push(position);
+                }
+                last_control = None;
+            }
+            Command::QuadraticCurve(..) | Command::SmoothQuadraticCurve(..) => {
+                let smooth = matches!(command, Command::SmoothQuadraticCurve(..));
+                let stride = if smooth { 2 } else { 4 };
+
+                for values in parameters.chunks_exact(stride) {
+                    let control = if smooth {
+                        reflect_control(last_control, position)
+                    } else {
+                        point(values[0], values[1], position)
+                    };
+                    let end = point(values[stride - 2], values[stride - 1], position);
+
+                    for i in 1..=CURVE_SEGMENTS {
+                        let t = i as Float / CURVE_SEGMENTS as Float;
+                        let u = 1.0 - t;
+                        current.push(position * (u * u) + control * (2.0 * u * t) + end * (t * t));
+                    }
+
+                    position = end;
+                    last_control = Some(control);
+                }
+            }
+            Command::CubicCurve(..) | Command::SmoothCubicCurve(..) => {
+                let smooth = matches!(command, Command::SmoothCubicCurve(..));
+                let stride = if smooth { 4 } else { 6 };
+
+                for values in parameters.chunks_exact(stride) {
+                    let control_1 = if smooth {
+                        reflect_control(last_control, position)
+                    } else {
+                        point(values[0], values[1], position)
+                    };
+                    let control_2 = point(values[stride - 4], values[stride - 3], position);
+                    let end = point(values[stride - 2], values[stride - 1], position);
+
+                    for i in 1..=CURVE_SEGMENTS {
+                        let t = i as Float / CURVE_SEGMENTS as Float;
+                        let u = 1.0 - t;
+                        current.push(
+                            position * (u * u * u)
+                                + control_1 * (3.0 * u * u * t)
+                                + control_2 * (3.0 * u * t * t)
+                                + end * (t * t * t),
+                        );
+                    }
+
+                    position = end;
+                    last_control = Some(control_2);
+                }
+            }
+            Command::EllipticalArc(..) => {
+                for values in parameters.chunks_exact(7) {
+                    let end = point(values[5], values[6], position);
+                    current.append(&mut arc_points(
+                        position,
+                        end,
+                        vec2(values[0], values[1]),
+                        values[2].to_radians(),
+                        values[3] != 0.0,
+                        values[4] != 0.0,
+                    ));
+                    position = end;
+                }
+                last_control = None;
+            }
+            Command::Close => unreachable!(),
+        }
+    }
+
+    paths.push(current);
+    paths
+}
+
+/// The first control point of a smooth curve is the reflection of the previous
+/// control point around the current position.
+fn reflect_control(last_control: Option<Vec2>, position: Vec2) -> Vec2 {
+    match last_control {
+        Some(control) => position + (position - control),
+        None => position,
+    }
+}
+
+/// Points along an svg elliptical arc, excluding the start point.
+/// Follows the conversion from the svg specification:
+/// https://www.w3.org/TR/SVG/implnote.html#ArcConversionEndpointToCenter
+fn arc_points(
+    start: Vec2,
+    end: Vec2,
+    radius: Vec2,
+    rotation: Float,
+    large_arc: bool,
+    sweep: bool,
+) -> Vec<Vec2> {
+    let mut rx = radius.x.abs();
+    let mut ry = radius.y.abs();
+    if rx == 0.0 || ry == 0.0 || start == end {
+        return vec![end];
+    }
+
+    let half = (start - end) / 2.0;
+    let p = half.rotate(-rotation);
+
+    // Scale up radii that are too small to reach the end point.
+    let lambda = (p.x / rx).powi(2) + (p.y / ry).powi(2);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let numerator = rx * rx * ry * ry - rx * rx * p.y * p.y - ry * ry * p.x * p.x;
+    let denominator = rx * rx * p.y * p.y + ry * ry * p.x * p.x;
+    let sign = if large_arc != sweep { 1.0 } else { -1.0 };
+    let coefficient = sign * (numerator / denominator).max(0.0).sqrt();
+    let center_p = vec2(rx * p.y / ry, -ry * p.x / rx) * coefficient;
+    let center = center_p.rotate(rotation) + (start + end) / 2.0;
+
+    let angle = |u: Vec2, v: Vec2| (u.x * v.y - u.y * v.x).atan2(u.dot(v));
+    let u = vec2((p.x - center_p.x) / rx, (p.y - center_p.y) / ry);
+    let v = vec2((-p.x - center_p.x) / rx, (-p.y - center_p.y) / ry);
+    let start_angle = angle(vec2(1.0, 0.0), u);
+    let mut delta = angle(u, v) % TAU;
+    if !sweep && delta > 0.0 {
+        delta -= TAU;
+    } else if sweep && delta < 0.0 {
+        delta += TAU;
+    }
+
+    let mut points: Vec<Vec2> = (1..CURVE_SEGMENTS)
+        .map(|i| {
+            let theta = start_angle + delta * i as Float / CURVE_SEGMENTS as Float;
+            center + vec2(rx * theta.cos(), ry * theta.sin()).rotate(rotation)
+        })
+        .collect();
+    // End exactly on the end point.
+    points.push(end);
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn view_box_or_clear_error() {
+        let content =
+            r#"<svg viewBox="10, 20 200 150"><line x1="10" y1="20" x2="60" y2="70"/></svg>"#;
+        assert_eq!(
+            view_box(content).unwrap(),
+            (vec2(10.0, 20.0), vec2(200.0, 150.0))
+        );
+        // Imported points are relative to the corner of the `viewBox`.
+        assert_eq!(
+            import_svg(content).unwrap(),
+            [vec![vec2(0.0, 0.0), vec2(50.0, 50.0)]]
+        );
+
+        let missing = view_box(r#"<svg width="10"></svg>"#).unwrap_err();
+        assert!(missing.to_string().contains("no `viewBox`"));
+        let invalid = view_box(r#"<svg viewBox="0 0 200"></svg>"#).unwrap_err();
+        assert!(invalid.to_string().contains("Expected 4 numbers"));
+        assert!(view_box(r#"<svg viewBox="0 0 a b"></svg>"#).is_err());
+    }
+
+    #[test]
+    fn import_lines() {
+        let content = r#"<svg viewBox="0 0 10 10">
+            <path d="M0,0 L5,0 l0,5 Z l1,1 M 1 1 H 3 V 3"/>
+            <line x1="0" y1="0" x2="1" y2="1"/>
+            <polygon points="0,0 1,0 1,1"/>
+        </svg>"#;
+
+        let paths = import_svg(content).unwrap();
+
+        assert_eq!(
+            paths,
+            vec![
+                vec![
+                    vec2(0.0, 0.0),
+                    vec2(5.0, 0.0),
+                    vec2(5.0, 5.0),
+                    vec2(0.0, 0.0)
+                ],
+                vec![vec2(0.0, 0.0), vec2(1.0, 1.0)],
+                vec![vec2(1.0, 1.0), vec2(3.0, 1.0), vec2(3.0, 3.0)],
+                vec![vec2(0.0, 0.0), vec2(1.0, 1.0)],
+                vec![
+                    vec2(0.0, 0.0),
+                    vec2(1.0, 0.0),
+                    vec2(1.0, 1.0),
+                    vec2(0.0, 0.0)
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_two_files() {
+        let directory = Utf8PathBuf::from_path_buf(std::env::temp_dir())
+            .unwrap()
+            .join(format!("plotter_generator_merge_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let first = directory.join("first.svg");
+        let second = directory.join("second.svg");
+        std::fs::write(&first, r#"<svg><path d="M0,0 L1,1"/></svg>"#).unwrap();
+        std::fs::write(
+            &second,
+            r#"<svg><path d="M0,0 L1,1"/><path d="M1,0 L0,1 M2,2 L3,3"/></svg>"#,
+        )
+        .unwrap();
+
+        let layers = merge_files(&[first, second.clone(), second], false, &[]).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        let summary: Vec<(&str, usize)> = layers
+            .iter()
+            .map(|layer| (layer.name.as_str(), layer.paths.len()))
+            .collect();
+        assert_eq!(summary, vec![("first", 1), ("second", 3), ("second-2", 3)]);
+    }
+
+    #[test]
+    fn split_red_and_blue_lines() {
+        let content = r##"<svg viewBox="0 0 10 10">
+            <path d="M0,0 L1,1" stroke="#ff0000"/>
+            <g stroke="#0000ff">
+                <path d="M1,0 L0,1"/>
+                <line x1="0" y1="0" x2="2" y2="2" style="fill:none; stroke: #e01010"/>
+            </g>
+            <polyline points="3,3 4,4"/>
+        </svg>"##;
+        let red: Color = "#ff0000".parse().unwrap();
+        let blue: Color = "#0000ff".parse().unwrap();
+
+        let paths = import_svg_with_colors(content).unwrap();
+        let colors: Vec<String> = paths.iter().map(|(color, _)| color.to_string()).collect();
+        assert_eq!(colors, ["#ff0000", "#0000ff", "#e01010", "#000000"]);
+
+        let groups = split_by_color(paths.clone(), &[red, blue]);
+        let summary: Vec<(Color, usize)> = groups
+            .iter()
+            .map(|(color, paths)| (*color, paths.len()))
+            .collect();
+        // Black is as far from red as from blue, and goes to the first.
+        assert_eq!(summary, vec![(red, 3), (blue, 1)]);
+
+        assert_eq!(split_by_color(paths, &[]).len(), 4);
+    }
+
+    #[test]
+    fn split_named_colors() {
+        let content = r##"<svg viewBox="0 0 10 10">
+            <path d="M0,0 L1,1" stroke="red"/>
+            <path d="M1,0 L0,1" stroke="blue"/>
+            <line x1="0" y1="0" x2="2" y2="2" style="stroke: rgb(0,255,0)"/>
+            <g stroke="Blue">
+                <polyline points="3,3 4,4" stroke="none"/>
+            </g>
+        </svg>"##;
+
+        let paths = import_svg_with_colors(content).unwrap();
+        let colors: Vec<String> = paths.iter().map(|(color, _)| color.to_string()).collect();
+        assert_eq!(colors, ["#ff0000", "#0000ff", "#00ff00", "#0000ff"]);
+
+        let groups = split_by_color(paths, &[]);
+        let summary: Vec<(String, usize)> = groups
+            .iter()
+            .map(|(color, paths)| (color.to_string(), paths.len()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("#ff0000".to_string(), 1),
+                ("#0000ff".to_string(), 2),
+                ("#00ff00".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn import_arc() {
+        // Half circle with radius 5 around (5, 0).
+        let content = r#"<svg><path d="M0,0 A5,5 0 0 1 10,0"/></svg>"#;
+
+        let paths = import_svg(content).unwrap();
+
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].len(), CURVE_SEGMENTS + 1);
+        for point in &paths[0] {
+            assert!(((*point - vec2(5.0, 0.0)).len() - 5.0).abs() < 1e-3);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use color_eyre::{eyre::bail, Result};
+
+use crate::vec::Vec2;
+
+/// A group of paths that is plotted together, for example with the same pen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+    pub name: String,
+    pub paths: Vec<Vec<Vec2>>,
+}
+
+impl Layer {
+    pub fn new(name: impl Into<String>, paths: Vec<Vec<Vec2>>) -> Self {
+        Self {
+            name: name.into(),
+            paths,
+        }
+    }
+}
+
+/// Puts the layers named in `order` first, in that order.
+/// Layers not mentioned keep their original order, after the named ones.
+pub fn order_layers(mut layers: Vec<Layer>, order: &[String]) -> Result<Vec<Layer>> {
+    let mut ordered = vec![];
+
+    for name in order {
+        let Some(index) = layers.iter().position(|layer| &layer.name == name) else {
+            let available = layers
+                .iter()
+                .chain(&ordered)
+                .map(|layer| layer.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            bail!("There is no layer named `{name}`. Available layers: {available}");
+        };
+
+        ordered.push(layers.remove(index));
+    }
+
+    ordered.append(&mut layers);
+    Ok(ordered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn names(layers: &[Layer]) -> Vec<&str> {
+        layers.iter().map(|layer| layer.name.as_str()).collect()
+    }
+
+    #[test]
+    fn order_layers_puts_named_layers_first() {
+        let layers = vec![
+            Layer::new("a", vec![]),
+            Layer::new("b", vec![]),
+            Layer::new("c", vec![]),
+        ];
+
+        let ordered = order_layers(layers, &["c".to_string(), "a".to_string()]).unwrap();
+
+        assert_eq!(names(&ordered), vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn order_layers_unknown_name() {
+        let layers = vec![Layer::new("a", vec![])];
+
+        assert!(order_layers(layers, &["b".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use crate::vec::{Float, Vec2};
+
+/// Lindenmayer system, drawn with turtle graphics.
+///
+/// When drawing, `F` and `G` move forward one unit, `+` turns counter-clockwise
+/// by `angle` and `-` turns clockwise. Other symbols are only used in the rules.
+#[derive(Debug, Clone)]
+pub struct LSystem {
+    pub axiom: String,
+    pub rules: Vec<(char, String)>,
+    /// Turning angle in degrees.
+    pub angle: Float,
+}
+
+impl LSystem {
+    /// Applies the rules `iterations` times to the axiom.
+    pub fn expand(&self, iterations: usize) -> String {
+        let mut current = self.axiom.clone();
+
+        for _ in 0..iterations {
+            let mut next = String::with_capacity(current.len() * 2);
+            for symbol in current.chars() {
+                match self.rules.iter().find(|(from, _)| *from == symbol) {
+                    Some((_, to)) => next.push_str(to),
+                    None => next.push(symbol),
+                }
+            }
+            current = next;
+        }
+
+        current
+    }
+
+    /// Draws the expanded system as a single line, starting at the origin
+    /// and heading along the positive x-axis.
+    pub fn draw(&self, iterations: usize) -> Vec<Vec2> {
+        let angle = self.angle.to_radians();
+
+        let mut position = Vec2::ZERO;
+        let mut heading: Float = 0.0;
+        let mut points = vec![position];
+
+        for symbol in self.expand(iterations).chars() {
+            match symbol {
+                'F' | 'G' => {
+                    position = position + Vec2::from_angle(heading);
+                    points.push(position);
+                }
+                '+' => heading += angle,
+                '-' => heading -= angle,
+                _ => {}
+            }
+        }
+
+        points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec2;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn expand_applies_rules() {
+        let system = LSystem {
+            axiom: "A".to_string(),
+            rules: vec![('A', "AB".to_string()), ('B', "A".to_string())],
+            angle: 90.0,
+        };
+
+        assert_eq!(system.expand(4), "ABAABABA");
+    }
+
+    #[test]
+    fn draw_square() {
+        let system = LSystem {
+            axiom: "F+F+F+F".to_string(),
+            rules: vec![],
+            angle: 90.0,
+        };
+
+        let points = system.draw(0);
+
+        assert_eq!(points.len(), 5);
+        assert!((points[2] - vec2(1.0, 1.0)).len() < 1e-6);
+        assert!(points[4].len() < 1e-6);
+    }
+}
@@ -1,37 +1,246 @@
 use std::{fs, str::FromStr};
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use chrono::Local;
-use clap::{Parser, Subcommand};
-use color_eyre::{eyre::Context, Result};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use color::Color;
+use color_eyre::{
+    eyre::{bail, eyre, Context},
+    Result,
+};
+use expression::Expression;
+use gcode::GcodeSettings;
+use generators::{flow::Boundary, motif::MotifShape, shape::NamedShape};
 use itertools::Itertools;
-use log::LevelFilter;
+use layer::Layer;
+use log::{info, warn, LevelFilter};
+use paths::{GridSize, Insets, MirrorLine, PathOrder, Sketchy, StartPoint};
+use profile::Profiles;
+use rand::{rngs::StdRng, SeedableRng};
+use scatter::Distribution;
 use simplelog::{ColorChoice, ConfigBuilder, TermLogger, TerminalMode};
-use svg::{
-    node::element::{path::Data, Path},
-    Document,
-};
-use vec::{vec2, Vec2};
+use svg_output::SvgSettings;
+use vec::{vec2, Float, Vec2};
 
+mod color;
+mod expression;
+mod gcode;
+mod generators;
+mod geometry;
+mod hpgl;
+mod import;
+mod layer;
+mod lsystem;
+mod marching_squares;
+mod noise;
+mod npy;
+mod paths;
+mod poster;
+mod profile;
+mod reproduce;
+mod scatter;
+mod svg_output;
+mod text;
 mod vec;
 
 const OUTPUT_DIR: &str = "output";
+const PROFILES_FILE: &str = "profiles.ini";
+/// Height of the text of `--legend`, as a fraction of the shortest side of the canvas.
+const LEGEND_HEIGHT: Float = 0.02;
 
 #[derive(Parser, Debug)]
-#[command(version, about)]
+#[command(version, about, args_override_self = true)]
 struct Args {
-    /// Width of the canvas.
-    #[arg(short, long, default_value_t = 100.0)]
-    width: f32,
-    /// Height of the canvas.
-    #[arg(short = 'H', long, default_value_t = 100.0)]
-    height: f32,
+    /// Width of the canvas. Defaults to 100, or for commands that read svg files,
+    /// to the width of the `viewBox` of the first file.
+    #[arg(short, long)]
+    width: Option<Float>,
+    /// Height of the canvas. Defaults to 100, or for commands that read svg files,
+    /// to the height of the `viewBox` of the first file.
+    #[arg(short = 'H', long)]
+    height: Option<Float>,
+    /// Seed for everything random. A random seed is used if not given.
+    #[arg(short, long)]
+    seed: Option<u64>,
+    /// Output file formats, as a comma separated list. Every format is saved
+    /// with the same file name.
+    #[arg(short, long, value_enum, value_delimiter = ',', default_value = "svg")]
+    format: Vec<Format>,
+    /// Order in which the layers are plotted, as a comma separated list of layer names.
+    /// Layers that are not mentioned are plotted afterwards.
+    #[arg(long, value_delimiter = ',')]
+    layer_order: Vec<String>,
+    /// Maximum amount of points in a single continuous line.
+    /// Longer lines are split up, for plotters that can't handle long moves.
+    #[arg(long)]
+    max_points: Option<usize>,
+    /// Only keep this many of the longest lines, and drop the rest.
+    /// Tames drawings with a lot of short lines.
+    #[arg(long)]
+    keep_longest: Option<usize>,
+    /// Only draw every this many-th line, to split a long plot over several sessions.
+    /// Use `--subpath-offset` to choose which lines.
+    #[arg(long)]
+    subpath_stride: Option<usize>,
+    /// Which of the lines to draw with `--subpath-stride`, from 0 up to the stride.
+    /// Plotting every offset in turn gives the whole drawing.
+    #[arg(long, default_value_t = 0)]
+    subpath_offset: usize,
+    /// Replace every line with its outline at this distance. Closed lines grow,
+    /// or shrink for a negative distance.
+    #[arg(long)]
+    outline: Option<Float>,
+    /// Size of the grid cells used to find the outlines. Smaller is more accurate, but slower.
+    #[arg(long, default_value_t = 0.25)]
+    outline_resolution: Float,
+    /// Go back and forth over this length at both ends of every line, to make the
+    /// ends look heavier, like a pen pressed down harder.
+    #[arg(long)]
+    taper: Option<Float>,
+    /// Only draw inside this polygon, given as `x,y;x,y;...` in canvas coordinates.
+    /// Lines leaving the polygon are split up.
+    #[arg(long, value_delimiter = ';')]
+    mask: Vec<Vec2>,
+    /// When a line starts this close to where the previous one ended, the pen is not lifted
+    /// in between. Only used for G-code and HPGL output.
+    #[arg(long)]
+    join_tolerance: Option<Float>,
+    /// Svg file with a line to bend the drawing along. The width of the canvas goes
+    /// along the line, its height across it.
+    #[arg(long)]
+    spine: Option<Utf8PathBuf>,
+    /// Width of the drawing when bent along `--spine`.
+    #[arg(long, default_value_t = 10.0)]
+    spine_width: Float,
+    /// Let the generators fill a band this wide around the canvas as well, so the drawing
+    /// runs off the edges, for plots without a blank border.
+    #[arg(long)]
+    bleed: Option<Float>,
+    /// Cut off everything outside of the canvas.
+    #[arg(long)]
+    clip: bool,
+    /// Bands along the sides of the canvas that the plotter can't reach, as
+    /// `top,right,bottom,left`. Everything inside those bands is cut off.
+    #[arg(long)]
+    unreachable: Option<Insets>,
+    /// Draw a rectangle around the reachable part of the canvas in a separate `border` layer,
+    /// optionally this far inside of it, as `--border=<distance>`.
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "0")]
+    border: Option<Float>,
+    /// Draw a legend in the top left corner of the reachable part of the canvas, with a
+    /// hatched square and the name of every layer, in a separate `legend` layer.
+    #[arg(long)]
+    legend: bool,
+    /// Add a copy of the drawing, mirrored in the line through two points, as
+    /// `x1,y1,x2,y2`, in a separate `mirror` layer.
+    #[arg(long)]
+    mirror: Option<MirrorLine>,
+    /// Draw a small circle with this radius at the start of every line, in a separate
+    /// `start-dots` layer. Shows where the lines start, or serves as registration marks.
+    #[arg(long)]
+    start_dots: Option<Float>,
+    /// Stop with an error when nothing is left to draw after all the processing,
+    /// instead of saving an empty drawing.
+    #[arg(long)]
+    fail_on_empty: bool,
+    /// Print statistics about every layer: the amount of lines and points, the length,
+    /// how much the lines turn, and roughly how much area is covered with ink.
+    #[arg(long)]
+    stats: bool,
+    /// Print how much line length ends up in every cell of a grid over the canvas,
+    /// as `<columns>x<rows>`. Shows where the drawing concentrates.
+    #[arg(long)]
+    density_map: Option<GridSize>,
+    /// Split the drawing over several sheets, as `<columns>x<rows>`. Every sheet is saved
+    /// separately, with registration crosses at the corners shared with other sheets.
+    #[arg(long)]
+    poster: Option<GridSize>,
+    /// Multiply all output coordinates, and the size of the canvas, by this factor.
+    /// Lets you work in convenient units, and convert to the units of the plotter at the end.
+    #[arg(long, default_value_t = 1.0)]
+    scale: Float,
+    /// Multiply the x coordinates, and the width of the canvas, by this factor, on top of
+    /// `--scale`. Stretches the drawing.
+    #[arg(long, default_value_t = 1.0)]
+    scale_x: Float,
+    /// Multiply the y coordinates, and the height of the canvas, by this factor, on top of
+    /// `--scale`. Stretches the drawing.
+    #[arg(long, default_value_t = 1.0)]
+    scale_y: Float,
+    /// Remove duplicate points, and points outside the canvas at the ends of lines.
+    #[arg(long)]
+    trim: bool,
+    /// Resample the lines to follow their curvature: straight stretches keep only their
+    /// ends, and curves get extra points until they stay within this distance of the
+    /// smooth curve through the original points.
+    #[arg(long)]
+    adaptive_resample: Option<Float>,
+    /// Only keep this fraction (0 to 1) of the points of every line, those where it
+    /// turns the most. Keeps sharp corners, even when leaving out most of the points.
+    #[arg(long)]
+    thin_by_importance: Option<Float>,
+    /// Draw every line several times, with the points moved a bit every time, like a
+    /// pencil sketch, as `<passes>,<wobble>`. The points stay within the wobble distance.
+    #[arg(long)]
+    sketchy: Option<Sketchy>,
+    /// Reverse every other line and connect the ends of the lines, so fills of parallel
+    /// lines are drawn as a single zigzag, without lifting the pen.
+    #[arg(long)]
+    reverse_alternate: bool,
+    /// Reorder the lines of every layer to reduce the moves with the pen up.
+    #[arg(long)]
+    optimize_order: bool,
+    /// Where the pen starts, as `x,y` or a corner like `top-left` or `bottom-right`.
+    /// The reordered lines start near it. Implies `--optimize-order`.
+    #[arg(long)]
+    start_point: Option<StartPoint>,
+    /// How to reorder the lines of every layer. `nearest` reduces the moves with the pen up,
+    /// like `--optimize-order`. `importance` draws the long lines near the middle first,
+    /// so a plot that is stopped early still looks complete, and ignores `--start-point`.
+    #[arg(long, value_enum)]
+    order_by: Option<PathOrder>,
+    /// Makes the circles of the generators look hand drawn, by changing their radius
+    /// slowly around the circle, by up to this fraction. Applies to the pads of `circuit`,
+    /// the circle motif of `motif-along` and the circles of `nested-circles`.
+    #[arg(long, default_value_t = 0.0)]
+    circle_wobble: Float,
+    /// How the random starting points of the generators are spread over the canvas.
+    /// Applies to the lines of `flow-field`.
+    #[arg(long, value_enum, default_value_t = Distribution::Uniform)]
+    distribution: Distribution,
+    /// Draw the direction of every corner of the hilbert curve in a separate `debug`
+    /// layer, for the hilbert generators. Useful for tuning the offsets.
+    #[arg(long)]
+    debug_geometry: bool,
+    /// Use the options saved in this profile as defaults. Options given on the
+    /// command line take precedence.
+    #[arg(long)]
+    profile: Option<String>,
+    /// Save the options before the command (except for the seed) as a profile with this
+    /// name, so they can be reused with `--profile`.
+    #[arg(long)]
+    save_profile: Option<String>,
+
+    #[command(flatten)]
+    svg: SvgSettings,
+
+    #[command(flatten)]
+    gcode: GcodeSettings,
 
     #[command(subcommand)]
     command: Commands,
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Svg,
+    Gcode,
+    Hpgl,
+    /// NumPy array of all points, see `npy::paths_to_npy` for the layout.
+    Npy,
+}
+
+#[derive(Subcommand, Debug, Clone)]
 enum Commands {
     /// Hilbert curve with 2 wonky offset lines.
     WonkyHilbert {
@@ -41,7 +250,7 @@ enum Commands {
 
         /// Offset of the wonky lines.
         #[arg(short, long, default_value_t = 1.0)]
-        offset: f32,
+        offset: Float,
     },
     /// Hilbert curve.
     Hilbert {
@@ -55,7 +264,481 @@ enum Commands {
 
         /// Offset between the lines.
         #[arg(short, long, default_value_t = 1.0)]
-        offset: f32,
+        offset: Float,
+    },
+    /// Hilbert curve with random holes in it, for a worn look.
+    HilbertHoles {
+        /// Amount of iterations on the hilbert curve.
+        #[arg(short, long, default_value_t = 5)]
+        iterations: usize,
+
+        /// Chance (0 to 1) that a cell of the curve is left out.
+        #[arg(short = 'p', long, default_value_t = 0.1)]
+        hole_probability: Float,
+    },
+    /// Hilbert curve that looks raised off the paper, with a moved copy behind it
+    /// and short segments connecting the two.
+    HilbertRelief {
+        /// Amount of iterations on the hilbert curve.
+        #[arg(short, long, default_value_t = 4)]
+        iterations: usize,
+
+        /// How far the copy is moved, as `x,y`.
+        #[arg(short, long, default_value = "1,-1")]
+        depth: Vec2,
+    },
+    /// Hilbert curve that is more detailed where the image has more contrast.
+    AdaptiveHilbert {
+        /// Image to base the detail on. It is stretched over the whole canvas.
+        image: Utf8PathBuf,
+
+        /// Minimum amount of iterations, used in flat areas of the image.
+        #[arg(long, default_value_t = 3)]
+        min_iter: usize,
+
+        /// Maximum amount of iterations, used in detailed areas of the image.
+        #[arg(long, default_value_t = 7)]
+        max_iter: usize,
+
+        /// Standard deviation of the brightness (0 to 1) above which an area
+        /// counts as detailed.
+        #[arg(short, long, default_value_t = 0.05)]
+        threshold: Float,
+    },
+    /// Organic blobs, the outlines of the high areas of a noise field.
+    Blobs {
+        /// Rough size of a blob.
+        #[arg(long, default_value_t = 20.0)]
+        scale: Float,
+
+        /// Noise level (0 to 1) above which the area is part of a blob.
+        /// Higher values give fewer and smaller blobs.
+        #[arg(short, long, default_value_t = 0.55)]
+        threshold: Float,
+    },
+    /// Cesàro fractal, a square torn open by Koch-like curves.
+    Cesaro {
+        /// Amount of iterations on the curve.
+        #[arg(short, long, default_value_t = 4)]
+        iterations: usize,
+
+        /// Angle of the tears in degrees. 60 gives Koch curves,
+        /// towards 90 the tears reach the center.
+        #[arg(short, long, default_value_t = 85.0)]
+        angle: Float,
+    },
+    /// Circuit board, pads connected by horizontal and vertical traces.
+    Circuit {
+        /// Fraction of the grid cells (0 to 1) that gets a pad.
+        #[arg(short, long, default_value_t = 0.05)]
+        density: Float,
+
+        /// Size of the grid cells the traces are routed over.
+        #[arg(short, long, default_value_t = 2.0)]
+        spacing: Float,
+    },
+    /// Fractal coastline across the canvas, made by midpoint displacement.
+    Coastline {
+        /// Amount of times every segment is split in two.
+        #[arg(short, long, default_value_t = 10)]
+        iterations: usize,
+
+        /// Factor (0 to 1) the displacements are multiplied by with every iteration.
+        /// Higher is more jagged.
+        #[arg(short, long, default_value_t = 0.55)]
+        roughness: Float,
+    },
+    /// Coral like structure grown by diffusion-limited aggregation.
+    Dla {
+        /// Amount of particles that stick to the structure.
+        #[arg(short, long, default_value_t = 3000)]
+        particles: usize,
+
+        /// Size of the steps the particles take, which is also the length of the lines.
+        #[arg(short, long, default_value_t = 0.5)]
+        step: Float,
+    },
+    /// Contour lines of a math expression in `x` and `y`.
+    Field {
+        /// Expression to draw, for example `x*x + y*y` or `sin(5*x) * cos(5*y)`.
+        /// `x` and `y` run from -1 to 1 along the shortest side of the canvas.
+        expr: String,
+
+        /// Amount of contour lines, evenly spaced between the lowest and highest value.
+        #[arg(short, long, default_value_t = 10)]
+        levels: usize,
+
+        /// Amount of grid cells along the longest side of the canvas.
+        #[arg(short, long, default_value_t = 200)]
+        resolution: usize,
+    },
+    /// Lines flowing along a field of swirls.
+    FlowField {
+        /// Amount of lines.
+        #[arg(short, long, default_value_t = 300)]
+        lines: usize,
+
+        /// Amount of steps every line takes.
+        #[arg(long, default_value_t = 200)]
+        steps: usize,
+
+        /// Length of a single step.
+        #[arg(long, default_value_t = 0.5)]
+        step_size: Float,
+
+        /// Rough size of the swirls.
+        #[arg(short, long, default_value_t = 40.0)]
+        scale: Float,
+
+        /// What happens to lines that reach the edge of the canvas.
+        #[arg(short, long, value_enum, default_value_t = Boundary::Clip)]
+        boundary: Boundary,
+    },
+    /// Golden spiral of quarter circles, in squares that follow the Fibonacci sequence.
+    GoldenSpiral {
+        /// Amount of times the spiral goes around.
+        #[arg(short, long, default_value_t = 3)]
+        turns: usize,
+
+        /// Also draw the squares, in a separate `squares` layer.
+        #[arg(short, long)]
+        squares: bool,
+    },
+    /// Gosper curve filling a hexagon, or a shape from an svg file.
+    Gosper {
+        /// Amount of iterations on the curve.
+        #[arg(short, long, default_value_t = 4)]
+        iterations: usize,
+
+        /// Svg file whose first line is the shape to fill, instead of a hexagon.
+        /// It is used as is, so it should be drawn on a canvas of the same size.
+        #[arg(long)]
+        shape: Option<Utf8PathBuf>,
+    },
+    /// Grid of harmonograph figures, with different frequencies in every cell.
+    HarmonographGrid {
+        /// Amount of columns in the grid.
+        #[arg(short, long, default_value_t = 5)]
+        cols: usize,
+
+        /// Amount of rows in the grid.
+        #[arg(short, long, default_value_t = 5)]
+        rows: usize,
+
+        /// How much smaller the figures get every turn, as a fraction.
+        #[arg(short, long, default_value_t = 0.05)]
+        decay: Float,
+    },
+    /// Parallel lines covering the canvas. Combine with `--reverse-alternate` to draw
+    /// them as a single line.
+    Hatch {
+        /// Distance between the lines.
+        #[arg(short, long, default_value_t = 1.0)]
+        spacing: Float,
+
+        /// Angle of the lines in degrees, 0 is horizontal.
+        #[arg(short, long, default_value_t = 45.0)]
+        angle: Float,
+
+        /// Svg file whose first line is the shape to fill, instead of the whole canvas.
+        /// It is used as is, so it should be drawn on a canvas of the same size.
+        #[arg(long)]
+        shape: Option<Utf8PathBuf>,
+    },
+    /// Islamic star pattern, a star inside a regular polygon in every cell of a grid.
+    IslamicStar {
+        /// Amount of points of the stars, and sides of the polygons around them.
+        #[arg(short, long, default_value_t = 8)]
+        n: usize,
+
+        /// Amount of cells in every row.
+        #[arg(short, long, default_value_t = 6)]
+        cols: usize,
+
+        /// Amount of rows of cells.
+        #[arg(short, long, default_value_t = 6)]
+        rows: usize,
+    },
+    /// Grid of isometric cubes.
+    IsoGrid {
+        /// Amount of cubes in every row.
+        #[arg(short, long, default_value_t = 8)]
+        cols: usize,
+
+        /// Amount of rows of cubes.
+        #[arg(short, long, default_value_t = 8)]
+        rows: usize,
+    },
+    /// A single open Koch curve across the width of the canvas.
+    KochLine {
+        /// Amount of iterations on the curve.
+        #[arg(short, long, default_value_t = 4)]
+        iterations: usize,
+    },
+    /// Lévy C curve.
+    LevyC {
+        /// Amount of iterations on the curve.
+        #[arg(short, long, default_value_t = 12)]
+        iterations: usize,
+    },
+    /// Low poly look, the canvas split into triangles with random sides flipped.
+    LowPoly {
+        /// Amount of times every triangle is split into four.
+        #[arg(short, long, default_value_t = 4)]
+        subdivisions: usize,
+
+        /// Also hatch every triangle, densely at the left and sparsely at the right,
+        /// in a separate `hatch` layer.
+        #[arg(long)]
+        hatch: bool,
+    },
+    /// Maze with exactly one way from the top left to the bottom right.
+    Maze {
+        /// Amount of cells in every row.
+        #[arg(short, long, default_value_t = 20)]
+        cols: usize,
+
+        /// Amount of rows of cells.
+        #[arg(short, long, default_value_t = 20)]
+        rows: usize,
+
+        /// Also draw the way through the maze, in a separate `solution` layer.
+        #[arg(short, long)]
+        solve: bool,
+    },
+    /// Small motifs placed along a hilbert curve.
+    MotifAlong {
+        /// Amount of iterations on the hilbert curve that guides the motifs.
+        #[arg(short, long, default_value_t = 3)]
+        iterations: usize,
+
+        /// Motif to place.
+        #[arg(short, long, value_enum, default_value_t = MotifShape::Arrow)]
+        motif: MotifShape,
+
+        /// Size of the motif.
+        #[arg(long, default_value_t = 3.0)]
+        motif_size: Float,
+
+        /// Distance between the motifs, along the curve.
+        #[arg(short, long, default_value_t = 5.0)]
+        spacing: Float,
+
+        /// Distance of the motifs from the curve, to its side.
+        #[arg(short, long, default_value_t = 0.0)]
+        offset: Float,
+    },
+    /// Combines several svg files, every file becomes its own layer.
+    Merge {
+        /// Files to merge. The layers are named after the files.
+        #[arg(required = true)]
+        files: Vec<Utf8PathBuf>,
+
+        /// Split every file into a layer per stroke color, to plot each color
+        /// with its own pen.
+        #[arg(long)]
+        split_colors: bool,
+
+        /// Colors of the pens, as a comma separated list. With `--split-colors`, every
+        /// line goes to the layer of the closest of these colors instead of its own color.
+        #[arg(long, value_delimiter = ',')]
+        palette: Vec<Color>,
+    },
+    /// Halfway between two svg files, morphing every line of the first into the line
+    /// at the same position in the second.
+    Morph {
+        /// File to start from.
+        from: Utf8PathBuf,
+
+        /// File to end at.
+        to: Utf8PathBuf,
+
+        /// How far along the morph is, 0 gives `from` and 1 gives `to`.
+        #[arg(short, long, default_value_t = 0.5)]
+        t: Float,
+    },
+    /// Circle with three circles inside it, each with three circles inside it, and so on.
+    NestedCircles {
+        /// Amount of levels of circles inside circles.
+        #[arg(short, long, default_value_t = 4)]
+        depth: usize,
+    },
+    /// Concentric polygons, each one turned a bit further and touching the one around it,
+    /// which together look like a spiral.
+    NestedPolygons {
+        /// Amount of sides of every polygon.
+        #[arg(short, long, default_value_t = 4)]
+        sides: usize,
+
+        /// Amount of polygons.
+        #[arg(short, long, default_value_t = 30)]
+        count: usize,
+
+        /// How much every polygon is turned compared to the one around it, in degrees.
+        #[arg(short, long, default_value_t = 5.0)]
+        rotation: Float,
+    },
+    /// Generates an svg from this program again, with the arguments stored in it,
+    /// and checks that the result is the same.
+    Reproduce {
+        /// Svg file generated by this program.
+        file: Utf8PathBuf,
+    },
+    /// Pythagoras tree fractal.
+    PythagorasTree {
+        /// Recursion depth; depth 0 is only the trunk.
+        #[arg(short, long, default_value_t = 8)]
+        depth: usize,
+
+        /// Angle between the left branch and its parent, in degrees.
+        /// 45 gives a symmetric tree.
+        #[arg(short, long, default_value_t = 45.0)]
+        angle: Float,
+    },
+    /// Dungeon map of rooms within rooms, with a corridor that goes through all of them.
+    RoomMaze {
+        /// Amount of times the rooms are split into four.
+        #[arg(short, long, default_value_t = 3)]
+        depth: usize,
+
+        /// Also draw the corridor through the rooms, in a separate `corridor` layer.
+        #[arg(short, long)]
+        corridor: bool,
+    },
+    /// Well known curve given by an equation, like a heart, as large as fits the canvas.
+    Shape {
+        /// Which curve to draw.
+        #[arg(value_enum)]
+        name: NamedShape,
+
+        /// Amount of points along the curve.
+        #[arg(short, long, default_value_t = 1000)]
+        samples: usize,
+    },
+    /// Sierpiński–Knopp curve, a space-filling curve that fills a triangle.
+    SierpinskiKnopp {
+        /// Amount of iterations on the curve.
+        #[arg(short, long, default_value_t = 10)]
+        iterations: usize,
+    },
+    /// Labyrinth of Truchet tiles with two quarter circles each, forming closed loops.
+    SmithLabyrinth {
+        /// Amount of tiles in every row.
+        #[arg(short, long, default_value_t = 20)]
+        cols: usize,
+
+        /// Amount of rows of tiles.
+        #[arg(short, long, default_value_t = 20)]
+        rows: usize,
+    },
+    /// Snowflake with any amount of arms, each with Koch curves along both sides.
+    Snowflake {
+        /// Amount of arms.
+        #[arg(short, long, default_value_t = 6)]
+        arms: usize,
+
+        /// Amount of iterations on the Koch curves.
+        #[arg(short, long, default_value_t = 3)]
+        iterations: usize,
+    },
+    /// Spiral that wiggles where the image is dark, drawing the image in a single line.
+    SpiralImage {
+        /// Image to draw. It is stretched over the whole canvas.
+        image: Utf8PathBuf,
+
+        /// Amount of times the spiral goes around.
+        #[arg(short, long, default_value_t = 40)]
+        turns: usize,
+    },
+    /// Square spiral from the center outward, as a single line.
+    SquareSpiral {
+        /// Amount of times the spiral goes around.
+        #[arg(short, long, default_value_t = 20)]
+        turns: usize,
+
+        /// Distance between neighbouring windings. It is reduced if the spiral
+        /// doesn't fit the canvas.
+        #[arg(short, long, default_value_t = 2.0)]
+        spacing: Float,
+    },
+    /// Nested squircles, or other superellipses, to use as frames.
+    SquircleFrames {
+        /// Amount of frames.
+        #[arg(short, long, default_value_t = 5)]
+        count: usize,
+
+        /// Exponent of the superellipses. 2 gives ellipses, higher values get closer
+        /// to rectangles.
+        #[arg(short, long, default_value_t = 4.0)]
+        n: Float,
+
+        /// Distance between the frames.
+        #[arg(short, long, default_value_t = 2.0)]
+        spacing: Float,
+    },
+    /// String art, straight strings between pins on spokes around the center that
+    /// together seem to form curves.
+    StringArt {
+        /// Amount of pins along every spoke, which is also the amount of strings
+        /// between two spokes.
+        #[arg(short, long, default_value_t = 30)]
+        pins: usize,
+
+        /// Amount of spokes, and families of strings between them. A single family is
+        /// stretched between two sides of the canvas instead, and 2 is not possible.
+        #[arg(short, long, default_value_t = 4)]
+        curves: usize,
+
+        /// Draw the curves that the strings seem to form, instead of the strings.
+        #[arg(short, long)]
+        envelope: bool,
+    },
+    /// T-square fractal, squares with smaller squares on their corners.
+    TSquare {
+        /// Amount of times smaller squares are added.
+        #[arg(short, long, default_value_t = 5)]
+        iterations: usize,
+    },
+    /// An svg motif repeated in a grid.
+    TileMotif {
+        /// Svg file with the motif. It is scaled to fit a single cell.
+        file: Utf8PathBuf,
+
+        /// Amount of columns in the grid.
+        #[arg(short, long, default_value_t = 5)]
+        cols: usize,
+
+        /// Amount of rows in the grid.
+        #[arg(short, long, default_value_t = 5)]
+        rows: usize,
+
+        /// Rotate every copy by a random angle of up to this many degrees, either way.
+        #[arg(long, default_value_t = 0.0)]
+        rotation: Float,
+
+        /// Move every copy by a random distance of up to this much, along both axes.
+        #[arg(short, long, default_value_t = 0.0)]
+        jitter: Float,
+    },
+    /// Pattern of line tiles that connect across the cells, laid out by wave function collapse.
+    WaveCollapse {
+        /// Amount of tiles in every row.
+        #[arg(short, long, default_value_t = 20)]
+        cols: usize,
+
+        /// Amount of rows of tiles.
+        #[arg(short, long, default_value_t = 20)]
+        rows: usize,
+    },
+    /// Vicsek fractal, a square repeatedly split into 3 by 3 squares of which five are kept.
+    Vicsek {
+        /// Amount of iterations on the fractal.
+        #[arg(short, long, default_value_t = 4)]
+        iterations: usize,
+
+        /// Keep the squares in an X shape instead of a plus shape.
+        #[arg(short, long)]
+        cross: bool,
     },
 }
 
@@ -70,44 +753,1009 @@ fn main() -> Result<()> {
         ColorChoice::Auto,
     )?;
 
-    let args = Args::parse();
+    let command_line: Vec<String> = std::env::args().collect();
+    let args = Args::parse_from(&command_line);
+    let (args, command_line) = match &args.profile {
+        Some(name) => {
+            let profiles = Profiles::load(PROFILES_FILE.into())?;
+            info!("Using profile `{name}`");
+            let command_line = with_profile(command_line, profiles.get(name)?);
+            (Args::parse_from(&command_line), command_line)
+        }
+        None => (args, command_line),
+    };
+
+    // Reproducing replaces the whole command line by the one from the file.
+    let (args, command_line, original) = match &args.command {
+        Commands::Reproduce { file } => {
+            let content =
+                fs::read_to_string(file).wrap_err_with(|| format!("Could not read `{file}`"))?;
+            let command_line = command_line
+                .into_iter()
+                .take(1)
+                .chain(reproduce::read_arguments(&content)?)
+                .collect_vec();
+            info!("Reproducing `{}`", command_line[1..].join(" "));
+            (
+                Args::try_parse_from(&command_line)?,
+                command_line,
+                Some(content),
+            )
+        }
+        _ => (args, command_line, None),
+    };
+
+    if let Some(name) = &args.save_profile {
+        let mut profiles = Profiles::load(PROFILES_FILE.into())?;
+        profiles.set(name, args.arguments());
+        profiles.save(PROFILES_FILE.into())?;
+        info!("Saved profile `{name}` to `{PROFILES_FILE}`");
+    }
 
     let output_dir = Utf8PathBuf::from_str(OUTPUT_DIR)?;
     if !output_dir.exists() {
         fs::create_dir(&output_dir)?;
     }
 
-    let size = vec2(args.width, args.height);
+    let size = canvas_size(&args)?;
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("Seed: {seed}");
+
+    let layers = build_layers(&args, size, seed)?;
+
+    if !args.clip && args.bleed.is_none() {
+        // Points on the edge of the canvas can end up just outside of it by rounding.
+        let tolerance = vec2(1.0, 1.0) * 1e-4 * size.x.max(size.y);
+        let (min, max) = (-tolerance, size + tolerance);
+        let outside: usize = layers
+            .iter()
+            .map(|layer| paths::out_of_bounds_count(&layer.paths, min, max))
+            .sum();
+        if outside > 0 {
+            warn!("{outside} points are outside of the canvas, use `--clip` to cut them off");
+        }
+    }
+
+    if args.stats {
+        let stroke_width = match args.svg.relative_stroke {
+            Some(fraction) => fraction * size.x.min(size.y),
+            None => args.svg.stroke_width,
+        };
+        for layer in &layers {
+            let points: usize = layer.paths.iter().map(Vec::len).sum();
+            let length: Float = layer
+                .paths
+                .iter()
+                .flat_map(|path| path.iter().tuple_windows())
+                .map(|(&a, &b)| (b - a).len())
+                .sum();
+            let turning: Float = layer.paths.iter().map(|p| geometry::total_turning(p)).sum();
+            let ink = paths::ink_estimate(&layer.paths, stroke_width);
+            // Lines closer together than half the width of the pen end up mostly on top
+            // of each other. Lines that are just touching side by side still count twice.
+            let covered = paths::covered_length(&layer.paths, stroke_width / 2.0);
+            info!(
+                "Layer `{}`: {} lines, {} points, length {:.1} ({:.1} without overlaps), turning {:.1} turns, ink {:.1}",
+                layer.name,
+                layer.paths.len(),
+                points,
+                length,
+                covered,
+                turning / vec::consts::TAU,
+                ink
+            );
+        }
+    }
 
-    let mut document = Document::new().set("viewBox", (0.0, 0.0, size.x, size.y));
+    if let Some(grid) = args.density_map {
+        let paths = layers
+            .iter()
+            .flat_map(|layer| layer.paths.clone())
+            .collect_vec();
+        let map = paths::density_map(&paths, size, grid.columns, grid.rows);
+        info!("Line length per cell:");
+        for row in map {
+            info!(
+                "{}",
+                row.iter().map(|length| format!("{length:8.1}")).join("")
+            );
+        }
+    }
+
+    let (layers, size) = scale_output(layers, size, args.scale_factor());
+
+    let local_time = Local::now();
+    let timestamp = local_time.format("%Y-%m-%d_%H-%M-%S");
+
+    let base = output_dir.join(format!("output_{timestamp}"));
+    match args.poster {
+        Some(grid) => save_poster(&args, &layers, size, seed, &command_line, grid, &base)?,
+        None => save_output(
+            &args,
+            &layers,
+            size,
+            seed,
+            &command_line,
+            original.as_deref(),
+            &base,
+        )?,
+    }
+
+    Ok(())
+}
+
+/// Size of the canvas from `--width` and `--height`. What is not given comes from the
+/// first svg file for commands that read them, and is 100 otherwise.
+fn canvas_size(args: &Args) -> Result<Vec2> {
+    let file = match &args.command {
+        Commands::Merge { files, .. } => files.first(),
+        Commands::Morph { from, .. } => Some(from),
+        _ => None,
+    };
+
+    let default = match (args.width, args.height, file) {
+        (Some(_), Some(_), _) | (_, _, None) => vec2(100.0, 100.0),
+        (_, _, Some(file)) => import::file_size(file)?,
+    };
+    Ok(vec2(
+        args.width.unwrap_or(default.x),
+        args.height.unwrap_or(default.y),
+    ))
+}
+
+/// Saves the drawing in every requested format. The names of all files start with `base`.
+/// `original` is the content of the file that is being reproduced, if any.
+fn save_output(
+    args: &Args,
+    layers: &[Layer],
+    size: Vec2,
+    seed: u64,
+    command_line: &[String],
+    original: Option<&str>,
+    base: &Utf8Path,
+) -> Result<()> {
+    for format in &args.format {
+        match format {
+            Format::Svg => {
+                let arguments = reproduce::reproducible_arguments(
+                    args.arguments(),
+                    reproduce::command_arguments(&Args::command(), command_line),
+                    seed,
+                );
+                let mut rng = StdRng::seed_from_u64(seed);
+                let document = svg_output::layers_to_document(layers, size, &args.svg, &mut rng);
+                let document = reproduce::embed_arguments(document, &arguments);
+
+                if let Some(original) = original {
+                    if import::import_svg(original)? == import::import_svg(&document.to_string())? {
+                        info!("The reproduced drawing is identical");
+                    } else {
+                        warn!("The reproduced drawing is different from the original");
+                    }
+                }
+
+                let output_file = Utf8PathBuf::from(format!("{base}.svg"));
+                svg::save(&output_file, &document)
+                    .wrap_err_with(|| format!("Could not save as `{output_file}`"))?;
+            }
+            Format::Gcode => {
+                // Each layer gets its own file, so the pen can be changed in between.
+                // The index keeps the files sorted in plotting order.
+                for (index, layer) in layers.iter().enumerate() {
+                    let gcode =
+                        gcode::paths_to_gcode(&layer.paths, size, &args.gcode, args.join_tolerance);
+
+                    let output_file =
+                        Utf8PathBuf::from(format!("{base}_{index}_{}.gcode", layer.name));
+                    fs::write(&output_file, gcode)
+                        .wrap_err_with(|| format!("Could not save as `{output_file}`"))?;
+                }
+            }
+            Format::Hpgl => {
+                for (index, layer) in layers.iter().enumerate() {
+                    let hpgl = hpgl::paths_to_hpgl(&layer.paths, size, args.join_tolerance);
+
+                    let output_file =
+                        Utf8PathBuf::from(format!("{base}_{index}_{}.hpgl", layer.name));
+                    fs::write(&output_file, hpgl)
+                        .wrap_err_with(|| format!("Could not save as `{output_file}`"))?;
+                }
+            }
+            Format::Npy => {
+                for (index, layer) in layers.iter().enumerate() {
+                    let npy = npy::paths_to_npy(&layer.paths);
+
+                    let output_file =
+                        Utf8PathBuf::from(format!("{base}_{index}_{}.npy", layer.name));
+                    fs::write(&output_file, npy)
+                        .wrap_err_with(|| format!("Could not save as `{output_file}`"))?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
 
-    match args.command {
+/// Saves every tile of the `--poster` like `save_output`, with the column and row
+/// of the tile added to `base`.
+fn save_poster(
+    args: &Args,
+    layers: &[Layer],
+    size: Vec2,
+    seed: u64,
+    command_line: &[String],
+    grid: GridSize,
+    base: &Utf8Path,
+) -> Result<()> {
+    for tile in poster::poster_tiles(layers, size, grid) {
+        let base = Utf8PathBuf::from(format!("{base}_tile_{}_{}", tile.column, tile.row));
+        save_output(
+            args,
+            &tile.layers,
+            tile.size,
+            seed,
+            command_line,
+            None,
+            &base,
+        )?;
+    }
+    Ok(())
+}
+
+/// Generates the drawing and applies all processing options to it.
+fn build_layers(args: &Args, size: Vec2, seed: u64) -> Result<Vec<Layer>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // The generators fill the canvas with the bleed around it, which is then moved
+    // so the canvas itself starts at the origin again.
+    let bleed = vec2(1.0, 1.0) * args.bleed.unwrap_or(0.0);
+    let layers = generate(args.command.clone(), size + bleed * 2.0, args, &mut rng)?
+        .into_iter()
+        .map(|layer| {
+            let paths = layer
+                .paths
+                .into_iter()
+                .map(|path| path.into_iter().map(|p| p - bleed).collect())
+                .collect();
+            Ok(Layer {
+                paths: post_process(args, size, paths, &mut rng)?,
+                ..layer
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    // The reflection can end up outside of the mask or the canvas, so it is cut again.
+    let mirror = match args.mirror {
+        Some(line) => {
+            let paths = layers
+                .iter()
+                .flat_map(|layer| &layer.paths)
+                .map(|path| geometry::reflect(path, line.from, line.to - line.from))
+                .collect();
+            Some(Layer::new("mirror", cut_paths(args, size, paths)?))
+        }
+        None => None,
+    };
+    let layers = layers.into_iter().chain(mirror).collect_vec();
+    let drawable = layers
+        .iter()
+        .flat_map(|layer| &layer.paths)
+        .any(|path| path.len() >= 2);
+    if args.fail_on_empty && !drawable {
+        bail!("Nothing is left to draw, check the clipping and masking options");
+    }
+    let dots = start_dots_layer(args, &layers, &mut rng);
+    let layers = layers
+        .into_iter()
+        .chain(dots)
+        .chain(border_layer(args, size))
+        .collect_vec();
+    let legend = legend_layer(args, &layers, size);
+    let layers = layers.into_iter().chain(legend).collect();
+
+    layer::order_layers(layers, &args.layer_order)
+}
+
+/// The command line with the profile inserted in front of the arguments. The options
+/// given on the command line replace those of the profile, also the ones that add up
+/// when given more than once, like `--mask`.
+fn with_profile(command_line: Vec<String>, profile: &[String]) -> Vec<String> {
+    let program = Args::command();
+    let given = reproduce::program_options(&program, command_line.get(1..).unwrap_or_default())
+        .into_iter()
+        .filter_map(|(id, _)| id)
+        .collect_vec();
+    let profile = reproduce::program_options(&program, profile)
+        .into_iter()
+        .filter(|(id, _)| id.as_ref().is_none_or(|id| !given.contains(id)))
+        .flat_map(|(_, arguments)| arguments.iter().cloned())
+        .collect_vec();
+
+    let mut arguments = command_line.into_iter();
+    let binary = arguments.next();
+    binary.into_iter().chain(profile).chain(arguments).collect()
+}
+
+impl Args {
+    /// How much the output is scaled along both axes, by `--scale` and `--scale-x/y`.
+    fn scale_factor(&self) -> Vec2 {
+        vec2(self.scale * self.scale_x, self.scale * self.scale_y)
+    }
+
+    /// The command line arguments that reproduce the options before the command,
+    /// except for the seed and the profile options themselves.
+    fn arguments(&self) -> Vec<String> {
+        let mut arguments = vec![];
+        if let Some(width) = self.width {
+            arguments.push(format!("--width={width}"));
+        }
+        if let Some(height) = self.height {
+            arguments.push(format!("--height={height}"));
+        }
+        arguments.push(format!(
+            "--format={}",
+            self.format
+                .iter()
+                .map(|format| format.to_possible_value().unwrap().get_name().to_string())
+                .join(",")
+        ));
+        if !self.layer_order.is_empty() {
+            arguments.push(format!("--layer-order={}", self.layer_order.join(",")));
+        }
+        if let Some(max_points) = self.max_points {
+            arguments.push(format!("--max-points={max_points}"));
+        }
+        if let Some(count) = self.keep_longest {
+            arguments.push(format!("--keep-longest={count}"));
+        }
+        if let Some(stride) = self.subpath_stride {
+            arguments.push(format!("--subpath-stride={stride}"));
+            arguments.push(format!("--subpath-offset={}", self.subpath_offset));
+        }
+        if let Some(distance) = self.outline {
+            arguments.push(format!("--outline={distance}"));
+        }
+        arguments.push(format!("--outline-resolution={}", self.outline_resolution));
+        if let Some(length) = self.taper {
+            arguments.push(format!("--taper={length}"));
+        }
+        if !self.mask.is_empty() {
+            let mask = self
+                .mask
+                .iter()
+                .map(|p| format!("{},{}", p.x, p.y))
+                .join(";");
+            arguments.push(format!("--mask={mask}"));
+        }
+        if let Some(file) = &self.spine {
+            arguments.push(format!("--spine={file}"));
+            arguments.push(format!("--spine-width={}", self.spine_width));
+        }
+        if let Some(tolerance) = self.join_tolerance {
+            arguments.push(format!("--join-tolerance={tolerance}"));
+        }
+        if let Some(bleed) = self.bleed {
+            arguments.push(format!("--bleed={bleed}"));
+        }
+        if self.clip {
+            arguments.push("--clip".to_string());
+        }
+        if let Some(i) = self.unreachable {
+            arguments.push(format!(
+                "--unreachable={},{},{},{}",
+                i.top, i.right, i.bottom, i.left
+            ));
+        }
+        if self.trim {
+            arguments.push("--trim".to_string());
+        }
+        if let Some(deviation) = self.adaptive_resample {
+            arguments.push(format!("--adaptive-resample={deviation}"));
+        }
+        if let Some(fraction) = self.thin_by_importance {
+            arguments.push(format!("--thin-by-importance={fraction}"));
+        }
+        if let Some(sketchy) = self.sketchy {
+            arguments.push(format!("--sketchy={},{}", sketchy.passes, sketchy.wobble));
+        }
+        if self.reverse_alternate {
+            arguments.push("--reverse-alternate".to_string());
+        }
+        if self.optimize_order {
+            arguments.push("--optimize-order".to_string());
+        }
+        if let Some(start) = self.start_point {
+            arguments.push(format!("--start-point={start}"));
+        }
+        if let Some(order) = self.order_by {
+            arguments.push(format!(
+                "--order-by={}",
+                order.to_possible_value().unwrap().get_name()
+            ));
+        }
+        if self.scale != 1.0 {
+            arguments.push(format!("--scale={}", self.scale));
+        }
+        if self.scale_x != 1.0 {
+            arguments.push(format!("--scale-x={}", self.scale_x));
+        }
+        if self.scale_y != 1.0 {
+            arguments.push(format!("--scale-y={}", self.scale_y));
+        }
+        if self.stats {
+            arguments.push("--stats".to_string());
+        }
+        if let Some(grid) = self.density_map {
+            arguments.push(format!("--density-map={}x{}", grid.columns, grid.rows));
+        }
+        if let Some(line) = self.mirror {
+            arguments.push(format!(
+                "--mirror={},{},{},{}",
+                line.from.x, line.from.y, line.to.x, line.to.y
+            ));
+        }
+        if let Some(radius) = self.start_dots {
+            arguments.push(format!("--start-dots={radius}"));
+        }
+        if self.fail_on_empty {
+            arguments.push("--fail-on-empty".to_string());
+        }
+        if let Some(grid) = self.poster {
+            arguments.push(format!("--poster={}x{}", grid.columns, grid.rows));
+        }
+        if let Some(inset) = self.border {
+            arguments.push(format!("--border={inset}"));
+        }
+        if self.legend {
+            arguments.push("--legend".to_string());
+        }
+        if self.circle_wobble != 0.0 {
+            arguments.push(format!("--circle-wobble={}", self.circle_wobble));
+        }
+        if self.distribution != Distribution::Uniform {
+            arguments.push(format!(
+                "--distribution={}",
+                self.distribution.to_possible_value().unwrap().get_name()
+            ));
+        }
+        if self.debug_geometry {
+            arguments.push("--debug-geometry".to_string());
+        }
+        arguments.extend(self.svg.arguments());
+        arguments.extend(self.gcode.arguments());
+        arguments
+    }
+}
+
+/// Runs the generator of the command.
+fn generate(command: Commands, size: Vec2, args: &Args, rng: &mut StdRng) -> Result<Vec<Layer>> {
+    let debug_layer = match command {
+        Commands::WonkyHilbert { iterations, offset }
+        | Commands::Hilbert {
+            iterations, offset, ..
+        } if args.debug_geometry => {
+            let points = hilbert_curve(
+                vec2(0.0, 0.0),
+                vec2(size.x, 0.0),
+                vec2(0.0, size.y),
+                iterations,
+            );
+            Some(Layer::new(
+                "debug",
+                corner_directions(&points, offset.abs()),
+            ))
+        }
+        _ => None,
+    };
+
+    let paths = match command {
+        Commands::Merge {
+            files,
+            split_colors,
+            palette,
+        } => return import::merge_files(&files, split_colors, &palette),
+        Commands::Morph { from, to, t } => {
+            let from = import::import_file(&from)?;
+            let to = import::import_file(&to)?;
+            if from.len() != to.len() {
+                warn!(
+                    "The files have a different amount of lines ({} and {}), the extra lines are left out",
+                    from.len(),
+                    to.len()
+                );
+            }
+            from.iter()
+                .zip(&to)
+                .map(|(a, b)| geometry::morph_pointsets(a, b, t))
+                .collect()
+        }
+        Commands::Reproduce { .. } => bail!("Only a drawing can be reproduced, not a reproduction"),
         Commands::WonkyHilbert { iterations, offset } => {
-            document = wonky_triple_hilbert_curve(document, size, iterations, offset)
+            wonky_triple_hilbert_curve(size, iterations, offset)
         }
         Commands::Hilbert {
             iterations,
             lines,
             offset,
-        } => document = parallel_hilbert_curves(document, size, iterations, lines, offset),
-    }
+        } => parallel_hilbert_curves(size, iterations, lines, offset),
+        Commands::HilbertHoles {
+            iterations,
+            hole_probability,
+        } => {
+            let curve = hilbert_curve(
+                vec2(0.0, 0.0),
+                vec2(size.x, 0.0),
+                vec2(0.0, size.y),
+                iterations,
+            );
+            generators::hilbert_holes::with_holes(&curve, hole_probability, rng)
+        }
+        Commands::HilbertRelief { iterations, depth } => {
+            // Leave room for the copy on the canvas.
+            let room = vec2(depth.x.abs(), depth.y.abs());
+            let start = -vec2(depth.x.min(0.0), depth.y.min(0.0));
+            let curve = hilbert_curve(
+                start,
+                vec2(size.x - room.x, 0.0),
+                vec2(0.0, size.y - room.y),
+                iterations,
+            );
+            generators::hilbert_relief::relief(&curve, depth)
+        }
+        Commands::AdaptiveHilbert {
+            image,
+            min_iter,
+            max_iter,
+            threshold,
+        } => {
+            let path = image;
+            let image = image::open(&path)
+                .wrap_err_with(|| format!("Could not open image `{path}`"))?
+                .to_luma8();
+            if image.width() == 0 || image.height() == 0 {
+                bail!("Image `{path}` is empty");
+            }
 
-    let local_time = Local::now();
-    let timestamp = local_time.format("%Y-%m-%d_%H-%M-%S");
+            vec![generators::adaptive_hilbert::adaptive_hilbert_curve(
+                size, &image, min_iter, max_iter, threshold,
+            )]
+        }
+        Commands::Blobs { scale, threshold } => {
+            if scale <= 0.0 {
+                bail!("The scale of the blobs has to be positive, not {scale}");
+            }
+            generators::blobs::blobs(size, scale, threshold, rng)
+        }
+        Commands::Cesaro { iterations, angle } => {
+            vec![generators::cesaro::cesaro(size, iterations, angle)]
+        }
+        Commands::Circuit { density, spacing } => {
+            generators::circuit::circuit(size, spacing, density, args.circle_wobble, rng)
+        }
+        Commands::Coastline {
+            iterations,
+            roughness,
+        } => vec![generators::coastline::coastline(
+            size, iterations, roughness, rng,
+        )],
+        Commands::Dla { particles, step } => generators::dla::dla(size, particles, step, rng),
+        Commands::Field {
+            expr,
+            levels,
+            resolution,
+        } => {
+            let expression = Expression::parse(&expr)?;
+            generators::field::field_contours(size, &expression, levels, resolution)
+        }
+        Commands::FlowField {
+            lines,
+            steps,
+            step_size,
+            scale,
+            boundary,
+        } => generators::flow::flow_field(
+            size,
+            lines,
+            steps,
+            step_size,
+            scale,
+            boundary,
+            args.distribution,
+            rng,
+        ),
+        Commands::GoldenSpiral { turns, squares } => {
+            let (spiral, outlines) = generators::golden_spiral::golden_spiral(size, turns);
+            if squares {
+                return Ok(vec![
+                    Layer::new("drawing", vec![spiral]),
+                    Layer::new("squares", outlines),
+                ]);
+            }
+            vec![spiral]
+        }
+        Commands::Gosper { iterations, shape } => {
+            let mask = match shape {
+                Some(file) => import::import_file(&file)?
+                    .into_iter()
+                    .next()
+                    .filter(|shape| shape.len() >= 3)
+                    .ok_or_else(|| eyre!("`{file}` has no shape with at least 3 points"))?,
+                None => generators::gosper::canvas_hexagon(size),
+            };
+            generators::gosper::gosper(iterations, &mask)
+        }
+        Commands::HarmonographGrid { cols, rows, decay } => {
+            generators::harmonograph::harmonograph_grid(size, cols, rows, decay)
+        }
+        Commands::Hatch {
+            spacing,
+            angle,
+            shape,
+        } => match shape {
+            Some(file) => {
+                let shape = import::import_file(&file)?
+                    .into_iter()
+                    .next()
+                    .filter(|shape| shape.len() >= 3)
+                    .ok_or_else(|| eyre!("`{file}` has no shape with at least 3 points"))?;
+                generators::hatch::hatch_shape(&shape, spacing, angle)
+            }
+            None => generators::hatch::hatch(size, spacing, angle),
+        },
+        Commands::IslamicStar { n, cols, rows } => {
+            generators::islamic_star::islamic_star(size, n, cols, rows)
+        }
+        Commands::IsoGrid { cols, rows } => generators::iso_grid::iso_grid(size, cols, rows),
+        Commands::KochLine { iterations } => {
+            vec![generators::koch_line::koch_line(size, iterations)]
+        }
+        Commands::LevyC { iterations } => vec![generators::levy_c::levy_c(size, iterations)],
+        Commands::LowPoly {
+            subdivisions,
+            hatch,
+        } => {
+            let (edges, hatching) = generators::low_poly::low_poly(size, subdivisions, hatch, rng);
+            if let Some(hatching) = hatching {
+                return Ok(vec![
+                    Layer::new("drawing", edges),
+                    Layer::new("hatch", hatching),
+                ]);
+            }
+            edges
+        }
+        Commands::Maze { cols, rows, solve } => {
+            let maze = generators::maze::Maze::generate(cols, rows, rng);
+            if solve {
+                return Ok(vec![
+                    Layer::new("drawing", maze.walls(size)),
+                    Layer::new("solution", vec![maze.solution(size)]),
+                ]);
+            }
+            maze.walls(size)
+        }
+        Commands::MotifAlong {
+            iterations,
+            motif,
+            motif_size,
+            spacing,
+            offset,
+        } => {
+            let guide = hilbert_curve(
+                vec2(0.0, 0.0),
+                vec2(size.x, 0.0),
+                vec2(0.0, size.y),
+                iterations,
+            );
+            let motif = match motif {
+                MotifShape::Circle if args.circle_wobble > 0.0 => {
+                    geometry::wobbly_circle(Vec2::ZERO, 0.5, 32, args.circle_wobble, rng)
+                }
+                _ => motif.points(),
+            };
+            let motif: Vec<Vec2> = motif.into_iter().map(|p| p * motif_size).collect();
 
-    let output_file = output_dir.join(format!("output_{}.svg", timestamp));
-    svg::save(&output_file, &document)
-        .wrap_err_with(|| format!("Could not save as `{output_file}`"))?;
+            generators::motif::place_along(&guide, &motif, spacing, offset)
+        }
+        Commands::NestedCircles { depth } => {
+            generators::nested_circles::nested_circles(size, depth, args.circle_wobble, rng)
+        }
+        Commands::NestedPolygons {
+            sides,
+            count,
+            rotation,
+        } => generators::nested_polygons::nested_polygons(size, sides, count, rotation),
+        Commands::PythagorasTree { depth, angle } => {
+            generators::pythagoras_tree::pythagoras_tree(size, depth, angle)
+        }
+        Commands::RoomMaze { depth, corridor } => {
+            let (walls, path) = generators::room_maze::room_maze(size, depth);
+            if corridor {
+                return Ok(vec![
+                    Layer::new("drawing", walls),
+                    Layer::new("corridor", vec![path]),
+                ]);
+            }
+            walls
+        }
+        Commands::Shape { name, samples } => {
+            vec![generators::shape::shape(size, name, samples)]
+        }
+        Commands::SierpinskiKnopp { iterations } => {
+            vec![generators::sierpinski_knopp::sierpinski_knopp(
+                size, iterations,
+            )]
+        }
+        Commands::SmithLabyrinth { cols, rows } => {
+            let loops = generators::smith_labyrinth::smith_labyrinth(size, cols, rows, rng);
+            info!("The labyrinth has {} loops", loops.len());
+            loops
+        }
+        Commands::Snowflake { arms, iterations } => {
+            generators::snowflake::snowflake(size, arms, iterations)
+        }
+        Commands::SpiralImage { image, turns } => {
+            let image = image::open(&image)
+                .wrap_err_with(|| format!("Could not open image `{image}`"))?
+                .to_luma8();
 
-    Ok(())
+            vec![generators::spiral_image::spiral_image(size, &image, turns)]
+        }
+        Commands::SquareSpiral { turns, spacing } => {
+            vec![generators::square_spiral::square_spiral(
+                size, turns, spacing,
+            )]
+        }
+        Commands::SquircleFrames { count, n, spacing } => {
+            generators::squircle_frames::squircle_frames(size, count, n, spacing)
+        }
+        Commands::StringArt {
+            pins,
+            curves,
+            envelope,
+        } => {
+            if curves == 2 {
+                bail!("String art needs a single curve, or 3 or more around the center, not 2");
+            }
+            generators::string_art::string_art(size, pins, curves, envelope)
+        }
+        Commands::TSquare { iterations } => generators::t_square::t_square(size, iterations),
+        Commands::TileMotif {
+            file,
+            cols,
+            rows,
+            rotation,
+            jitter,
+        } => {
+            let motif = import::import_file(&file)?;
+            generators::tile_motif::tile_motif(size, motif, cols, rows, rotation, jitter, rng)
+        }
+        Commands::Vicsek { iterations, cross } => {
+            generators::vicsek::vicsek(size, iterations, cross)
+        }
+        Commands::WaveCollapse { cols, rows } => {
+            generators::wave_collapse::wave_collapse(size, cols, rows, rng)
+        }
+    };
+
+    let mut layers = vec![Layer::new("drawing", paths)];
+    layers.extend(debug_layer);
+    Ok(layers)
 }
 
-fn wonky_triple_hilbert_curve(
-    mut document: Document,
+/// Applies the processing options to the generated paths.
+/// The random generator is only used for `--sketchy`.
+fn post_process(
+    args: &Args,
     size: Vec2,
-    iterations: usize,
-    offset: f32,
-) -> Document {
+    paths: Vec<Vec<Vec2>>,
+    rng: &mut StdRng,
+) -> Result<Vec<Vec<Vec2>>> {
+    let paths = match &args.spine {
+        Some(file) => {
+            let spine = import::import_file(file)?
+                .into_iter()
+                .next()
+                .ok_or_else(|| eyre!("`{file}` has no line to bend the drawing along"))?;
+            // Short segments, so straight lines follow the bends of the spine.
+            paths::subdivide(paths, size.x / 100.0)
+                .into_iter()
+                .map(|path| {
+                    let strip = path.iter().map(|p| vec2(p.x / size.x, p.y / size.y));
+                    geometry::warp_to_spine(&strip.collect_vec(), &spine, args.spine_width)
+                })
+                .collect()
+        }
+        None => paths,
+    };
+
+    let paths = match args.outline {
+        Some(distance) => paths
+            .iter()
+            .flat_map(|path| paths::offset_sdf(path, distance, args.outline_resolution))
+            .collect(),
+        None => paths,
+    };
+
+    let paths = match args.taper {
+        Some(length) => paths::taper_paths(paths, length),
+        None => paths,
+    };
+
+    let paths = if args.reverse_alternate {
+        paths::reverse_alternate(paths)
+    } else {
+        paths
+    };
+
+    let paths = cut_paths(args, size, paths)?;
+
+    let paths = if args.trim {
+        paths::trim_paths(paths, size)
+    } else {
+        paths
+    };
+
+    let paths = match args.adaptive_resample {
+        Some(deviation) => paths
+            .iter()
+            .map(|path| geometry::adaptive_resample(path, deviation))
+            .collect(),
+        None => paths,
+    };
+
+    let paths = match args.thin_by_importance {
+        Some(fraction) => paths
+            .iter()
+            .map(|path| geometry::simplify_by_curvature(path, fraction))
+            .collect(),
+        None => paths,
+    };
+
+    let paths = match args.keep_longest {
+        Some(count) => paths::keep_longest(paths, count),
+        None => paths,
+    };
+
+    // The wobble can move the lines back out of the mask or the canvas, so they are cut again.
+    let paths = match args.sketchy {
+        Some(sketchy) => cut_paths(
+            args,
+            size,
+            paths::sketchy(paths, sketchy.passes, sketchy.wobble, rng),
+        )?,
+        None => paths,
+    };
+
+    let nearest = args.optimize_order || args.order_by == Some(PathOrder::Nearest);
+    let paths = match args.start_point {
+        _ if args.order_by == Some(PathOrder::Importance) => {
+            paths::order_by_importance(paths, size)
+        }
+        Some(start) => paths::optimize_path_order(paths, start.position(size)),
+        None if nearest => paths::optimize_path_order(paths, Vec2::ZERO),
+        None => paths,
+    };
+
+    let paths = match args.max_points {
+        Some(max_points) => paths::split_long_paths(paths, max_points),
+        None => paths,
+    };
+
+    let paths = match args.subpath_stride {
+        Some(0) => bail!("The subpath stride has to be at least 1"),
+        Some(stride) if args.subpath_offset >= stride => bail!(
+            "The subpath offset has to be less than the stride of {stride}, got {}",
+            args.subpath_offset
+        ),
+        Some(stride) => paths::every_nth(paths, stride, args.subpath_offset),
+        None => paths,
+    };
+
+    Ok(paths)
+}
+
+/// Cuts off the parts of the lines outside of the `--mask`, and outside of the reachable
+/// area with `--unreachable`, or else outside of the canvas with `--clip`.
+fn cut_paths(args: &Args, size: Vec2, paths: Vec<Vec<Vec2>>) -> Result<Vec<Vec<Vec2>>> {
+    let paths = match args.mask.len() {
+        0 => paths,
+        1 | 2 => bail!("The mask needs at least 3 points to form a polygon"),
+        _ => paths::mask_paths(paths, &args.mask),
+    };
+
+    Ok(if args.unreachable.is_some() {
+        let (min, max) = reachable_area(args, size);
+        paths::clip_paths(paths, min, max)
+    } else if args.clip {
+        paths::clip_paths(paths, Vec2::ZERO, size)
+    } else {
+        paths
+    })
+}
+
+/// Multiplies all coordinates and the canvas size by `factor`, separately along both axes.
+fn scale_output(layers: Vec<Layer>, size: Vec2, factor: Vec2) -> (Vec<Layer>, Vec2) {
+    let scale = |p: Vec2| vec2(p.x * factor.x, p.y * factor.y);
+    let layers = layers
+        .into_iter()
+        .map(|layer| Layer {
+            paths: layer
+                .paths
+                .into_iter()
+                .map(|path| path.into_iter().map(scale).collect())
+                .collect(),
+            ..layer
+        })
+        .collect();
+
+    (layers, scale(size))
+}
+
+/// The part of the canvas outside of the `--unreachable` bands.
+fn reachable_area(args: &Args, size: Vec2) -> (Vec2, Vec2) {
+    match args.unreachable {
+        Some(insets) => (
+            vec2(insets.left, insets.top),
+            size - vec2(insets.right, insets.bottom),
+        ),
+        None => (Vec2::ZERO, size),
+    }
+}
+
+/// The `--border` rectangle as a closed line, in its own layer.
+fn border_layer(args: &Args, size: Vec2) -> Option<Layer> {
+    let inset = vec2(1.0, 1.0) * args.border?;
+    let (min, max) = reachable_area(args, size);
+    let (min, max) = (min + inset, max - inset);
+
+    let rectangle = vec![min, vec2(max.x, min.y), max, vec2(min.x, max.y), min];
+    Some(Layer::new("border", vec![rectangle]))
+}
+
+/// The `--legend`, with a row for every layer, in its own layer.
+fn legend_layer(args: &Args, layers: &[Layer], size: Vec2) -> Option<Layer> {
+    if !args.legend {
+        return None;
+    }
+    let height = LEGEND_HEIGHT * size.x.min(size.y);
+    let (min, _) = reachable_area(args, size);
+    let origin = min + vec2(height, height);
+
+    let mut paths = vec![];
+    for (index, layer) in layers.iter().enumerate() {
+        let top = origin + vec2(0.0, index as Float * height * 2.0);
+        let square = vec![
+            top,
+            top + vec2(height, 0.0),
+            top + vec2(height, height),
+            top + vec2(0.0, height),
+            top,
+        ];
+        paths.extend(generators::hatch::hatch_shape(&square, height / 4.0, 45.0));
+        paths.push(square);
+        paths.extend(text::text(
+            &layer.name,
+            top + vec2(height * 1.5, 0.0),
+            height,
+        ));
+    }
+    Some(Layer::new("legend", paths))
+}
+
+/// The `--start-dots` circles at the start of every line of every layer, in their own layer.
+fn start_dots_layer(args: &Args, layers: &[Layer], rng: &mut StdRng) -> Option<Layer> {
+    let radius = args.start_dots?;
+    let dots = layers
+        .iter()
+        .flat_map(|layer| &layer.paths)
+        .filter_map(|path| path.first())
+        .map(|&start| geometry::wobbly_circle(start, radius, 16, args.circle_wobble, rng))
+        .collect();
+    Some(Layer::new("start-dots", dots))
+}
+
+fn wonky_triple_hilbert_curve(size: Vec2, iterations: usize, offset: Float) -> Vec<Vec<Vec2>> {
     let points = hilbert_curve(
         vec2(0.0, 0.0),
         vec2(size.x, 0.0),
@@ -115,21 +1763,16 @@ fn wonky_triple_hilbert_curve(
         iterations,
     );
 
-    document = document.add(points_to_path(&points));
-
-    let offset_points = wonky_offset_line(&points, offset);
-    document = document.add(points_to_path(&offset_points));
+    let positive_offset = wonky_offset_line(&points, offset);
+    let negative_offset = wonky_offset_line(&points, -offset);
 
-    let offset_points = wonky_offset_line(&points, -offset);
-    document = document.add(points_to_path(&offset_points));
-
-    document
+    vec![points, positive_offset, negative_offset]
 }
 
 /// Creates a new line based on the original by calculating the points "inside"
 /// the corners, and following that. Will cross over the original line if
 /// the corners change direction.
-fn wonky_offset_line(points: &[Vec2], amount: f32) -> Vec<Vec2> {
+fn wonky_offset_line(points: &[Vec2], amount: Float) -> Vec<Vec2> {
     let mut offset_points = vec![];
 
     for (&a, &b, &c) in points.iter().tuple_windows() {
@@ -141,16 +1784,30 @@ fn wonky_offset_line(points: &[Vec2], amount: f32) -> Vec<Vec2> {
     offset_points
 }
 
+/// A short line for every corner of the path, from the corner in the direction
+/// that `direction_of_corner` gives. Straight corners have no direction and are skipped.
+fn corner_directions(points: &[Vec2], length: Float) -> Vec<Vec<Vec2>> {
+    points
+        .iter()
+        .tuple_windows()
+        .filter_map(|(&a, &b, &c)| {
+            let direction = direction_of_corner(a, b, c)?;
+            Some(vec![b, b + direction * length])
+        })
+        .collect()
+}
+
 fn parallel_hilbert_curves(
-    mut document: Document,
     size: Vec2,
     iterations: usize,
     lines: usize,
-    offset: f32,
-) -> Document {
+    offset: Float,
+) -> Vec<Vec<Vec2>> {
+    let mut paths = vec![];
+
     if lines == 0 {
         // No lines to draw.
-        return document;
+        return paths;
     }
 
     let points = hilbert_curve(
@@ -160,30 +1817,32 @@ fn parallel_hilbert_curves(
         iterations,
     );
 
-    let start_offset = if lines % 2 == 0 {
+    let start_offset = if lines.is_multiple_of(2) {
         // Even number of lines. This means we shouldn't use the original curve.
         offset / 2.0
     } else {
         // Uneven number of lines: the original curve is included.
-        document = document.add(points_to_path(&points));
+        paths.push(points.clone());
         offset
     };
 
     for index in 0..(lines / 2) {
-        let line_offset: f32 = start_offset + offset * (index as f32);
-
-        let offset_points = offset_line(&points, line_offset);
-        document = document.add(points_to_path(&offset_points));
+        let line_offset: Float = start_offset + offset * (index as Float);
 
-        let offset_points = offset_line(&points, -line_offset);
-        document = document.add(points_to_path(&offset_points));
+        paths.push(offset_line(&points, line_offset));
+        paths.push(offset_line(&points, -line_offset));
     }
 
-    document
+    paths
 }
 
 /// Algorithm taken from https://stackoverflow.com/questions/68104969/offset-a-parallel-line-to-a-given-line-python
-fn offset_line(points: &[Vec2], offset: f32) -> Vec<Vec2> {
+///
+/// The result only depends on the shape of the line: a mirror symmetric line, like a
+/// hilbert curve, gives a mirror symmetric offset line. Every point is calculated from
+/// its corner alone, with operations that give the same result when the two sides of
+/// the corner are swapped.
+fn offset_line(points: &[Vec2], offset: Float) -> Vec<Vec2> {
     let mut offset_points = vec![];
 
     // Offset the first point.
@@ -205,37 +1864,21 @@ fn offset_line(points: &[Vec2], offset: f32) -> Vec<Vec2> {
     offset_points
 }
 
-fn offset_central_point(a: Vec2, b: Vec2, c: Vec2, offset: f32) -> Vec2 {
+fn offset_central_point(a: Vec2, b: Vec2, c: Vec2, offset: Float) -> Vec2 {
     let ab = (b - a).normalize();
     let bc = (c - b).normalize();
 
     let ab_90 = vec2(ab.y, -ab.x);
     let bc_90 = vec2(bc.y, -bc.x);
 
+    // Both of these are symmetric in `ab_90` and `bc_90`, so walking the corner
+    // the other way around gives exactly the same result.
     let bisector = (ab_90 + bc_90).normalize();
-    let length = offset / ((1.0 + ab_90.x * bc_90.x + ab_90.y * bc_90.y) / 2.0).sqrt();
+    let length = offset / ((1.0 + ab_90.dot(bc_90)) / 2.0).sqrt();
 
     b + bisector * length
 }
 
-fn points_to_path(points: &[Vec2]) -> Path {
-    let mut data = Data::new();
-
-    for (index, point) in points.iter().enumerate() {
-        if index == 0 {
-            data = data.move_to((point.x, point.y));
-        } else {
-            data = data.line_to((point.x, point.y));
-        }
-    }
-
-    Path::new()
-        .set("fill", "none")
-        .set("stroke", "black")
-        .set("stroke-width", "0.1")
-        .set("d", data)
-}
-
 /// Algorithm taken from https://www.fundza.com/algorithmic/space_filling/hilbert/basics/
 fn hilbert_curve(p: Vec2, x_vec: Vec2, y_vec: Vec2, n: usize) -> Vec<Vec2> {
     let half_x = x_vec / 2.0;
@@ -286,11 +1929,536 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn saved_profile_reproduces_the_options() {
+        let args = Args::parse_from([
+            "plotter_generator",
+            "-w",
+            "210",
+            "--format",
+            "gcode",
+            "--mask=-1,0;5,0;5,5",
+            "--unreachable=1,2,3,4",
+            "--clip",
+            "--stroke-width",
+            "0.3",
+            "--pen-down-z=-1.5",
+            "--save-profile",
+            "a4",
+            "hilbert",
+        ]);
+
+        let file = Utf8PathBuf::from_path_buf(std::env::temp_dir())
+            .unwrap()
+            .join(format!("plotter_generator_profiles_{}", std::process::id()));
+        let mut profiles = Profiles::default();
+        profiles.set("a4", args.arguments());
+        profiles.save(&file).unwrap();
+        let profiles = Profiles::load(&file).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        let command_line = ["plotter_generator", "--profile", "a4", "hilbert"].map(String::from);
+        let loaded = Args::parse_from(with_profile(
+            command_line.to_vec(),
+            profiles.get("a4").unwrap(),
+        ));
+        assert_eq!(loaded.arguments(), args.arguments());
+
+        // The command line overrides the profile.
+        let command_line = [
+            "plotter_generator",
+            "-w",
+            "100",
+            "--profile",
+            "a4",
+            "hilbert",
+        ]
+        .map(String::from);
+        let loaded = Args::parse_from(with_profile(
+            command_line.to_vec(),
+            profiles.get("a4").unwrap(),
+        ));
+        assert_eq!(loaded.width, Some(100.0));
+        assert_eq!(loaded.svg.stroke_width, 0.3);
+
+        // Also options that can be given more than once, which would otherwise add up.
+        let command_line = [
+            "plotter_generator",
+            "--format",
+            "svg",
+            "--mask=0,0;9,0;9,9",
+            "--profile",
+            "a4",
+            "hilbert",
+        ]
+        .map(String::from);
+        let loaded = Args::parse_from(with_profile(
+            command_line.to_vec(),
+            profiles.get("a4").unwrap(),
+        ));
+        assert_eq!(loaded.format, [Format::Svg]);
+        assert_eq!(
+            loaded.mask,
+            [vec2(0.0, 0.0), vec2(9.0, 0.0), vec2(9.0, 9.0)]
+        );
+        assert_eq!(loaded.width, Some(210.0));
+    }
+
+    #[test]
+    fn reproducing_gives_identical_paths() {
+        let command_line = [
+            "plotter_generator",
+            "--mask=10,10;90,10;50,90",
+            "blobs",
+            "--scale",
+            "30",
+        ]
+        .map(String::from);
+        let args = Args::parse_from(&command_line);
+        let size = canvas_size(&args).unwrap();
+
+        let render = |args: &Args, command_line: &[String], seed: u64| {
+            let layers = build_layers(args, size, seed).unwrap();
+            let arguments = reproduce::reproducible_arguments(
+                args.arguments(),
+                reproduce::command_arguments(&Args::command(), command_line),
+                seed,
+            );
+            let mut rng = StdRng::seed_from_u64(seed);
+            let document = svg_output::layers_to_document(&layers, size, &args.svg, &mut rng);
+            reproduce::embed_arguments(document, &arguments).to_string()
+        };
+        let original = render(&args, &command_line, 1234);
+
+        let command_line = ["plotter_generator".to_string()]
+            .into_iter()
+            .chain(reproduce::read_arguments(&original).unwrap())
+            .collect_vec();
+        let reproduced_args = Args::parse_from(&command_line);
+        assert_eq!(reproduced_args.seed, Some(1234));
+        let reproduced = render(&reproduced_args, &command_line, 1234);
+
+        let paths = import::import_svg(&original).unwrap();
+        assert!(!paths.is_empty());
+        assert_eq!(import::import_svg(&reproduced).unwrap(), paths);
+        assert_eq!(reproduced, original);
+    }
+
+    #[test]
+    fn reproducing_keeps_the_options_of_the_command() {
+        let command_line = [
+            "plotter_generator",
+            "-s",
+            "5",
+            "--profile",
+            "a4",
+            "maze",
+            "-s",
+            "-c",
+            "10",
+        ]
+        .map(String::from);
+        let args = Args::parse_from(&command_line);
+
+        let arguments = reproduce::reproducible_arguments(
+            args.arguments(),
+            reproduce::command_arguments(&Args::command(), &command_line),
+            5,
+        );
+
+        assert_eq!(arguments[arguments.len() - 4..], ["maze", "-s", "-c", "10"]);
+        let reproduced =
+            Args::parse_from(["plotter_generator".to_string()].iter().chain(&arguments));
+        assert_eq!(reproduced.seed, Some(5));
+        assert_eq!(reproduced.profile, None);
+        assert!(matches!(
+            reproduced.command,
+            Commands::Maze {
+                cols: 10,
+                rows: 20,
+                solve: true
+            }
+        ));
+    }
+
+    #[test]
+    fn reproducing_finds_the_command_after_an_option_without_value() {
+        let command_line = ["plotter_generator", "--border", "hilbert"].map(String::from);
+        let args = Args::parse_from(&command_line);
+
+        let command = reproduce::command_arguments(&Args::command(), &command_line);
+        assert_eq!(command, ["hilbert"]);
+
+        let arguments = reproduce::reproducible_arguments(args.arguments(), command, 5);
+        let reproduced =
+            Args::try_parse_from(["plotter_generator".to_string()].iter().chain(&arguments))
+                .unwrap();
+        assert_eq!(reproduced.border, Some(0.0));
+        assert!(matches!(reproduced.command, Commands::Hilbert { .. }));
+    }
+
+    #[test]
+    fn merge_uses_the_size_of_the_first_file() {
+        let file = Utf8PathBuf::from_path_buf(std::env::temp_dir())
+            .unwrap()
+            .join(format!(
+                "plotter_generator_merge_{}.svg",
+                std::process::id()
+            ));
+        std::fs::write(
+            &file,
+            r#"<svg viewBox="0 0 200 150"><line x1="0" y1="0" x2="200" y2="150"/></svg>"#,
+        )
+        .unwrap();
+
+        let args = Args::parse_from(["plotter_generator", "merge", file.as_str()]);
+        let size = canvas_size(&args).unwrap();
+        let layers = build_layers(&args, size, 0).unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        let svg = svg_output::layers_to_document(&layers, size, &args.svg, &mut rng).to_string();
+
+        let args = Args::parse_from(["plotter_generator", "-w", "50", "merge", file.as_str()]);
+        let overridden = canvas_size(&args).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        assert_eq!(size, vec2(200.0, 150.0));
+        assert!(svg.contains(r#"viewBox="0 0 200 150""#));
+        assert_eq!(overridden, vec2(50.0, 150.0));
+        assert_eq!(
+            canvas_size(&Args::parse_from(["plotter_generator", "hilbert"])).unwrap(),
+            vec2(100.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn saves_every_requested_format() {
+        let args = Args::parse_from([
+            "plotter_generator",
+            "--format",
+            "svg,gcode",
+            "hilbert",
+            "-i",
+            "2",
+        ]);
+        let size = canvas_size(&args).unwrap();
+        let layers = build_layers(&args, size, 0).unwrap();
+
+        let directory = Utf8PathBuf::from_path_buf(std::env::temp_dir())
+            .unwrap()
+            .join(format!("plotter_generator_formats_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let base = directory.join("output");
+        let command_line = ["plotter_generator".to_string()];
+        save_output(&args, &layers, size, 0, &command_line, None, &base).unwrap();
+
+        let mut files = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect_vec();
+        files.sort();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(files, ["output.svg", "output_0_drawing.gcode"]);
+    }
+
+    #[test]
+    fn poster_saves_a_file_per_tile() {
+        let args = Args::parse_from([
+            "plotter_generator",
+            "-w",
+            "200",
+            "--poster",
+            "2x1",
+            "hilbert",
+            "-i",
+            "3",
+        ]);
+        let size = canvas_size(&args).unwrap();
+        let layers = build_layers(&args, size, 0).unwrap();
+
+        let directory = Utf8PathBuf::from_path_buf(std::env::temp_dir())
+            .unwrap()
+            .join(format!("plotter_generator_poster_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let command_line = ["plotter_generator".to_string()];
+        let grid = args.poster.unwrap();
+        save_poster(
+            &args,
+            &layers,
+            size,
+            0,
+            &command_line,
+            grid,
+            &directory.join("output"),
+        )
+        .unwrap();
+
+        let mut files = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect_vec();
+        files.sort();
+        let contents = files
+            .iter()
+            .map(|file| std::fs::read_to_string(file).unwrap())
+            .collect_vec();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert!(files[0].ends_with("output_tile_0_0.svg"));
+        assert!(files[1].ends_with("output_tile_1_0.svg"));
+        for content in contents {
+            assert!(content.contains(r#"viewBox="0 0 100 100""#));
+            assert!(content.contains(r#"id="registration""#));
+        }
+    }
+
+    #[test]
+    fn scale_doubles_coordinates_and_view_box() {
+        let args = Args::parse_from(["plotter_generator", "--scale", "2", "hilbert"]);
+        let layers = vec![Layer::new(
+            "drawing",
+            vec![vec![vec2(1.0, 2.0), vec2(30.0, 40.0)]],
+        )];
+
+        let (layers, size) = scale_output(layers, vec2(100.0, 50.0), args.scale_factor());
+
+        assert_eq!(size, vec2(200.0, 100.0));
+        assert_eq!(
+            layers[0].paths,
+            vec![vec![vec2(2.0, 4.0), vec2(60.0, 80.0)]]
+        );
+        let mut rng = StdRng::seed_from_u64(0);
+        let svg = svg_output::layers_to_document(&layers, size, &args.svg, &mut rng).to_string();
+        assert!(svg.contains(r#"viewBox="0 0 200 100""#));
+    }
+
+    #[test]
+    fn scale_x_only_stretches_horizontally() {
+        let args = Args::parse_from(["plotter_generator", "--scale-x", "2", "hilbert"]);
+        let layers = vec![Layer::new(
+            "drawing",
+            vec![vec![vec2(1.0, 2.0), vec2(30.0, 40.0)]],
+        )];
+
+        let (layers, size) = scale_output(layers, vec2(100.0, 50.0), args.scale_factor());
+
+        assert_eq!(size, vec2(200.0, 50.0));
+        assert_eq!(
+            layers[0].paths,
+            vec![vec![vec2(2.0, 2.0), vec2(60.0, 40.0)]]
+        );
+    }
+
+    #[test]
+    fn border_is_the_inset_canvas() {
+        let args = Args::parse_from([
+            "plotter_generator",
+            "--unreachable=1,2,3,4",
+            "--border",
+            "hilbert",
+        ]);
+
+        let border = border_layer(&args, vec2(100.0, 50.0)).unwrap();
+
+        assert_eq!(border.name, "border");
+        assert_eq!(
+            border.paths,
+            vec![vec![
+                vec2(4.0, 1.0),
+                vec2(98.0, 1.0),
+                vec2(98.0, 47.0),
+                vec2(4.0, 47.0),
+                vec2(4.0, 1.0),
+            ]]
+        );
+
+        let args = Args::parse_from(["plotter_generator", "--border=5", "hilbert"]);
+        let border = border_layer(&args, vec2(100.0, 50.0)).unwrap();
+        assert_eq!(border.paths[0][0], vec2(5.0, 5.0));
+        assert_eq!(border.paths[0][2], vec2(95.0, 45.0));
+    }
+
+    #[test]
+    fn bleed_runs_off_the_canvas() {
+        let size = vec2(100.0, 100.0);
+        let args = Args::parse_from(["plotter_generator", "--bleed=5", "hilbert"]);
+        let layers = build_layers(&args, size, 0).unwrap();
+
+        let points = layers.iter().flat_map(|layer| layer.paths.iter().flatten());
+        let (min, max) = paths::bounding_box(&layers[0].paths).unwrap();
+        assert!(min.x < 0.0 && min.y < 0.0);
+        assert!(max.x > size.x && max.y > size.y);
+        for point in points {
+            assert!(point.x >= -5.0 && point.y >= -5.0);
+            assert!(point.x <= size.x + 5.0 && point.y <= size.y + 5.0);
+        }
+    }
+
+    #[test]
+    fn legend_has_an_entry_for_every_layer() {
+        let args = Args::parse_from(["plotter_generator", "--legend", "--border", "hilbert"]);
+        let layers = build_layers(&args, vec2(100.0, 100.0), 0).unwrap();
+
+        assert_eq!(layers.len(), 3);
+        let legend = layers.iter().find(|layer| layer.name == "legend").unwrap();
+        let swatches = legend
+            .paths
+            .iter()
+            .filter(|path| path.len() == 5 && path[0] == vec2(2.0, path[0].y))
+            .count();
+        assert_eq!(swatches, 2);
+    }
+
+    #[test]
+    fn mirror_layer_is_the_mirrored_drawing() {
+        let args = Args::parse_from(["plotter_generator", "--mirror=50,0,50,1", "hilbert"]);
+        let layers = build_layers(&args, vec2(100.0, 100.0), 0).unwrap();
+
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[1].name, "mirror");
+        for (path, mirrored) in layers[0].paths.iter().zip(&layers[1].paths) {
+            for (p, q) in path.iter().zip(mirrored) {
+                assert!((*q - vec2(100.0 - p.x, p.y)).len() < 1e-4);
+            }
+        }
+
+        // The mirrored drawing is cut off at the canvas and the mask as well.
+        let args = Args::parse_from([
+            "plotter_generator",
+            "--mirror=0,0,100,50",
+            "--clip",
+            "--mask=0,0;100,0;100,100",
+            "hilbert",
+        ]);
+        let layers = build_layers(&args, vec2(100.0, 100.0), 0).unwrap();
+        assert!(!layers[1].paths.is_empty());
+        for p in layers[1].paths.iter().flatten() {
+            assert!((0.0..=100.0).contains(&p.x) && (0.0..=100.0).contains(&p.y));
+            assert!(p.y <= p.x + 1e-3, "{p:?} is outside of the mask");
+        }
+    }
+
+    #[test]
+    fn sketchy_lines_stay_clipped() {
+        let args = Args::parse_from([
+            "plotter_generator",
+            "--clip",
+            "--sketchy=3,2",
+            "hatch",
+            "-s",
+            "10",
+        ]);
+        let layers = build_layers(&args, vec2(100.0, 100.0), 0).unwrap();
+
+        assert!(!layers[0].paths.is_empty());
+        let canvas = -1e-4..=100.0 + 1e-4;
+        for p in layers[0].paths.iter().flatten() {
+            assert!(canvas.contains(&p.x) && canvas.contains(&p.y), "{p:?}");
+        }
+    }
+
+    #[test]
+    fn subpath_stride_of_zero_is_rejected() {
+        let args = Args::parse_from(["plotter_generator", "--subpath-stride=0", "hilbert"]);
+
+        let error = build_layers(&args, vec2(100.0, 100.0), 0).unwrap_err();
+
+        assert!(error.to_string().contains("stride has to be at least 1"));
+    }
+
+    #[test]
+    fn a_start_dot_for_every_line() {
+        let args = Args::parse_from(["plotter_generator", "--start-dots=0.5", "hilbert"]);
+        let layers = build_layers(&args, vec2(100.0, 100.0), 0).unwrap();
+
+        let lines: Vec<&Vec<Vec2>> = layers
+            .iter()
+            .filter(|layer| layer.name != "start-dots")
+            .flat_map(|layer| &layer.paths)
+            .collect();
+        let dots = &layers
+            .iter()
+            .find(|layer| layer.name == "start-dots")
+            .unwrap()
+            .paths;
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(dots.len(), lines.len());
+        for (dot, line) in dots.iter().zip(lines) {
+            for point in dot {
+                assert!(((*point - line[0]).len() - 0.5).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn fail_on_empty_errors_when_everything_is_clipped() {
+        let size = vec2(100.0, 100.0);
+        let mask = "--mask=200,200;300,200;300,300";
+
+        let args = Args::parse_from(["plotter_generator", mask, "hilbert"]);
+        let layers = build_layers(&args, size, 0).unwrap();
+        assert!(layers.iter().all(|layer| layer.paths.is_empty()));
+
+        let args = Args::parse_from(["plotter_generator", "--fail-on-empty", mask, "hilbert"]);
+        assert!(build_layers(&args, size, 0).is_err());
+
+        let args = Args::parse_from(["plotter_generator", "--fail-on-empty", "hilbert"]);
+        assert!(build_layers(&args, size, 0).is_ok());
+    }
+
+    #[test]
+    fn debug_layer_has_a_segment_per_corner() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let command = Commands::WonkyHilbert {
+            iterations: 2,
+            offset: 1.0,
+        };
+
+        let args = Args::parse_from(["plotter_generator", "--debug-geometry", "hilbert"]);
+
+        let layers = generate(command, vec2(10.0, 10.0), &args, &mut rng).unwrap();
+
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[1].name, "debug");
+        // The 16 points of the curve have 14 interior corners, of which 2 are straight.
+        assert_eq!(layers[1].paths.len(), 12);
+        for segment in &layers[1].paths {
+            assert_eq!(segment.len(), 2);
+            assert!(((segment[1] - segment[0]).len() - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn offset_line_of_symmetric_line_is_symmetric() {
+        // A zigzag that is mirror symmetric around x = 5.
+        let points = [
+            vec2(0.0, 0.0),
+            vec2(1.0, 3.0),
+            vec2(2.3, 0.7),
+            vec2(3.9, 2.1),
+            vec2(5.0, 0.1),
+            vec2(6.1, 2.1),
+            vec2(7.7, 0.7),
+            vec2(9.0, 3.0),
+            vec2(10.0, 0.0),
+        ];
+        let mirror = |p: Vec2| vec2(10.0 - p.x, p.y);
+
+        let offset = offset_line(&points, 0.3);
+
+        assert_eq!(offset.len(), points.len());
+        for (point, opposite) in offset.iter().zip(offset.iter().rev()) {
+            assert!((*point - mirror(*opposite)).len() < 1e-5);
+        }
+    }
+
     #[test]
     fn direction_of_corner_90_degrees() {
         let direction = direction_of_corner(vec2(0.0, 0.0), vec2(10.0, 0.0), vec2(10.0, 5.0));
 
-        assert_eq!(direction, Some(vec2(-0.70710677, 0.70710677)));
+        assert_eq!(direction, Some(vec2(-1.0, 1.0).normalize()));
     }
 
     #[test]
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use crate::vec::{vec2, Float, Vec2};
+
+/// Values of a scalar field, sampled on a regular grid.
+#[derive(Debug, Clone)]
+pub struct Grid {
+    /// Amount of samples along the x-axis.
+    pub columns: usize,
+    /// Amount of samples along the y-axis.
+    pub rows: usize,
+    /// Position of the first sample.
+    pub origin: Vec2,
+    /// Distance between two neighbouring samples.
+    pub spacing: Float,
+    /// Row-major samples.
+    pub values: Vec<Float>,
+}
+
+impl Grid {
+    /// Samples `field` over the area from `origin` to `origin + size`, with square cells.
+    /// `resolution` is the amount of cells along the longest side.
+    pub fn sample(
+        origin: Vec2,
+        size: Vec2,
+        resolution: usize,
+        field: impl Fn(Vec2) -> Float,
+    ) -> Grid {
+        let spacing = size.x.max(size.y) / resolution.max(1) as Float;
+        let columns = (size.x / spacing).ceil() as usize + 1;
+        let rows = (size.y / spacing).ceil() as usize + 1;
+
+        let mut grid = Grid {
+            columns,
+            rows,
+            origin,
+            spacing,
+            values: Vec::with_capacity(columns * rows),
+        };
+
+        for row in 0..rows {
+            for column in 0..columns {
+                let value = field(grid.position(column, row));
+                grid.values.push(value);
+            }
+        }
+
+        grid
+    }
+
+    pub fn get(&self, column: usize, row: usize) -> Float {
+        self.values[row * self.columns + column]
+    }
+
+    pub fn position(&self, column: usize, row: usize) -> Vec2 {
+        self.origin + vec2(column as Float, row as Float) * self.spacing
+    }
+}
+
+/// An edge between two neighbouring samples.
+/// `Horizontal(c, r)` goes from sample `(c, r)` to `(c + 1, r)`,
+/// `Vertical(c, r)` from `(c, r)` to `(c, r + 1)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Edge {
+    Horizontal(usize, usize),
+    Vertical(usize, usize),
+}
+
+/// Extracts the lines where the field crosses `level`, using marching squares.
+/// Lines that form a loop end with their first point. Lines that run into the edge
+/// of the grid are left open.
+pub fn contours(grid: &Grid, level: Float) -> Vec<Vec<Vec2>> {
+    let mut segments = vec![];
+
+    for row in 0..grid.rows.saturating_sub(1) {
+        for column in 0..grid.columns.saturating_sub(1) {
+            cell_segments(grid, level, column, row, &mut segments);
+        }
+    }
+
+    chain_segments(grid, level, &segments)
+}
+
+fn cell_segments(
+    grid: &Grid,
+    level: Float,
+    column: usize,
+    row: usize,
+    segments: &mut Vec<(Edge, Edge)>,
+) {
+    let corners = [
+        grid.get(column, row),
+        grid.get(column + 1, row),
+        grid.get(column + 1, row + 1),
+        grid.get(column, row + 1),
+    ];
+    let inside = corners.map(|value| value > level);
+
+    let top = Edge::Horizontal(column, row);
+    let right = Edge::Vertical(column + 1, row);
+    let bottom = Edge::Horizontal(column, row + 1);
+    let left = Edge::Vertical(column, row);
+
+    // The edges on both sides of every corner, clockwise starting at the top left.
+    let corner_edges = [(left, top), (top, right), (right, bottom), (bottom, left)];
+    let edges = [top, right, bottom, left];
+
+    let crossing: Vec<Edge> = (0..4)
+        .filter(|&i| inside[i] != inside[(i + 1) % 4])
+        .map(|i| edges[i])
+        .collect();
+
+    match crossing.len() {
+        2 => segments.push((crossing[0], crossing[1])),
+        4 => {
+            // Saddle point: the value in the center decides which corners are connected.
+            // The corners that differ from the center are cut off.
+            let center = corners.iter().sum::<Float>() / 4.0 > level;
+            for (i, &edges) in corner_edges.iter().enumerate() {
+                if inside[i] != center {
+                    segments.push(edges);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn chain_segments(grid: &Grid, level: Float, segments: &[(Edge, Edge)]) -> Vec<Vec<Vec2>> {
+    let mut neighbours: HashMap<Edge, Vec<usize>> = HashMap::new();
+    for (index, &(a, b)) in segments.iter().enumerate() {
+        neighbours.entry(a).or_default().push(index);
+        neighbours.entry(b).or_default().push(index);
+    }
+
+    let mut used = vec![false; segments.len()];
+    let mut lines = vec![];
+
+    let walk = |start: Edge, used: &mut Vec<bool>| {
+        let mut line = vec![crossing_point(grid, level, start)];
+        let mut current = start;
+
+        while let Some(&next_segment) = neighbours[&current].iter().find(|&&s| !used[s]) {
+            used[next_segment] = true;
+            let (a, b) = segments[next_segment];
+            current = if a == current { b } else { a };
+            line.push(crossing_point(grid, level, current));
+        }
+
+        line
+    };
+
+    // Open lines first, starting from an end that has only one segment,
+    // otherwise they would be split in two.
+    for index in 0..segments.len() {
+        if used[index] {
+            continue;
+        }
+        let (a, b) = segments[index];
+        if neighbours[&a].len() == 1 {
+            lines.push(walk(a, &mut used));
+        } else if neighbours[&b].len() == 1 {
+            lines.push(walk(b, &mut used));
+        }
+    }
+
+    // Everything that is left is a loop.
+    for index in 0..segments.len() {
+        if !used[index] {
+            lines.push(walk(segments[index].0, &mut used));
+        }
+    }
+
+    lines
+}
+
+/// Where along the edge the field crosses the level, linearly interpolated.
+fn crossing_point(grid: &Grid, level: Float, edge: Edge) -> Vec2 {
+    let ((c_a, r_a), (c_b, r_b)) = match edge {
+        Edge::Horizontal(c, r) => ((c, r), (c + 1, r)),
+        Edge::Vertical(c, r) => ((c, r), (c, r + 1)),
+    };
+
+    let value_a = grid.get(c_a, r_a);
+    let value_b = grid.get(c_b, r_b);
+    let t = ((level - value_a) / (value_b - value_a)).clamp(0.0, 1.0);
+
+    let a = grid.position(c_a, r_a);
+    let b = grid.position(c_b, r_b);
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn circle_is_a_closed_loop() {
+        let grid = Grid::sample(vec2(-2.0, -2.0), vec2(4.0, 4.0), 40, |p| p.len());
+
+        let lines = contours(&grid, 1.0);
+
+        assert_eq!(lines.len(), 1);
+        let line = &lines[0];
+        assert_eq!(line.first(), line.last());
+        for point in line {
+            assert!((point.len() - 1.0).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn line_touching_the_edge_stays_open() {
+        let grid = Grid::sample(vec2(0.0, 0.0), vec2(4.0, 4.0), 8, |p| p.x);
+
+        let lines = contours(&grid, 2.1);
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].len(), 9);
+        assert_ne!(lines[0].first(), lines[0].last());
+    }
+}
@@ -0,0 +1,81 @@
+use rand::{seq::SliceRandom, Rng};
+
+use crate::vec::{consts::FRAC_1_SQRT_2, vec2, Float, Vec2};
+
+/// Two dimensional gradient noise, as described by Ken Perlin in "Improving Noise".
+pub struct Perlin {
+    /// Shuffled 0..256, repeated twice to avoid wrapping indices.
+    permutation: Vec<usize>,
+}
+
+/// Gradients at the grid corners.
+const GRADIENTS: [Vec2; 8] = [
+    vec2(1.0, 0.0),
+    vec2(-1.0, 0.0),
+    vec2(0.0, 1.0),
+    vec2(0.0, -1.0),
+    vec2(FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    vec2(-FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    vec2(FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+    vec2(-FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+];
+
+impl Perlin {
+    pub fn new(rng: &mut impl Rng) -> Self {
+        let mut permutation: Vec<usize> = (0..256).collect();
+        permutation.shuffle(rng);
+        permutation.extend_from_within(..);
+
+        Self { permutation }
+    }
+
+    /// Noise value at the point, roughly between -1 and 1.
+    /// The noise varies on a scale of 1 unit.
+    pub fn get(&self, p: Vec2) -> Float {
+        let cell_x = p.x.floor();
+        let cell_y = p.y.floor();
+        let local = vec2(p.x - cell_x, p.y - cell_y);
+        let x = (cell_x as i64).rem_euclid(256) as usize;
+        let y = (cell_y as i64).rem_euclid(256) as usize;
+
+        let gradient = |dx: usize, dy: usize| {
+            let hash = self.permutation[self.permutation[x + dx] + y + dy];
+            GRADIENTS[hash % GRADIENTS.len()].dot(local - vec2(dx as Float, dy as Float))
+        };
+
+        let fade = |t: Float| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+        let lerp = |a: Float, b: Float, t: Float| a + (b - a) * t;
+
+        let u = fade(local.x);
+        let v = fade(local.y);
+
+        let top = lerp(gradient(0, 0), gradient(1, 0), u);
+        let bottom = lerp(gradient(0, 1), gradient(1, 1), u);
+        // The maximum of 2D perlin noise is sqrt(0.5), scale it up to about -1..1.
+        lerp(top, bottom, v) * Float::sqrt(2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn zero_at_grid_corners() {
+        let noise = Perlin::new(&mut StdRng::seed_from_u64(1));
+
+        assert_eq!(noise.get(vec2(3.0, -7.0)), 0.0);
+    }
+
+    #[test]
+    fn same_seed_same_noise() {
+        let a = Perlin::new(&mut StdRng::seed_from_u64(1));
+        let b = Perlin::new(&mut StdRng::seed_from_u64(1));
+
+        let point = vec2(1.3, 2.7);
+        assert_eq!(a.get(point), b.get(point));
+        assert!(a.get(point).abs() <= 1.0);
+    }
+}
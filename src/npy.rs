@@ -0,0 +1,96 @@
+use crate::vec::Vec2;
+
+/// Converts the paths to a NumPy `.npy` file, that `numpy.load` reads directly.
+///
+/// The file holds a single array of little endian `f32`s with shape `(n, 2)`: one `x, y`
+/// row per point, in canvas coordinates, with the paths one after the other. Between two
+/// paths is a row of `NaN, NaN`, which matplotlib also treats as a break in the line.
+// Casting to `f32` is only necessary when `Float` is `f64`.
+#[allow(clippy::unnecessary_cast)]
+pub fn paths_to_npy(paths: &[Vec<Vec2>]) -> Vec<u8> {
+    let paths: Vec<&Vec<Vec2>> = paths.iter().filter(|path| !path.is_empty()).collect();
+    let rows = paths.iter().map(|path| path.len()).sum::<usize>() + paths.len().saturating_sub(1);
+
+    let mut header = format!("{{'descr': '<f4', 'fortran_order': False, 'shape': ({rows}, 2), }}");
+    // The magic string, version and header length take 10 bytes. The header is padded
+    // with spaces and ends with a newline, so the data starts at a multiple of 64 bytes.
+    let padding = 64 - (10 + header.len() + 1) % 64;
+    header.push_str(&" ".repeat(padding % 64));
+    header.push('\n');
+
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend((header.len() as u16).to_le_bytes());
+    bytes.extend(header.as_bytes());
+
+    for (index, path) in paths.iter().enumerate() {
+        if index > 0 {
+            bytes.extend(f32::NAN.to_le_bytes());
+            bytes.extend(f32::NAN.to_le_bytes());
+        }
+        for point in path.iter() {
+            bytes.extend((point.x as f32).to_le_bytes());
+            bytes.extend((point.y as f32).to_le_bytes());
+        }
+    }
+
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::{vec2, Float};
+    use pretty_assertions::assert_eq;
+
+    /// Reads the file back the way NumPy would, from the header.
+    fn npy_to_paths(bytes: &[u8]) -> Vec<Vec<Vec2>> {
+        assert_eq!(&bytes[..8], b"\x93NUMPY\x01\x00");
+        let header_length = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        let header = std::str::from_utf8(&bytes[10..10 + header_length]).unwrap();
+        assert!(header.contains("'descr': '<f4'"));
+        let data = &bytes[10 + header_length..];
+
+        let rows: usize = header
+            .split("'shape': (")
+            .nth(1)
+            .and_then(|rest| rest.split(',').next())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(data.len(), rows * 2 * 4);
+
+        let values: Vec<f32> = data
+            .chunks(4)
+            .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        let mut paths = vec![vec![]];
+        for row in values.chunks(2) {
+            if row[0].is_nan() {
+                paths.push(vec![]);
+            } else {
+                paths
+                    .last_mut()
+                    .unwrap()
+                    .push(vec2(Float::from(row[0]), Float::from(row[1])));
+            }
+        }
+        paths
+    }
+
+    #[test]
+    fn round_trip() {
+        let paths = vec![
+            vec![vec2(0.0, 0.0), vec2(1.5, 2.0), vec2(-3.0, 4.25)],
+            vec![vec2(10.0, 20.0), vec2(30.0, 40.0)],
+        ];
+
+        let bytes = paths_to_npy(&paths);
+
+        assert_eq!(
+            (10 + u16::from_le_bytes([bytes[8], bytes[9]]) as usize) % 64,
+            0
+        );
+        assert_eq!(npy_to_paths(&bytes), paths);
+    }
+}
@@ -0,0 +1,1145 @@
+use std::{fmt, str::FromStr};
+
+use clap::ValueEnum;
+use rand::Rng;
+
+use crate::{
+    geometry::{point_in_polygon, polyline_sdf},
+    marching_squares::{contours, Grid},
+    vec::{consts::TAU, vec2, Float, Vec2},
+};
+
+/// Splits every path that has more than `max_points` points into chunks of at most
+/// `max_points` points. Consecutive chunks share their boundary point, so there is no
+/// visible gap between them.
+///
+/// A chunk needs at least 2 points to draw anything, so a `max_points` below 2 is
+/// treated as 2.
+pub fn split_long_paths(paths: Vec<Vec<Vec2>>, max_points: usize) -> Vec<Vec<Vec2>> {
+    let max_points = max_points.max(2);
+    let mut output = vec![];
+
+    for path in paths {
+        if path.len() <= max_points {
+            output.push(path);
+            continue;
+        }
+
+        let mut start = 0;
+        while start + 1 < path.len() {
+            let end = (start + max_points).min(path.len());
+            output.push(path[start..end].to_vec());
+            // The last point of this chunk is the first point of the next one.
+            start = end - 1;
+        }
+    }
+
+    output
+}
+
+/// Reverses every other path, and connects the end of every path to the start of the
+/// next one. A fill of parallel lines becomes a single zigzag line, so the pen doesn't
+/// have to travel back to the start of every line.
+pub fn reverse_alternate(paths: Vec<Vec<Vec2>>) -> Vec<Vec<Vec2>> {
+    let mut joined = vec![];
+
+    for (index, mut path) in paths
+        .into_iter()
+        .filter(|path| !path.is_empty())
+        .enumerate()
+    {
+        if index % 2 == 1 {
+            path.reverse();
+        }
+        joined.append(&mut path);
+    }
+
+    if joined.is_empty() {
+        vec![]
+    } else {
+        vec![joined]
+    }
+}
+
+/// Splits every segment longer than `max_length` into equal parts that are not.
+pub fn subdivide(paths: Vec<Vec<Vec2>>, max_length: Float) -> Vec<Vec<Vec2>> {
+    if max_length <= 0.0 {
+        return paths;
+    }
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let mut output = path.first().into_iter().copied().collect::<Vec<_>>();
+            for pair in path.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                let parts = ((b - a).len() / max_length).ceil().max(1.0) as usize;
+                output.extend((1..=parts).map(|i| a + (b - a) * (i as Float / parts as Float)));
+            }
+            output
+        })
+        .collect()
+}
+
+/// The paths and `count - 1` copies of them, turned around `center` in equal steps,
+/// for drawings with rotational symmetry.
+pub fn radial_copies(paths: &[Vec<Vec2>], center: Vec2, count: usize) -> Vec<Vec<Vec2>> {
+    (0..count)
+        .flat_map(|k| {
+            let angle = TAU * k as Float / count as Float;
+            paths.iter().map(move |path| {
+                path.iter()
+                    .map(|&p| center + (p - center).rotate(angle))
+                    .collect()
+            })
+        })
+        .collect()
+}
+
+/// Only keeps the paths whose index divided by `stride` leaves `offset`. Drawing with
+/// every offset from 0 up to `stride` in turn gives all the paths, so a long plot can be
+/// split over several sessions.
+pub fn every_nth(paths: Vec<Vec<Vec2>>, stride: usize, offset: usize) -> Vec<Vec<Vec2>> {
+    paths
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| index % stride == offset)
+        .map(|(_, path)| path)
+        .collect()
+}
+
+/// Rough area covered with ink when the paths are drawn with a pen of `stroke_width`:
+/// the total length times the width. Places where lines overlap or cross are counted
+/// more than once, and the round ends of the strokes are left out.
+pub fn ink_estimate(paths: &[Vec<Vec2>], stroke_width: Float) -> Float {
+    let length: Float = paths
+        .iter()
+        .flat_map(|path| path.windows(2))
+        .map(|pair| (pair[1] - pair[0]).len())
+        .sum();
+    length * stroke_width
+}
+
+/// Total length of the paths, where segments that lie on top of each other, within
+/// `tolerance`, are only counted once. Unlike the plain length, this doesn't grow when
+/// parts of the drawing are drawn over again. Lines that only cross are not merged.
+pub fn covered_length(paths: &[Vec<Vec2>], tolerance: Float) -> Float {
+    let cross = |u: Vec2, v: Vec2| u.x * v.y - u.y * v.x;
+    let mut segments: Vec<(Vec2, Vec2)> = paths
+        .iter()
+        .flat_map(|path| path.windows(2))
+        .map(|pair| (pair[0], pair[1]))
+        .filter(|(a, b)| a != b)
+        .collect();
+    // Sweep from left to right, so only segments that overlap horizontally are compared.
+    segments.sort_by(|a, b| a.0.x.min(a.1.x).total_cmp(&b.0.x.min(b.1.x)));
+
+    let mut length = 0.0;
+    let mut active: Vec<(Vec2, Vec2)> = vec![];
+    for (a, b) in segments {
+        let direction = b - a;
+        let segment_length = direction.len();
+        active.retain(|(c, d)| c.x.max(d.x) >= a.x.min(b.x) - tolerance);
+
+        // The parts of this segment, from 0 to 1, that are already covered by an earlier
+        // segment along the same line.
+        let mut covered: Vec<(Float, Float)> = active
+            .iter()
+            .filter(|(c, d)| {
+                let distance = |p: Vec2| cross(p - a, direction).abs() / segment_length;
+                distance(*c) <= tolerance && distance(*d) <= tolerance
+            })
+            .map(|&(c, d)| {
+                let along = |p: Vec2| (p - a).dot(direction) / (segment_length * segment_length);
+                let (start, end) = (along(c), along(d));
+                (start.min(end).max(0.0), start.max(end).min(1.0))
+            })
+            .filter(|(start, end)| start < end)
+            .collect();
+        covered.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut uncovered = 1.0;
+        let mut reached: Float = 0.0;
+        for (start, end) in covered {
+            if end > reached {
+                uncovered -= end - start.max(reached);
+                reached = end;
+            }
+        }
+        length += uncovered * segment_length;
+        active.push((a, b));
+    }
+
+    length
+}
+
+/// Only keeps the `count` longest paths, measured along the path, in their original order.
+/// Paths of equal length are kept in order until there are `count` of them.
+pub fn keep_longest(paths: Vec<Vec<Vec2>>, count: usize) -> Vec<Vec<Vec2>> {
+    let length =
+        |path: &Vec<Vec2>| -> Float { path.windows(2).map(|pair| (pair[1] - pair[0]).len()).sum() };
+
+    let mut by_length: Vec<(usize, Float)> = paths.iter().map(length).enumerate().collect();
+    by_length.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let mut kept = vec![false; paths.len()];
+    for &(index, _) in by_length.iter().take(count) {
+        kept[index] = true;
+    }
+
+    paths
+        .into_iter()
+        .zip(kept)
+        .filter_map(|(path, kept)| kept.then_some(path))
+        .collect()
+}
+
+/// Only keeps the points that are inside the mask polygon.
+/// A path that leaves the mask is split into separate paths, one for every
+/// stretch that is inside. Stretches of a single point can't be drawn and are dropped.
+pub fn mask_paths(paths: Vec<Vec<Vec2>>, mask: &[Vec2]) -> Vec<Vec<Vec2>> {
+    let mut output = vec![];
+
+    for path in paths {
+        let mut current = vec![];
+
+        for point in path {
+            if point_in_polygon(point, mask) {
+                current.push(point);
+            } else if !current.is_empty() {
+                output.push(std::mem::take(&mut current));
+            }
+        }
+
+        output.push(current);
+    }
+
+    output.retain(|path| path.len() >= 2);
+    output
+}
+
+/// Tidies up the paths by removing consecutive duplicate points, and the points
+/// at the start and end of a path that are outside of the canvas.
+/// Paths that have less than 2 points left are removed.
+pub fn trim_paths(paths: Vec<Vec<Vec2>>, size: Vec2) -> Vec<Vec<Vec2>> {
+    let inside = |p: &Vec2| p.x >= 0.0 && p.x <= size.x && p.y >= 0.0 && p.y <= size.y;
+
+    paths
+        .into_iter()
+        .filter_map(|mut path| {
+            path.dedup();
+
+            let start = path.iter().position(inside)?;
+            let end = path.iter().rposition(inside)?;
+
+            let trimmed = path[start..=end].to_vec();
+            (trimmed.len() >= 2).then_some(trimmed)
+        })
+        .collect()
+}
+
+/// Amount of points outside the rectangle from `min` to `max`. Points on the border
+/// count as inside.
+pub fn out_of_bounds_count(paths: &[Vec<Vec2>], min: Vec2, max: Vec2) -> usize {
+    paths
+        .iter()
+        .flatten()
+        .filter(|p| p.x < min.x || p.x > max.x || p.y < min.y || p.y > max.y)
+        .count()
+}
+
+/// Cuts off everything outside the rectangle from `min` to `max`.
+/// Paths that leave the rectangle are split up, with new points on the border.
+pub fn clip_paths(paths: Vec<Vec<Vec2>>, min: Vec2, max: Vec2) -> Vec<Vec<Vec2>> {
+    let mut output = vec![];
+
+    for path in paths {
+        let mut current: Vec<Vec2> = vec![];
+
+        for (&a, &b) in path.iter().zip(path.iter().skip(1)) {
+            match clip_segment(a, b, min, max) {
+                Some((start, end)) => {
+                    if current.last() != Some(&start) {
+                        // Re-entering the rectangle.
+                        output.push(std::mem::take(&mut current));
+                        current.push(start);
+                    }
+                    current.push(end);
+                }
+                None => output.push(std::mem::take(&mut current)),
+            }
+        }
+
+        output.push(current);
+    }
+
+    output.retain(|path| path.len() >= 2);
+    output
+}
+
+/// Liang-Barsky line clipping. Returns the part of the segment inside the rectangle.
+fn clip_segment(a: Vec2, b: Vec2, min: Vec2, max: Vec2) -> Option<(Vec2, Vec2)> {
+    let delta = b - a;
+    let mut t_start: Float = 0.0;
+    let mut t_end: Float = 1.0;
+
+    let edges = [
+        (-delta.x, a.x - min.x),
+        (delta.x, max.x - a.x),
+        (-delta.y, a.y - min.y),
+        (delta.y, max.y - a.y),
+    ];
+
+    for (p, q) in edges {
+        if p == 0.0 {
+            // Parallel to this edge.
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let t = q / p;
+            if p < 0.0 {
+                t_start = t_start.max(t);
+            } else {
+                t_end = t_end.min(t);
+            }
+        }
+    }
+
+    if t_start > t_end {
+        return None;
+    }
+
+    // Keep the original points exactly, so consecutive segments still connect.
+    let start = if t_start == 0.0 {
+        a
+    } else {
+        a + delta * t_start
+    };
+    let end = if t_end == 1.0 { b } else { a + delta * t_end };
+    Some((start, end))
+}
+
+/// Length of the lines in every cell of a grid of `columns` by `rows` cells over the canvas.
+/// The result is indexed as `[row][column]`. Lines outside of the canvas are not counted.
+pub fn density_map(
+    paths: &[Vec<Vec2>],
+    size: Vec2,
+    columns: usize,
+    rows: usize,
+) -> Vec<Vec<Float>> {
+    let mut map = vec![vec![0.0; columns]; rows];
+    if columns == 0 || rows == 0 {
+        return map;
+    }
+    let cell = vec2(size.x / columns as Float, size.y / rows as Float);
+    let cell_of = |p: Vec2| {
+        (
+            ((p.x / cell.x).floor().max(0.0) as usize).min(columns - 1),
+            ((p.y / cell.y).floor().max(0.0) as usize).min(rows - 1),
+        )
+    };
+
+    for path in paths {
+        for (&a, &b) in path.iter().zip(path.iter().skip(1)) {
+            // Only look at the cells that the bounding box of the segment covers.
+            let (first_column, first_row) = cell_of(vec2(a.x.min(b.x), a.y.min(b.y)));
+            let (last_column, last_row) = cell_of(vec2(a.x.max(b.x), a.y.max(b.y)));
+
+            for (row, cells) in map
+                .iter_mut()
+                .enumerate()
+                .take(last_row + 1)
+                .skip(first_row)
+            {
+                for (column, length) in cells
+                    .iter_mut()
+                    .enumerate()
+                    .take(last_column + 1)
+                    .skip(first_column)
+                {
+                    let min = vec2(column as Float * cell.x, row as Float * cell.y);
+                    if let Some((start, end)) = clip_segment(a, b, min, min + cell) {
+                        *length += (end - start).len();
+                    }
+                }
+            }
+        }
+    }
+
+    map
+}
+
+/// Amount of columns and rows of a grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridSize {
+    pub columns: usize,
+    pub rows: usize,
+}
+
+/// Parses `<columns>x<rows>`, for use in command line arguments.
+impl FromStr for GridSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((columns, rows)) = s.split_once('x') else {
+            return Err(format!("Expected `<columns>x<rows>`, got `{s}`"));
+        };
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<usize>()
+                .map_err(|e| format!("Invalid amount `{value}`: {e}"))
+        };
+
+        Ok(GridSize {
+            columns: parse(columns)?,
+            rows: parse(rows)?,
+        })
+    }
+}
+
+/// Distances from each of the sides of the canvas.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Insets {
+    pub top: Float,
+    pub right: Float,
+    pub bottom: Float,
+    pub left: Float,
+}
+
+/// Parses `top,right,bottom,left`, for use in command line arguments.
+impl FromStr for Insets {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split(',')
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<Float>()
+                    .map_err(|e| format!("Invalid distance `{value}`: {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let [top, right, bottom, left] = values[..] else {
+            return Err(format!("Expected `top,right,bottom,left`, got `{s}`"));
+        };
+
+        Ok(Insets {
+            top,
+            right,
+            bottom,
+            left,
+        })
+    }
+}
+
+/// How often every line is drawn over, and how far the copies stray, for a sketchy look.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sketchy {
+    pub passes: usize,
+    pub wobble: Float,
+}
+
+/// Parses `<passes>,<wobble>`, for use in command line arguments.
+impl FromStr for Sketchy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((passes, wobble)) = s.split_once(',') else {
+            return Err(format!("Expected `<passes>,<wobble>`, got `{s}`"));
+        };
+
+        let passes = passes
+            .trim()
+            .parse()
+            .map_err(|e| format!("Invalid amount of passes `{passes}`: {e}"))?;
+        if passes == 0 {
+            return Err("At least 1 pass is needed to draw anything".to_string());
+        }
+
+        Ok(Sketchy {
+            passes,
+            wobble: wobble
+                .trim()
+                .parse()
+                .map_err(|e| format!("Invalid wobble `{wobble}`: {e}"))?,
+        })
+    }
+}
+
+/// Draws every path `passes` times, every time with its points moved a bit, like
+/// going over a pencil sketch. The points stay within `wobble` of where they were,
+/// and move gradually along the path, so the copies don't look jagged.
+pub fn sketchy(
+    paths: Vec<Vec<Vec2>>,
+    passes: usize,
+    wobble: Float,
+    rng: &mut impl Rng,
+) -> Vec<Vec<Vec2>> {
+    let mut output = vec![];
+    for path in paths {
+        for _ in 0..passes {
+            let mut offset = random_offset(wobble, rng);
+            let copy = path
+                .iter()
+                .map(|&p| {
+                    // Mixing in some of the previous offset keeps it inside the circle.
+                    offset = offset * 0.7 + random_offset(wobble, rng) * 0.3;
+                    p + offset
+                })
+                .collect();
+            output.push(copy);
+        }
+    }
+    output
+}
+
+/// Random offset within a circle with a radius of `wobble`.
+fn random_offset(wobble: Float, rng: &mut impl Rng) -> Vec2 {
+    loop {
+        let offset = vec2(rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0));
+        if offset.len() <= 1.0 {
+            return offset * wobble;
+        }
+    }
+}
+
+/// A line through two points, to mirror the drawing in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MirrorLine {
+    pub from: Vec2,
+    pub to: Vec2,
+}
+
+/// Parses `x1,y1,x2,y2`, for use in command line arguments.
+impl FromStr for MirrorLine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split(',')
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<Float>()
+                    .map_err(|e| format!("Invalid coordinate `{value}`: {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let [x1, y1, x2, y2] = values[..] else {
+            return Err(format!("Expected `x1,y1,x2,y2`, got `{s}`"));
+        };
+        if (x1, y1) == (x2, y2) {
+            return Err(format!("The two points of the line are the same in `{s}`"));
+        }
+
+        Ok(MirrorLine {
+            from: vec2(x1, y1),
+            to: vec2(x2, y2),
+        })
+    }
+}
+
+/// Where the pen starts: a point on the canvas, or one of its corners.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StartPoint {
+    Point(Vec2),
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl StartPoint {
+    pub fn position(self, size: Vec2) -> Vec2 {
+        match self {
+            StartPoint::Point(point) => point,
+            StartPoint::TopLeft => Vec2::ZERO,
+            StartPoint::TopRight => vec2(size.x, 0.0),
+            StartPoint::BottomLeft => vec2(0.0, size.y),
+            StartPoint::BottomRight => size,
+        }
+    }
+}
+
+/// Parses `x,y`, `top-left`, `top-right`, `bottom-left` or `bottom-right`,
+/// for use in command line arguments.
+impl FromStr for StartPoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "top-left" => StartPoint::TopLeft,
+            "top-right" => StartPoint::TopRight,
+            "bottom-left" => StartPoint::BottomLeft,
+            "bottom-right" => StartPoint::BottomRight,
+            _ => StartPoint::Point(
+                s.parse()
+                    .map_err(|e| format!("{e}, or a corner like `top-left` or `bottom-right`"))?,
+            ),
+        })
+    }
+}
+
+/// Formats the same way as it is parsed.
+impl fmt::Display for StartPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StartPoint::Point(point) => write!(f, "{},{}", point.x, point.y),
+            StartPoint::TopLeft => write!(f, "top-left"),
+            StartPoint::TopRight => write!(f, "top-right"),
+            StartPoint::BottomLeft => write!(f, "bottom-left"),
+            StartPoint::BottomRight => write!(f, "bottom-right"),
+        }
+    }
+}
+
+/// How to order the paths before plotting.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum PathOrder {
+    /// Always the closest path next, see `optimize_path_order`.
+    Nearest,
+    /// The most important paths first, see `order_by_importance`.
+    Importance,
+}
+
+/// Reorders the paths to shorten the moves with the pen up. Starting at `start`, the next
+/// path is always the one with an end closest to the pen, reversed if its last point is
+/// the closest. This greedy order is not the shortest possible, but usually close enough.
+pub fn optimize_path_order(mut paths: Vec<Vec<Vec2>>, start: Vec2) -> Vec<Vec<Vec2>> {
+    paths.retain(|path| !path.is_empty());
+    let mut ordered = Vec::with_capacity(paths.len());
+    let mut pen = start;
+
+    while !paths.is_empty() {
+        let distance = |p: &Vec2| (*p - pen).len();
+        let (index, reverse, _) = paths
+            .iter()
+            .enumerate()
+            .flat_map(|(index, path)| {
+                [
+                    (index, false, distance(&path[0])),
+                    (index, true, distance(path.last().unwrap())),
+                ]
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .unwrap();
+
+        let mut path = paths.swap_remove(index);
+        if reverse {
+            path.reverse();
+        }
+        pen = *path.last().unwrap();
+        ordered.push(path);
+    }
+
+    ordered
+}
+
+/// Reorders the paths so the ones that matter most for the look of the drawing come first,
+/// so a plot that is stopped early still looks complete. Long paths are the most
+/// important, and paths near the middle of the canvas count up to twice as much as
+/// paths in the corners.
+pub fn order_by_importance(paths: Vec<Vec<Vec2>>, size: Vec2) -> Vec<Vec<Vec2>> {
+    let center = size / 2.0;
+    let importance = |path: &Vec<Vec2>| -> Float {
+        let length: Float = path.windows(2).map(|pair| (pair[1] - pair[0]).len()).sum();
+        let middle = path.iter().fold(Vec2::ZERO, |sum, &p| sum + p) / path.len() as Float;
+        let distance = ((middle - center).len() / center.len()).min(1.0);
+        length * (2.0 - distance)
+    };
+
+    let mut scored: Vec<(Float, Vec<Vec2>)> = paths
+        .into_iter()
+        .filter(|path| !path.is_empty())
+        .map(|path| (importance(&path), path))
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.into_iter().map(|(_, path)| path).collect()
+}
+
+/// How many times the pen goes back and forth at the ends of a tapered path.
+const TAPER_STROKES: usize = 3;
+
+/// Makes the ends of every path look heavier, by going back and forth over the first and
+/// last `length` units a few times, each time a bit shorter.
+pub fn taper_paths(paths: Vec<Vec<Vec2>>, length: Float) -> Vec<Vec<Vec2>> {
+    if length <= 0.0 {
+        return paths;
+    }
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let mut path = taper_start(path, length);
+            path.reverse();
+            let mut path = taper_start(path, length);
+            path.reverse();
+            path
+        })
+        .collect()
+}
+
+fn taper_start(path: Vec<Vec2>, length: Float) -> Vec<Vec2> {
+    if path.len() < 2 {
+        return path;
+    }
+
+    let mut output = vec![path[0]];
+    for stroke in 0..TAPER_STROKES {
+        let distance = length * (TAPER_STROKES - stroke) as Float / TAPER_STROKES as Float;
+        let start = start_of_path(&path, distance);
+        output.extend(start.iter().skip(1));
+        output.extend(start.iter().rev().skip(1));
+    }
+    output.extend(path.iter().skip(1));
+
+    output
+}
+
+/// The part of the path from its start up to `distance` along it.
+fn start_of_path(path: &[Vec2], distance: Float) -> Vec<Vec2> {
+    let mut output = vec![path[0]];
+    let mut left = distance;
+
+    for (&a, &b) in path.iter().zip(path.iter().skip(1)) {
+        let length = (b - a).len();
+        if length >= left {
+            if length > 0.0 {
+                output.push(a + (b - a) * (left / length));
+            }
+            break;
+        }
+        output.push(b);
+        left -= length;
+    }
+
+    output
+}
+
+/// Offsets the path by `amount`, by sampling its signed distance field on a grid with
+/// cells of `resolution` units, and tracing where the distance equals `amount`.
+/// Unlike moving every corner, this also works for sharp corners and self-intersecting
+/// paths, at the cost of a lot more computation. Open paths get an outline all around,
+/// closed paths grow for a positive `amount` and shrink for a negative one.
+pub fn offset_sdf(path: &[Vec2], amount: Float, resolution: Float) -> Vec<Vec<Vec2>> {
+    let Some((min, max)) = bounding_box(&[path.to_vec()]) else {
+        return vec![];
+    };
+    if resolution <= 0.0 {
+        return vec![];
+    }
+
+    // Leave some room, so the offset line never touches the edge of the grid.
+    let margin = vec2(1.0, 1.0) * (amount.max(0.0) + resolution * 2.0);
+    let origin = min - margin;
+    let extent = max + margin - origin;
+    let cells = (extent.x.max(extent.y) / resolution).ceil() as usize;
+
+    let grid = Grid::sample(origin, extent, cells, |p| polyline_sdf(p, path));
+    contours(&grid, amount)
+}
+
+/// Scales the paths uniformly, so that they fit the canvas as large as possible,
+/// and centers them.
+pub fn fit_to_canvas(paths: Vec<Vec<Vec2>>, size: Vec2) -> Vec<Vec<Vec2>> {
+    let Some((min, max)) = bounding_box(&paths) else {
+        return paths;
+    };
+
+    let extent = max - min;
+    let scale_x = if extent.x > 0.0 {
+        size.x / extent.x
+    } else {
+        Float::INFINITY
+    };
+    let scale_y = if extent.y > 0.0 {
+        size.y / extent.y
+    } else {
+        Float::INFINITY
+    };
+    let scale = scale_x.min(scale_y);
+    let scale = if scale.is_finite() { scale } else { 1.0 };
+
+    let offset = (size - extent * scale) / 2.0;
+
+    paths
+        .into_iter()
+        .map(|path| {
+            path.into_iter()
+                .map(|p| (p - min) * scale + offset)
+                .collect()
+        })
+        .collect()
+}
+
+/// Smallest and largest coordinates of all the points, or `None` if there are no points.
+pub fn bounding_box(paths: &[Vec<Vec2>]) -> Option<(Vec2, Vec2)> {
+    let mut points = paths.iter().flatten();
+    let first = *points.next()?;
+
+    Some(points.fold((first, first), |(min, max), p| {
+        (
+            vec2(min.x.min(p.x), min.y.min(p.y)),
+            vec2(max.x.max(p.x), max.y.max(p.y)),
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn start_point_changes_the_first_path() {
+        let size = vec2(100.0, 100.0);
+        let paths = vec![
+            vec![vec2(10.0, 10.0), vec2(20.0, 10.0)],
+            vec![vec2(80.0, 90.0), vec2(90.0, 90.0)],
+            vec![vec2(50.0, 50.0), vec2(60.0, 50.0)],
+        ];
+
+        let from_origin = optimize_path_order(paths.clone(), StartPoint::TopLeft.position(size));
+        assert_eq!(
+            from_origin,
+            vec![paths[0].clone(), paths[2].clone(), paths[1].clone()]
+        );
+
+        let corner: StartPoint = "bottom-right".parse().unwrap();
+        let from_corner = optimize_path_order(paths.clone(), corner.position(size));
+        assert_eq!(from_corner[0], vec![vec2(90.0, 90.0), vec2(80.0, 90.0)]);
+        assert_eq!(from_corner[1], vec![vec2(60.0, 50.0), vec2(50.0, 50.0)]);
+
+        let point: StartPoint = "55,45".parse().unwrap();
+        assert_eq!(point, StartPoint::Point(vec2(55.0, 45.0)));
+        assert_eq!(point.to_string().parse(), Ok(point));
+        assert_eq!(
+            optimize_path_order(paths.clone(), point.position(size))[0],
+            paths[2]
+        );
+    }
+
+    #[test]
+    fn importance_starts_with_the_longest_path() {
+        let size = vec2(100.0, 100.0);
+        let paths = vec![
+            vec![vec2(45.0, 50.0), vec2(55.0, 50.0)],
+            vec![vec2(10.0, 10.0), vec2(10.0, 40.0), vec2(30.0, 40.0)],
+            vec![vec2(90.0, 90.0), vec2(95.0, 90.0)],
+            vec![vec2(40.0, 60.0), vec2(60.0, 60.0)],
+        ];
+
+        let ordered = order_by_importance(paths.clone(), size);
+
+        assert_eq!(ordered.len(), 4);
+        assert_eq!(ordered[0], paths[1]);
+        assert_eq!(ordered[1], paths[3]);
+        assert_eq!(ordered[3], paths[2]);
+    }
+
+    #[test]
+    fn reverse_alternate_connects_hatch_lines() {
+        let hatch = crate::generators::hatch::hatch(vec2(100.0, 100.0), 2.0, 30.0);
+        assert!(hatch.len() > 50);
+
+        let connected = reverse_alternate(hatch.clone());
+
+        assert_eq!(connected.len(), 1);
+        let points = &connected[0];
+        assert_eq!(points.len(), hatch.len() * 2);
+        assert_eq!(points[..2], hatch[0][..]);
+        assert_eq!(points[2..4], [hatch[1][1], hatch[1][0]]);
+        // The connections between the lines are short.
+        for i in (1..points.len() - 1).step_by(2) {
+            assert!((points[i + 1] - points[i]).len() < 5.0);
+        }
+    }
+
+    #[test]
+    fn subdivide_splits_long_segments() {
+        let paths = vec![vec![vec2(0.0, 0.0), vec2(3.0, 0.0), vec2(3.0, 0.5)]];
+
+        assert_eq!(
+            subdivide(paths, 1.0),
+            vec![vec![
+                vec2(0.0, 0.0),
+                vec2(1.0, 0.0),
+                vec2(2.0, 0.0),
+                vec2(3.0, 0.0),
+                vec2(3.0, 0.5)
+            ]]
+        );
+    }
+
+    #[test]
+    fn every_nth_sessions_add_up_to_all_paths() {
+        let paths: Vec<Vec<Vec2>> = (0..10)
+            .map(|i| vec![vec2(i as Float, 0.0), vec2(i as Float, 1.0)])
+            .collect();
+
+        let sessions: Vec<Vec<Vec<Vec2>>> = (0..3)
+            .map(|offset| every_nth(paths.clone(), 3, offset))
+            .collect();
+
+        assert_eq!(
+            sessions.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![4, 3, 3]
+        );
+        let mut combined = sessions.concat();
+        combined.sort_by(|a, b| a[0].x.total_cmp(&b[0].x));
+        assert_eq!(combined, paths);
+    }
+
+    #[test]
+    fn ink_of_a_straight_stroke() {
+        let paths = vec![vec![vec2(1.0, 1.0), vec2(4.0, 5.0), vec2(9.0, 5.0)]];
+
+        assert_eq!(ink_estimate(&paths, 0.5), 10.0 * 0.5);
+        assert_eq!(ink_estimate(&[], 0.5), 0.0);
+    }
+
+    #[test]
+    fn overlapping_strokes_are_covered_once() {
+        let stroke = vec![vec2(0.0, 0.0), vec2(10.0, 0.0)];
+        let back = vec![vec2(10.0, 0.01), vec2(4.0, 0.01), vec2(4.0, 5.0)];
+
+        assert_eq!(covered_length(&[stroke.clone(), stroke.clone()], 0.1), 10.0);
+        assert!((covered_length(&[stroke, back], 0.1) - 14.99).abs() < 1e-4);
+    }
+
+    #[test]
+    fn sketchy_passes_stay_close_to_the_original() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let paths = vec![
+            vec![vec2(0.0, 0.0), vec2(10.0, 0.0), vec2(10.0, 10.0)],
+            vec![vec2(5.0, 5.0), vec2(8.0, 2.0)],
+        ];
+
+        let sketched = sketchy(paths.clone(), 3, 0.5, &mut rng);
+
+        assert_eq!(sketched.len(), 6);
+        for (copy, original) in sketched.iter().zip(paths.iter().flat_map(|p| [p, p, p])) {
+            assert_eq!(copy.len(), original.len());
+            assert_ne!(copy, original);
+            for (&p, &q) in copy.iter().zip(original) {
+                assert!((p - q).len() <= 0.5 + 1e-4);
+            }
+        }
+
+        assert_eq!(
+            "3, 0.5".parse(),
+            Ok(Sketchy {
+                passes: 3,
+                wobble: 0.5
+            })
+        );
+        assert!("0,0.5".parse::<Sketchy>().is_err());
+    }
+
+    #[test]
+    fn keep_longest_keeps_the_order() {
+        let line = |length: Float| vec![vec2(0.0, 0.0), vec2(length, 0.0)];
+        let paths = vec![
+            line(3.0),
+            line(1.0),
+            vec![vec2(0.0, 0.0), vec2(3.0, 0.0), vec2(3.0, 4.0)],
+            line(5.0),
+            line(2.0),
+        ];
+
+        assert_eq!(
+            keep_longest(paths.clone(), 3),
+            vec![paths[0].clone(), paths[2].clone(), paths[3].clone()]
+        );
+        assert_eq!(keep_longest(paths.clone(), 0), Vec::<Vec<Vec2>>::new());
+        assert_eq!(keep_longest(paths.clone(), 10), paths);
+    }
+
+    #[test]
+    fn density_map_central_stroke() {
+        let paths = vec![vec![vec2(40.0, 50.0), vec2(60.0, 50.0)]];
+
+        let map = density_map(&paths, vec2(100.0, 100.0), 3, 3);
+
+        assert_eq!(
+            map,
+            vec![
+                vec![0.0, 0.0, 0.0],
+                vec![0.0, 20.0, 0.0],
+                vec![0.0, 0.0, 0.0]
+            ]
+        );
+        assert_eq!(
+            "4x2".parse::<GridSize>(),
+            Ok(GridSize {
+                columns: 4,
+                rows: 2
+            })
+        );
+    }
+
+    #[test]
+    fn taper_adds_points_near_the_ends() {
+        let path: Vec<Vec2> = (0..=10).map(|i| vec2(i as Float * 10.0, 0.0)).collect();
+
+        let tapered = taper_paths(vec![path.clone()], 3.0);
+
+        assert_eq!(tapered.len(), 1);
+        let tapered = &tapered[0];
+        assert_eq!(tapered.first(), path.first());
+        assert_eq!(tapered.last(), path.last());
+
+        let near_start = tapered.iter().filter(|p| p.x <= 3.0).count();
+        let near_end = tapered.iter().filter(|p| p.x >= 97.0).count();
+        let middle = tapered.iter().filter(|p| p.x > 3.0 && p.x < 97.0).count();
+        // There and back again for every stroke, on both ends.
+        assert_eq!(near_start, 1 + 2 * TAPER_STROKES);
+        assert_eq!(near_end, 1 + 2 * TAPER_STROKES);
+        assert_eq!(middle, 9);
+    }
+
+    #[test]
+    fn offset_sdf_rounds_the_corners_of_a_square() {
+        let square = [
+            vec2(0.0, 0.0),
+            vec2(4.0, 0.0),
+            vec2(4.0, 4.0),
+            vec2(0.0, 4.0),
+            vec2(0.0, 0.0),
+        ];
+
+        let lines = offset_sdf(&square, 1.0, 0.05);
+
+        assert_eq!(lines.len(), 1);
+        let line = &lines[0];
+        assert_eq!(line.first(), line.last());
+
+        let (min, max) = bounding_box(&lines).unwrap();
+        assert!((min - vec2(-1.0, -1.0)).len() < 0.05);
+        assert!((max - vec2(5.0, 5.0)).len() < 0.05);
+
+        for &point in line {
+            assert!((polyline_sdf(point, &square) - 1.0).abs() < 0.01);
+            // The corners are rounded, nothing gets close to the corner of the bounding box.
+            assert!((point - vec2(5.0, 5.0)).len() > 0.4);
+        }
+    }
+
+    #[test]
+    fn split_long_paths_shares_boundary_points() {
+        let path: Vec<Vec2> = (0..1000).map(|i| vec2(i as Float, 0.0)).collect();
+
+        let chunks = split_long_paths(vec![path.clone()], 300);
+
+        assert_eq!(chunks.len(), 4);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 300));
+        for (a, b) in chunks.iter().zip(chunks.iter().skip(1)) {
+            assert_eq!(a.last(), b.first());
+        }
+        assert_eq!(chunks.first().unwrap().first(), path.first());
+        assert_eq!(chunks.last().unwrap().last(), path.last());
+    }
+
+    #[test]
+    fn mask_paths_triangle() {
+        let triangle = [vec2(0.0, 100.0), vec2(50.0, 0.0), vec2(100.0, 100.0)];
+        let curve = crate::hilbert_curve(vec2(0.0, 0.0), vec2(100.0, 0.0), vec2(0.0, 100.0), 4);
+
+        let masked = mask_paths(vec![curve], &triangle);
+
+        assert!(masked.len() > 1);
+        for point in masked.iter().flatten() {
+            assert!(point_in_polygon(*point, &triangle));
+        }
+    }
+
+    #[test]
+    fn trim_paths_removes_duplicates_and_outside_ends() {
+        let path = vec![
+            vec2(1.0, 1.0),
+            vec2(1.0, 1.0),
+            vec2(5.0, 1.0),
+            vec2(5.0, 5.0),
+            vec2(5.0, 15.0),
+            vec2(5.0, 20.0),
+        ];
+
+        let trimmed = trim_paths(vec![path], vec2(10.0, 10.0));
+
+        assert_eq!(
+            trimmed,
+            vec![vec![vec2(1.0, 1.0), vec2(5.0, 1.0), vec2(5.0, 5.0)]]
+        );
+    }
+
+    #[test]
+    fn out_of_bounds_counts_only_outside_points() {
+        let paths = vec![
+            vec![vec2(0.0, 0.0), vec2(5.0, 5.0), vec2(10.0, 10.0)],
+            vec![vec2(5.0, 5.0), vec2(12.0, 5.0)],
+        ];
+
+        let count = out_of_bounds_count(&paths, vec2(0.0, 0.0), vec2(10.0, 10.0));
+
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn clip_paths_splits_at_border() {
+        let path = vec![
+            vec2(5.0, 5.0),
+            vec2(15.0, 5.0),
+            vec2(15.0, 8.0),
+            vec2(5.0, 8.0),
+        ];
+
+        let clipped = clip_paths(vec![path], vec2(0.0, 0.0), vec2(10.0, 10.0));
+
+        assert_eq!(
+            clipped,
+            vec![
+                vec![vec2(5.0, 5.0), vec2(10.0, 5.0)],
+                vec![vec2(10.0, 8.0), vec2(5.0, 8.0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn unreachable_bands_are_respected() {
+        let size = vec2(100.0, 100.0);
+        let insets: Insets = "5,10,15,20".parse().unwrap();
+        let curve = crate::hilbert_curve(vec2(0.0, 0.0), vec2(100.0, 0.0), vec2(0.0, 100.0), 4);
+
+        let clipped = clip_paths(
+            vec![curve],
+            vec2(insets.left, insets.top),
+            size - vec2(insets.right, insets.bottom),
+        );
+
+        assert!(!clipped.is_empty());
+        for point in clipped.iter().flatten() {
+            assert!(point.x >= 20.0 && point.x <= 90.0);
+            assert!(point.y >= 5.0 && point.y <= 85.0);
+        }
+    }
+
+    #[test]
+    fn fit_to_canvas_centers_and_scales() {
+        let path = vec![vec2(-1.0, -1.0), vec2(1.0, 0.0)];
+
+        let fitted = fit_to_canvas(vec![path], vec2(100.0, 100.0));
+
+        assert_eq!(fitted, vec![vec![vec2(0.0, 25.0), vec2(100.0, 75.0)]]);
+    }
+
+    #[test]
+    fn split_long_paths_keeps_short_paths() {
+        let path = vec![vec2(0.0, 0.0), vec2(1.0, 0.0)];
+
+        assert_eq!(split_long_paths(vec![path.clone()], 300), vec![path]);
+    }
+}
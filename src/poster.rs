@@ -0,0 +1,122 @@
+use crate::{
+    layer::Layer,
+    paths::{clip_paths, GridSize},
+    vec::{vec2, Float, Vec2},
+};
+
+/// Name of the layer with the registration crosses.
+pub const REGISTRATION_LAYER: &str = "registration";
+
+/// Length of the arms of the registration crosses, as a fraction of the shortest side
+/// of a tile.
+const CROSS_SIZE: Float = 0.05;
+
+/// A single sheet of a poster.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tile {
+    pub column: usize,
+    pub row: usize,
+    /// The layers cut off at the edges of the tile, moved so the tile starts at the origin.
+    pub layers: Vec<Layer>,
+    /// Where the tile starts on the whole poster.
+    pub offset: Vec2,
+    pub size: Vec2,
+}
+
+/// Splits the drawing into a grid of tiles, to plot a drawing that is bigger than the
+/// plotter on several sheets. Every corner shared by tiles gets a registration cross,
+/// in its own layer, so the sheets can be lined up afterwards.
+pub fn poster_tiles(layers: &[Layer], size: Vec2, grid: GridSize) -> Vec<Tile> {
+    let (columns, rows) = (grid.columns.max(1), grid.rows.max(1));
+    let tile_size = vec2(size.x / columns as Float, size.y / rows as Float);
+    let arm = tile_size.x.min(tile_size.y) * CROSS_SIZE;
+
+    let mut crosses = vec![];
+    for row in 0..=rows {
+        for column in 0..=columns {
+            let shared = (1..columns).contains(&column) || (1..rows).contains(&row);
+            if shared {
+                let corner = vec2(column as Float * tile_size.x, row as Float * tile_size.y);
+                crosses.push(vec![corner - vec2(arm, 0.0), corner + vec2(arm, 0.0)]);
+                crosses.push(vec![corner - vec2(0.0, arm), corner + vec2(0.0, arm)]);
+            }
+        }
+    }
+    let registration = Layer::new(REGISTRATION_LAYER, crosses);
+
+    let mut tiles = vec![];
+    for row in 0..rows {
+        for column in 0..columns {
+            let offset = vec2(column as Float * tile_size.x, row as Float * tile_size.y);
+            let layers = layers
+                .iter()
+                .chain([&registration])
+                .map(|layer| Layer {
+                    paths: clip_paths(layer.paths.clone(), offset, offset + tile_size)
+                        .into_iter()
+                        .map(|path| path.into_iter().map(|p| p - offset).collect())
+                        .collect(),
+                    ..layer.clone()
+                })
+                .collect();
+
+            tiles.push(Tile {
+                column,
+                row,
+                layers,
+                offset,
+                size: tile_size,
+            });
+        }
+    }
+
+    tiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn tiles_reassemble_the_drawing() {
+        let drawing = vec![
+            vec![vec2(10.0, 10.0), vec2(190.0, 90.0)],
+            vec![vec2(50.0, 20.0), vec2(80.0, 20.0), vec2(120.0, 70.0)],
+        ];
+        let layers = vec![Layer::new("drawing", drawing.clone())];
+        let length = |paths: &[Vec<Vec2>]| -> Float {
+            paths
+                .iter()
+                .flat_map(|path| path.windows(2))
+                .map(|pair| (pair[1] - pair[0]).len())
+                .sum()
+        };
+
+        let tiles = poster_tiles(
+            &layers,
+            vec2(200.0, 100.0),
+            GridSize {
+                columns: 2,
+                rows: 1,
+            },
+        );
+
+        assert_eq!(tiles.len(), 2);
+        let mut total = 0.0;
+        for tile in &tiles {
+            assert_eq!(tile.size, vec2(100.0, 100.0));
+            assert_eq!(tile.layers[0].name, "drawing");
+            for point in tile.layers[0].paths.iter().flatten() {
+                assert!((0.0..=100.0).contains(&point.x) && (0.0..=100.0).contains(&point.y));
+            }
+            total += length(&tile.layers[0].paths);
+
+            // Half of the crosses at the top and bottom of the cut.
+            assert_eq!(tile.layers[1].name, REGISTRATION_LAYER);
+            assert_eq!(tile.layers[1].paths.len(), 4);
+        }
+        assert!((total - length(&drawing)).abs() < 1e-3);
+        assert_eq!(tiles[1].offset, vec2(100.0, 0.0));
+    }
+}
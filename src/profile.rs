@@ -0,0 +1,112 @@
+use std::fs;
+
+use camino::Utf8Path;
+use color_eyre::{
+    eyre::{bail, Context},
+    Result,
+};
+
+/// Named sets of command line arguments, stored in a file like:
+///
+/// ```text
+/// [a4]
+/// --width=210
+/// --height=297
+/// ```
+///
+/// Every argument is on its own line, so values may contain spaces.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Profiles {
+    profiles: Vec<(String, Vec<String>)>,
+}
+
+impl Profiles {
+    /// Reads the profiles from the file. A file that doesn't exist has no profiles.
+    pub fn load(file: &Utf8Path) -> Result<Profiles> {
+        if !file.exists() {
+            return Ok(Profiles::default());
+        }
+
+        let contents =
+            fs::read_to_string(file).wrap_err_with(|| format!("Could not read `{file}`"))?;
+        Profiles::parse(&contents).wrap_err_with(|| format!("Invalid profiles file `{file}`"))
+    }
+
+    pub fn save(&self, file: &Utf8Path) -> Result<()> {
+        fs::write(file, self.to_string()).wrap_err_with(|| format!("Could not save `{file}`"))
+    }
+
+    fn parse(contents: &str) -> Result<Profiles> {
+        let mut profiles: Vec<(String, Vec<String>)> = vec![];
+
+        for line in contents.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                profiles.push((name.to_string(), vec![]));
+            } else if let Some((_, arguments)) = profiles.last_mut() {
+                arguments.push(line.to_string());
+            } else {
+                bail!("Argument `{line}` is not part of a profile");
+            }
+        }
+
+        Ok(Profiles { profiles })
+    }
+
+    /// The arguments of the profile with this name.
+    pub fn get(&self, name: &str) -> Result<&[String]> {
+        match self.profiles.iter().find(|(n, _)| n == name) {
+            Some((_, arguments)) => Ok(arguments),
+            None => bail!(
+                "There is no profile named `{name}`. Available profiles: {}",
+                self.profiles
+                    .iter()
+                    .map(|(n, _)| n.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    /// Adds the profile, or replaces it if one with this name already exists.
+    pub fn set(&mut self, name: &str, arguments: Vec<String>) {
+        match self.profiles.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = arguments,
+            None => self.profiles.push((name.to_string(), arguments)),
+        }
+    }
+}
+
+impl std::fmt::Display for Profiles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, (name, arguments)) in self.profiles.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "[{name}]")?;
+            for argument in arguments {
+                writeln!(f, "{argument}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn replace_and_roundtrip() {
+        let mut profiles = Profiles::default();
+        profiles.set("a4", vec!["--width=210".into(), "--height=297".into()]);
+        profiles.set("small", vec!["--width=50".into()]);
+        profiles.set("a4", vec!["--width=297".into(), "--height=210".into()]);
+
+        let parsed = Profiles::parse(&profiles.to_string()).unwrap();
+
+        assert_eq!(parsed, profiles);
+        assert_eq!(parsed.get("a4").unwrap(), ["--width=297", "--height=210"]);
+        assert!(parsed.get("a3").is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use clap::Command;
+use color_eyre::{eyre::eyre, Result};
+use svg::{
+    node::{
+        element::{tag, Description},
+        Text,
+    },
+    parser::Event,
+    Document,
+};
+
+/// Id of the element in the svg output that holds the arguments it was generated with.
+const ARGUMENTS_ID: &str = "plotter_generator_arguments";
+
+/// The arguments that give the same drawing again: the seed, the `options` of the
+/// program, and the command with its own options as they were given.
+/// The options are passed separately, because profiles are already applied to the
+/// command line, and could change later.
+pub fn reproducible_arguments(options: Vec<String>, command: &[String], seed: u64) -> Vec<String> {
+    [format!("--seed={seed}")]
+        .into_iter()
+        .chain(options)
+        .chain(command.iter().cloned())
+        .collect()
+}
+
+/// Everything after the options of the `program` itself: the command, with its own
+/// options. Those can have the same names as the options of the program, so options
+/// are only recognised up to the command.
+pub fn command_arguments<'a>(program: &Command, command_line: &'a [String]) -> &'a [String] {
+    let arguments = command_line.get(1..).unwrap_or_default();
+    let used: usize = program_options(program, arguments)
+        .iter()
+        .map(|(_, option)| option.len())
+        .sum();
+    &arguments[used..]
+}
+
+/// The options of the `program` itself at the start of `arguments`, up to the command.
+/// Every option comes with its id, if the program knows it, and the arguments it takes
+/// up: two when the value is given separately, otherwise one.
+pub fn program_options<'a>(
+    program: &Command,
+    arguments: &'a [String],
+) -> Vec<(Option<String>, &'a [String])> {
+    let mut options = vec![];
+    let mut rest = arguments;
+
+    while let Some(argument) = rest.first() {
+        // Whether the value is part of the argument, like `--seed=5` or `-s5`.
+        let (option, attached) = if let Some(long) = argument.strip_prefix("--") {
+            let (name, attached) = match long.split_once('=') {
+                Some((name, _)) => (name, true),
+                None => (long, false),
+            };
+            let option = program
+                .get_arguments()
+                .find(|option| option.get_long() == Some(name));
+            (option, attached)
+        } else if let Some(short) = argument.strip_prefix('-') {
+            let mut characters = short.chars();
+            let short = characters.next();
+            let option = program
+                .get_arguments()
+                .find(|option| short.is_some() && option.get_short() == short);
+            (option, characters.next().is_some())
+        } else {
+            break;
+        };
+
+        // Options with an optional value, like `--border`, only take it after a `=`.
+        let separate = !attached
+            && option.is_some_and(|option| {
+                option.get_action().takes_values()
+                    && !option.is_require_equals_set()
+                    && option
+                        .get_num_args()
+                        .is_none_or(|range| range.min_values() > 0)
+            });
+        let length = if separate { 2 } else { 1 }.min(rest.len());
+        options.push((
+            option.map(|option| option.get_id().to_string()),
+            &rest[..length],
+        ));
+        rest = &rest[length..];
+    }
+
+    options
+}
+
+/// Stores the arguments in the document, one per line.
+pub fn embed_arguments(document: Document, arguments: &[String]) -> Document {
+    document.add(
+        Description::new()
+            .set("id", ARGUMENTS_ID)
+            .add(Text::new(arguments.join("\n"))),
+    )
+}
+
+/// Reads the arguments that `embed_arguments` stored in the svg.
+pub fn read_arguments(content: &str) -> Result<Vec<String>> {
+    let mut inside = false;
+
+    for event in svg::read(content)? {
+        match event {
+            Event::Tag(tag::Description, tag::Type::Start, attributes) => {
+                inside = attributes.get("id").is_some_and(|id| &**id == ARGUMENTS_ID);
+            }
+            Event::Text(text) if inside => {
+                return Ok(unescape(text).lines().map(str::to_string).collect());
+            }
+            Event::Tag(tag::Description, _, _) => inside = false,
+            _ => {}
+        }
+    }
+
+    Err(eyre!(
+        "The svg doesn't say which arguments it was generated with"
+    ))
+}
+
+/// Undoes the escaping of special characters that `Text` does.
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Arg, ArgAction};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn arguments_round_trip() {
+        let program = Command::new("plotter_generator")
+            .arg(Arg::new("seed").short('s').long("seed"))
+            .arg(Arg::new("profile").long("profile"))
+            .arg(Arg::new("width").short('w'))
+            .arg(Arg::new("clip").long("clip").action(ArgAction::SetTrue))
+            .subcommand(
+                Command::new("field")
+                    .arg(Arg::new("solve").short('s').action(ArgAction::SetTrue))
+                    .arg(Arg::new("expression")),
+            );
+        let command_line = [
+            "plotter_generator",
+            "--profile",
+            "a4",
+            "-s",
+            "12",
+            "--clip",
+            "-w=200",
+            "field",
+            "-s",
+            "sin(x) < y & 2",
+        ]
+        .map(String::from);
+
+        let command = command_arguments(&program, &command_line);
+        assert_eq!(command, ["field", "-s", "sin(x) < y & 2"]);
+
+        let arguments = reproducible_arguments(vec!["-w=200".to_string()], command, 12);
+        let svg = embed_arguments(Document::new(), &arguments).to_string();
+
+        assert_eq!(
+            read_arguments(&svg).unwrap(),
+            ["--seed=12", "-w=200", "field", "-s", "sin(x) < y & 2"]
+        );
+        assert!(read_arguments("<svg></svg>").is_err());
+    }
+}
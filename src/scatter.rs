@@ -0,0 +1,114 @@
+use clap::ValueEnum;
+use rand::Rng;
+
+use crate::vec::{consts::TAU, vec2, Float, Vec2};
+
+/// Amount of candidates tried for every point of the `Poisson` distribution.
+/// More candidates spread the points out more evenly, but take longer.
+const CANDIDATES: usize = 10;
+
+/// How random points are spread over the canvas.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    /// Every place on the canvas is equally likely.
+    Uniform,
+    /// Points cluster around the center of the canvas.
+    Gaussian,
+    /// Points keep their distance from each other, spreading out evenly without
+    /// looking like a grid (blue noise).
+    Poisson,
+}
+
+/// `count` random points on the canvas, spread according to the distribution.
+pub fn scatter(
+    size: Vec2,
+    count: usize,
+    distribution: Distribution,
+    rng: &mut impl Rng,
+) -> Vec<Vec2> {
+    let mut uniform = || vec2(rng.gen_range(0.0..size.x), rng.gen_range(0.0..size.y));
+
+    match distribution {
+        Distribution::Uniform => (0..count).map(|_| uniform()).collect(),
+        Distribution::Gaussian => (0..count)
+            .map(|_| {
+                // Points that land outside of the canvas are tried again.
+                loop {
+                    let point = size / 2.0 + gaussian_pair(rng) * (size.x.min(size.y) / 6.0);
+                    if (0.0..size.x).contains(&point.x) && (0.0..size.y).contains(&point.y) {
+                        break point;
+                    }
+                }
+            })
+            .collect(),
+        Distribution::Poisson => {
+            // Mitchell's best candidate: of a few random candidates, the one furthest
+            // away from the points so far is added.
+            let mut points: Vec<Vec2> = vec![];
+            for _ in 0..count {
+                let distance = |candidate: Vec2| {
+                    points
+                        .iter()
+                        .map(|&p| (p - candidate).len())
+                        .fold(Float::INFINITY, Float::min)
+                };
+                let best = (0..CANDIDATES)
+                    .map(|_| uniform())
+                    .max_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+                    .unwrap();
+                points.push(best);
+            }
+            points
+        }
+    }
+}
+
+/// Two independent samples of the standard normal distribution, using the Box-Muller
+/// transform.
+fn gaussian_pair(rng: &mut impl Rng) -> Vec2 {
+    // Excluding 0, which has no logarithm.
+    let radius = (-2.0 * (1.0 - rng.gen::<Float>()).ln()).sqrt();
+    Vec2::from_angle(rng.gen_range(0.0..TAU)) * radius
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn gaussian_is_closer_to_the_center() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let size = vec2(100.0, 50.0);
+        let mean_distance = |points: &[Vec2]| {
+            points
+                .iter()
+                .map(|&p| (p - size / 2.0).len())
+                .sum::<Float>()
+                / points.len() as Float
+        };
+        let closest = |points: &[Vec2]| {
+            points
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &a)| points[i + 1..].iter().map(move |&b| (b - a).len()))
+                .fold(Float::INFINITY, Float::min)
+        };
+
+        let uniform = scatter(size, 500, Distribution::Uniform, &mut rng);
+        let gaussian = scatter(size, 500, Distribution::Gaussian, &mut rng);
+        let poisson = scatter(size, 100, Distribution::Poisson, &mut rng);
+
+        for points in [&uniform, &gaussian, &poisson] {
+            for p in points.iter() {
+                assert!((0.0..size.x).contains(&p.x) && (0.0..size.y).contains(&p.y));
+            }
+        }
+        assert_eq!(gaussian.len(), 500);
+        assert!(mean_distance(&gaussian) < mean_distance(&uniform) * 0.7);
+        // Spread out further than the same amount of uniform points.
+        assert_eq!(poisson.len(), 100);
+        assert!(closest(&poisson) > closest(&uniform[..100]) * 2.0);
+    }
+}
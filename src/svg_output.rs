@@ -0,0 +1,728 @@
+use clap::Args;
+use itertools::Itertools;
+use rand::Rng;
+use svg::{
+    node::element::{path::Data, Group, Path},
+    Document,
+};
+
+use crate::{
+    color::Color,
+    layer::Layer,
+    vec::{consts, vec2, Float, Vec2},
+};
+
+/// Settings for the svg output.
+#[derive(Args, Debug, Clone)]
+pub struct SvgSettings {
+    /// Color of the drawn lines, as `#rrggbb`, `rgb(r, g, b)` or a name like `red`.
+    #[arg(long, default_value = "black")]
+    pub stroke: Color,
+    /// Gives every line a slightly different color around `--stroke`, for previews that
+    /// look hand colored. The hue changes by up to this fraction of the color wheel,
+    /// the saturation and lightness by up to this much.
+    #[arg(long)]
+    pub color_jitter: Option<Float>,
+    /// Width of the drawn lines.
+    #[arg(long, default_value_t = 0.1)]
+    pub stroke_width: Float,
+    /// Draws the lines semi-transparent, with this opacity (0 to 1), so areas where
+    /// lines overlap, also from different layers, look darker. Shows the density in previews.
+    #[arg(long)]
+    pub stroke_opacity: Option<Float>,
+    /// Makes lines thinner the further they are from the center of the canvas.
+    /// At the corners of the canvas the stroke width is reduced by this fraction (0 to 1).
+    #[arg(long)]
+    pub radial_fade: Option<Float>,
+    /// Width of the drawn lines as a fraction of the shortest side of the canvas,
+    /// instead of `--stroke-width`. Keeps the look the same across canvas sizes.
+    #[arg(long)]
+    pub relative_stroke: Option<Float>,
+    /// Makes lines thicker where they turn sharply. Segments next to a full reversal
+    /// get this many times the stroke width extra, straight segments none.
+    #[arg(long)]
+    pub curvature_weight: Option<Float>,
+    /// Replaces runs of points that lie on a circle, within this distance, by a single arc.
+    /// Gives smaller files and smoother curves.
+    #[arg(long)]
+    pub fit_arcs: Option<Float>,
+    /// Colors the lines from blue to red in the order they are plotted, over all layers,
+    /// to check the order before plotting. Replaces `--stroke` and `--color-jitter`.
+    #[arg(long)]
+    pub order_preview: bool,
+    /// Colors the lines from red for the shortest to blue for the longest, over all layers,
+    /// to spot tiny stray lines. Replaces `--stroke` and `--color-jitter`.
+    #[arg(long)]
+    pub color_by_length: bool,
+    /// Draws the moves with the pen up, from the end of every line to the start of the next,
+    /// as dashed lines in a separate `travel` layer. That layer is skipped when plotting,
+    /// using the AxiDraw convention of layer labels starting with `%`.
+    #[arg(long)]
+    pub show_travel: bool,
+    /// Makes the plotter pause before every layer but the first, to change pens.
+    /// Uses the AxiDraw convention of layer labels starting with `!`.
+    #[arg(long)]
+    pub pause_between_layers: bool,
+}
+
+impl Default for SvgSettings {
+    fn default() -> Self {
+        Self {
+            stroke: Color::BLACK,
+            color_jitter: None,
+            stroke_width: 0.1,
+            stroke_opacity: None,
+            radial_fade: None,
+            relative_stroke: None,
+            curvature_weight: None,
+            fit_arcs: None,
+            order_preview: false,
+            color_by_length: false,
+            show_travel: false,
+            pause_between_layers: false,
+        }
+    }
+}
+
+impl SvgSettings {
+    /// The command line arguments that reproduce these settings.
+    pub fn arguments(&self) -> Vec<String> {
+        let mut arguments = vec![
+            format!("--stroke={}", self.stroke),
+            format!("--stroke-width={}", self.stroke_width),
+        ];
+        if let Some(amount) = self.color_jitter {
+            arguments.push(format!("--color-jitter={amount}"));
+        }
+        if let Some(opacity) = self.stroke_opacity {
+            arguments.push(format!("--stroke-opacity={opacity}"));
+        }
+        if let Some(fade) = self.radial_fade {
+            arguments.push(format!("--radial-fade={fade}"));
+        }
+        if let Some(fraction) = self.relative_stroke {
+            arguments.push(format!("--relative-stroke={fraction}"));
+        }
+        if let Some(weight) = self.curvature_weight {
+            arguments.push(format!("--curvature-weight={weight}"));
+        }
+        if let Some(tolerance) = self.fit_arcs {
+            arguments.push(format!("--fit-arcs={tolerance}"));
+        }
+        if self.order_preview {
+            arguments.push("--order-preview".to_string());
+        }
+        if self.color_by_length {
+            arguments.push("--color-by-length".to_string());
+        }
+        if self.show_travel {
+            arguments.push("--show-travel".to_string());
+        }
+        if self.pause_between_layers {
+            arguments.push("--pause-between-layers".to_string());
+        }
+        arguments
+    }
+}
+
+/// Color of the first plotted line for `--order-preview`.
+const ORDER_START: Color = Color {
+    r: 0.0,
+    g: 0.0,
+    b: 1.0,
+};
+/// Color of the last plotted line for `--order-preview`.
+const ORDER_END: Color = Color {
+    r: 1.0,
+    g: 0.0,
+    b: 0.0,
+};
+
+/// Color of the shortest line for `--color-by-length`.
+const SHORTEST_COLOR: Color = Color {
+    r: 1.0,
+    g: 0.0,
+    b: 0.0,
+};
+/// Color of the longest line for `--color-by-length`.
+const LONGEST_COLOR: Color = Color {
+    r: 0.0,
+    g: 0.0,
+    b: 1.0,
+};
+
+/// Color of the pen up moves for `--show-travel`.
+const TRAVEL_COLOR: Color = Color {
+    r: 0.6,
+    g: 0.6,
+    b: 0.6,
+};
+
+/// Every layer becomes an inkscape layer, which most plotter software understands.
+/// The random generator is only used for `--color-jitter`.
+pub fn layers_to_document(
+    layers: &[Layer],
+    size: Vec2,
+    settings: &SvgSettings,
+    rng: &mut impl Rng,
+) -> Document {
+    let settings = &SvgSettings {
+        stroke_width: match settings.relative_stroke {
+            Some(fraction) => fraction * size.x.min(size.y),
+            None => settings.stroke_width,
+        },
+        ..settings.clone()
+    };
+
+    let mut document = Document::new()
+        .set(
+            "xmlns:inkscape",
+            "http://www.inkscape.org/namespaces/inkscape",
+        )
+        .set("viewBox", (0.0, 0.0, size.x, size.y));
+
+    let line_count: usize = layers.iter().map(|layer| layer.paths.len()).sum();
+    let mut plotted = 0;
+    let (shortest, longest) = layers
+        .iter()
+        .flat_map(|layer| &layer.paths)
+        .map(|path| path_length(path))
+        .minmax()
+        .into_option()
+        .unwrap_or((0.0, 0.0));
+
+    for (index, layer) in layers.iter().enumerate() {
+        let label = if settings.pause_between_layers && index > 0 {
+            format!("!{}", layer.name)
+        } else {
+            layer.name.clone()
+        };
+        let mut group = Group::new()
+            .set("id", layer.name.as_str())
+            .set("inkscape:groupmode", "layer")
+            .set("inkscape:label", label);
+
+        for points in &layer.paths {
+            let color = if settings.order_preview {
+                let t = plotted as Float / line_count.saturating_sub(1).max(1) as Float;
+                ORDER_START.lerp(ORDER_END, t)
+            } else if settings.color_by_length {
+                let t = match longest - shortest {
+                    range if range > 0.0 => (path_length(points) - shortest) / range,
+                    _ => 0.0,
+                };
+                SHORTEST_COLOR.lerp(LONGEST_COLOR, t)
+            } else {
+                match settings.color_jitter {
+                    Some(amount) => settings.stroke.jitter(amount, rng),
+                    None => settings.stroke,
+                }
+            };
+            plotted += 1;
+
+            if settings.radial_fade.is_some() || settings.curvature_weight.is_some() {
+                // Every segment has its own width, so it needs to be its own path.
+                let widths = segment_widths(points, size, settings);
+                for (segment, width) in points.windows(2).zip(widths) {
+                    group = group.add(
+                        points_to_path(segment, color, width, settings.stroke_opacity, None)
+                            .set("stroke-linecap", "round"),
+                    );
+                }
+            } else {
+                group = group.add(points_to_path(
+                    points,
+                    color,
+                    settings.stroke_width,
+                    settings.stroke_opacity,
+                    settings.fit_arcs,
+                ));
+            }
+        }
+
+        document = document.add(group);
+    }
+
+    if settings.show_travel {
+        let mut group = Group::new()
+            .set("id", "travel")
+            .set("inkscape:groupmode", "layer")
+            .set("inkscape:label", "%travel");
+        let dash = settings.stroke_width * 4.0;
+        for travel in travel_moves(layers) {
+            group = group.add(
+                points_to_path(
+                    &travel,
+                    TRAVEL_COLOR,
+                    settings.stroke_width,
+                    settings.stroke_opacity,
+                    None,
+                )
+                .set("stroke-dasharray", format!("{dash} {dash}")),
+            );
+        }
+        document = document.add(group);
+    }
+
+    document
+}
+
+/// Length of the line through all the points.
+fn path_length(points: &[Vec2]) -> Float {
+    points
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).len())
+        .sum()
+}
+
+/// The moves with the pen up, in plotting order, from the end of every line to the start
+/// of the next one. Also between the last line of a layer and the first of the next.
+fn travel_moves(layers: &[Layer]) -> Vec<Vec<Vec2>> {
+    layers
+        .iter()
+        .flat_map(|layer| &layer.paths)
+        .filter(|path| !path.is_empty())
+        .tuple_windows()
+        .map(|(from, to)| vec![*from.last().unwrap(), to[0]])
+        .collect()
+}
+
+/// Stroke width of every segment of the path, for the settings that vary it.
+fn segment_widths(points: &[Vec2], size: Vec2, settings: &SvgSettings) -> Vec<Float> {
+    points
+        .windows(2)
+        .enumerate()
+        .map(|(index, segment)| {
+            let mut width = settings.stroke_width;
+
+            if let Some(fade) = settings.radial_fade {
+                width = radial_fade_width(segment[0], segment[1], size, width, fade);
+            }
+
+            if let Some(weight) = settings.curvature_weight {
+                // The sharpest of the corners at both ends of the segment.
+                let before = index.checked_sub(1).map(|i| points[i]);
+                let after = points.get(index + 2).copied();
+                let turn = Float::max(
+                    before.map_or(0.0, |a| turn_angle(a, segment[0], segment[1])),
+                    after.map_or(0.0, |c| turn_angle(segment[0], segment[1], c)),
+                );
+                width *= 1.0 + weight * turn / consts::PI;
+            }
+
+            width
+        })
+        .collect()
+}
+
+/// How far the direction changes at `b`, in radians. 0 for a straight line, pi for
+/// a full reversal.
+fn turn_angle(a: Vec2, b: Vec2, c: Vec2) -> Float {
+    if a == b || b == c {
+        return 0.0;
+    }
+    let cosine = (b - a).normalize().dot((c - b).normalize());
+    cosine.clamp(-1.0, 1.0).acos()
+}
+
+/// Stroke width of the segment from `a` to `b`, based on how far its middle
+/// is from the center of the canvas.
+fn radial_fade_width(a: Vec2, b: Vec2, size: Vec2, stroke_width: Float, fade: Float) -> Float {
+    let center = size / 2.0;
+    let distance = ((a + b) / 2.0 - center).len();
+    let max_distance = center.len();
+
+    let fraction = (distance / max_distance).min(1.0);
+    stroke_width * (1.0 - fade.clamp(0.0, 1.0) * fraction)
+}
+
+fn points_to_path(
+    points: &[Vec2],
+    color: Color,
+    stroke_width: Float,
+    opacity: Option<Float>,
+    fit_arcs: Option<Float>,
+) -> Path {
+    let mut data = Data::new();
+
+    if let Some(&first) = points.first() {
+        data = data.move_to((first.x, first.y));
+    }
+
+    let pieces = match fit_arcs {
+        Some(tolerance) => fit_arc_pieces(points, tolerance),
+        None => points.iter().skip(1).map(|&p| Piece::Line(p)).collect(),
+    };
+    for piece in pieces {
+        data = match piece {
+            Piece::Line(end) => data.line_to((end.x, end.y)),
+            Piece::Arc {
+                radius,
+                large_arc,
+                sweep,
+                end,
+            } => data.elliptical_arc_to((
+                radius,
+                radius,
+                0,
+                large_arc as u8,
+                sweep as u8,
+                end.x,
+                end.y,
+            )),
+        };
+    }
+
+    let path = Path::new()
+        .set("fill", "none")
+        .set("stroke", color.to_string())
+        .set("stroke-width", stroke_width);
+    let path = match opacity {
+        Some(opacity) => path.set("stroke-opacity", opacity.clamp(0.0, 1.0)),
+        None => path,
+    };
+    path.set("d", data)
+}
+
+/// A part of a path, from the end of the previous part to `end`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Piece {
+    Line(Vec2),
+    /// Circular arc, with the flags of the svg arc command.
+    /// `sweep` is set when the angle increases, which is clockwise on screen.
+    Arc {
+        radius: Float,
+        large_arc: bool,
+        sweep: bool,
+        end: Vec2,
+    },
+}
+
+/// Splits the path into lines and arcs. Starting from every point, the longest run of
+/// at least 4 points that fits an arc within `tolerance` becomes that arc.
+fn fit_arc_pieces(points: &[Vec2], tolerance: Float) -> Vec<Piece> {
+    // Repeated points would break up the arcs.
+    let points = points.iter().copied().dedup().collect_vec();
+    let mut pieces = vec![];
+    let mut start = 0;
+
+    while start + 1 < points.len() {
+        let mut longest = None;
+        for end in (start + 3)..points.len() {
+            match fit_arc(&points[start..=end], tolerance) {
+                Some(arc) => longest = Some((end, arc)),
+                None => break,
+            }
+        }
+
+        match longest {
+            Some((end, arc)) => {
+                pieces.push(arc);
+                start = end;
+            }
+            None => {
+                pieces.push(Piece::Line(points[start + 1]));
+                start += 1;
+            }
+        }
+    }
+
+    pieces
+}
+
+/// The arc through the first, middle and last point, if all points and the middles of
+/// all segments are within `tolerance` of it, and the points go around in one direction.
+fn fit_arc(points: &[Vec2], tolerance: Float) -> Option<Piece> {
+    let start = points[0];
+    let end = *points.last()?;
+    let center = circumcenter(start, points[points.len() / 2], end)?;
+    let radius = (start - center).len();
+    let off_circle = |p: Vec2| ((p - center).len() - radius).abs() > tolerance;
+
+    let mut total_angle: Float = 0.0;
+    for pair in points.windows(2) {
+        let (a, b) = (pair[0] - center, pair[1] - center);
+        if off_circle(pair[1]) || off_circle((pair[0] + pair[1]) / 2.0) {
+            return None;
+        }
+        let angle = (a.x * b.y - a.y * b.x).atan2(a.dot(b));
+        if angle == 0.0 || (total_angle != 0.0 && angle.signum() != total_angle.signum()) {
+            return None;
+        }
+        total_angle += angle;
+    }
+
+    // An arc can't end where it starts, a full circle needs at least two.
+    if total_angle.abs() >= consts::TAU * 0.99 {
+        return None;
+    }
+
+    Some(Piece::Arc {
+        radius,
+        large_arc: total_angle.abs() > consts::PI,
+        sweep: total_angle > 0.0,
+        end,
+    })
+}
+
+/// Center of the circle through the three points, or `None` if they are on a line.
+fn circumcenter(a: Vec2, b: Vec2, c: Vec2) -> Option<Vec2> {
+    // Relative to `a`, to keep the numbers small.
+    let (b, c) = (b - a, c - a);
+    let determinant = 2.0 * (b.x * c.y - b.y * c.x);
+    if determinant.abs() < 1e-9 {
+        return None;
+    }
+
+    let (b_squared, c_squared) = (b.dot(b), c.dot(c));
+    let center = vec2(
+        c.y * b_squared - b.y * c_squared,
+        b.x * c_squared - c.x * b_squared,
+    ) / determinant;
+    Some(a + center)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn layers_are_serialized_in_requested_order() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let layers = vec![
+            Layer::new("dark", vec![vec![vec2(0.0, 0.0), vec2(1.0, 1.0)]]),
+            Layer::new("light", vec![vec![vec2(1.0, 0.0), vec2(0.0, 1.0)]]),
+        ];
+        let layers = layer::order_layers(layers, &["light".to_string()]).unwrap();
+
+        let svg = layers_to_document(&layers, vec2(1.0, 1.0), &SvgSettings::default(), &mut rng)
+            .to_string();
+
+        let light = svg.find(r#"id="light""#).unwrap();
+        let dark = svg.find(r#"id="dark""#).unwrap();
+        assert!(light < dark);
+    }
+
+    #[test]
+    fn relative_stroke_scales_with_the_canvas() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let layers = vec![Layer::new("a", vec![vec![vec2(0.0, 0.0), vec2(1.0, 1.0)]])];
+        let settings = SvgSettings {
+            relative_stroke: Some(0.01),
+            ..SvgSettings::default()
+        };
+
+        let small =
+            layers_to_document(&layers, vec2(100.0, 200.0), &settings, &mut rng).to_string();
+        let large =
+            layers_to_document(&layers, vec2(200.0, 400.0), &settings, &mut rng).to_string();
+
+        assert!(small.contains(r#"stroke-width="1""#));
+        assert!(large.contains(r#"stroke-width="2""#));
+    }
+
+    #[test]
+    fn pause_before_every_layer_but_the_first() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let layers = ["a", "b", "c"]
+            .map(|name| Layer::new(name, vec![vec![vec2(0.0, 0.0), vec2(1.0, 1.0)]]))
+            .to_vec();
+        let settings = SvgSettings {
+            pause_between_layers: true,
+            ..SvgSettings::default()
+        };
+
+        let svg = layers_to_document(&layers, vec2(1.0, 1.0), &settings, &mut rng).to_string();
+
+        assert_eq!(svg.matches(r#"inkscape:label="!"#).count(), 2);
+        assert!(svg.contains(r#"inkscape:label="a""#));
+        assert!(svg.contains(r#"inkscape:label="!b""#));
+        assert!(svg.contains(r#"inkscape:label="!c""#));
+    }
+
+    #[test]
+    fn stroke_opacity_is_set_on_every_path() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let layers = ["a", "b"]
+            .map(|name| Layer::new(name, vec![vec![vec2(0.0, 0.0), vec2(1.0, 1.0)]]))
+            .to_vec();
+        let settings = SvgSettings {
+            stroke_opacity: Some(0.4),
+            ..SvgSettings::default()
+        };
+
+        let svg = layers_to_document(&layers, vec2(1.0, 1.0), &settings, &mut rng).to_string();
+        assert_eq!(svg.matches(r#"stroke-opacity="0.4""#).count(), 2);
+
+        let svg = layers_to_document(&layers, vec2(1.0, 1.0), &SvgSettings::default(), &mut rng)
+            .to_string();
+        assert!(!svg.contains("stroke-opacity"));
+    }
+
+    #[test]
+    fn order_preview_goes_from_start_to_end_color() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let line = vec![vec2(0.0, 0.0), vec2(1.0, 1.0)];
+        let layers = vec![
+            Layer::new("a", vec![line.clone(), line.clone()]),
+            Layer::new("b", vec![line.clone(), line]),
+        ];
+        let settings = SvgSettings {
+            order_preview: true,
+            ..SvgSettings::default()
+        };
+
+        let svg = layers_to_document(&layers, vec2(1.0, 1.0), &settings, &mut rng).to_string();
+
+        let colors: Vec<&str> = svg
+            .split(r#"stroke=""#)
+            .skip(1)
+            .map(|rest| &rest[..7])
+            .collect();
+        assert_eq!(colors.len(), 4);
+        assert_eq!(colors[0], ORDER_START.to_string());
+        assert_eq!(colors[3], ORDER_END.to_string());
+        assert_eq!(
+            colors[1],
+            ORDER_START.lerp(ORDER_END, 1.0 / 3.0).to_string()
+        );
+    }
+
+    #[test]
+    fn color_by_length_goes_from_shortest_to_longest() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let layers = vec![
+            Layer::new("a", vec![vec![vec2(0.0, 0.0), vec2(2.0, 0.0)]]),
+            Layer::new(
+                "b",
+                vec![
+                    vec![vec2(0.0, 0.0), vec2(5.0, 0.0), vec2(5.0, 5.0)],
+                    vec![vec2(0.0, 1.0), vec2(1.0, 1.0)],
+                ],
+            ),
+        ];
+        let settings = SvgSettings {
+            color_by_length: true,
+            ..SvgSettings::default()
+        };
+
+        let svg = layers_to_document(&layers, vec2(10.0, 10.0), &settings, &mut rng).to_string();
+
+        let colors: Vec<&str> = svg
+            .split(r#"stroke=""#)
+            .skip(1)
+            .map(|rest| &rest[..7])
+            .collect();
+        assert_eq!(colors.len(), 3);
+        assert_eq!(colors[1], LONGEST_COLOR.to_string());
+        assert_eq!(colors[2], SHORTEST_COLOR.to_string());
+        assert_eq!(
+            colors[0],
+            SHORTEST_COLOR.lerp(LONGEST_COLOR, 1.0 / 9.0).to_string()
+        );
+    }
+
+    #[test]
+    fn travel_connects_every_line_to_the_next() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let layers = vec![
+            Layer::new(
+                "a",
+                vec![
+                    vec![vec2(0.0, 0.0), vec2(1.0, 0.0)],
+                    vec![vec2(2.0, 0.0), vec2(3.0, 1.0)],
+                ],
+            ),
+            Layer::new("b", vec![vec![vec2(0.0, 2.0), vec2(1.0, 3.0)]]),
+        ];
+
+        assert_eq!(
+            travel_moves(&layers),
+            vec![
+                vec![vec2(1.0, 0.0), vec2(2.0, 0.0)],
+                vec![vec2(3.0, 1.0), vec2(0.0, 2.0)],
+            ]
+        );
+
+        let settings = SvgSettings {
+            show_travel: true,
+            ..SvgSettings::default()
+        };
+        let svg = layers_to_document(&layers, vec2(3.0, 3.0), &settings, &mut rng).to_string();
+        assert!(svg.contains(r#"inkscape:label="%travel""#));
+        assert_eq!(svg.matches("stroke-dasharray").count(), 2);
+    }
+
+    #[test]
+    fn radial_fade_central_segment_is_thicker() {
+        let size = vec2(100.0, 100.0);
+
+        let central = radial_fade_width(vec2(49.0, 50.0), vec2(51.0, 50.0), size, 0.1, 0.8);
+        let edge = radial_fade_width(vec2(0.0, 0.0), vec2(2.0, 0.0), size, 0.1, 0.8);
+
+        assert!(central > edge);
+    }
+
+    #[test]
+    fn curvature_weight_sharp_turn_is_thicker() {
+        let settings = SvgSettings {
+            curvature_weight: Some(2.0),
+            ..SvgSettings::default()
+        };
+        let points = [
+            vec2(0.0, 0.0),
+            vec2(1.0, 0.0),
+            vec2(2.0, 0.0),
+            vec2(3.0, 0.0),
+            vec2(3.0, 1.0),
+        ];
+
+        let widths = segment_widths(&points, vec2(10.0, 10.0), &settings);
+
+        assert_eq!(widths.len(), 4);
+        // The straight start is unchanged, the segments at the corner get thicker.
+        assert_eq!(widths[0], 0.1);
+        assert!(widths[2] > widths[0]);
+        assert!(widths[3] > widths[0]);
+        assert!((widths[3] - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn circle_collapses_to_arcs() {
+        let circle: Vec<Vec2> = (0..=360)
+            .map(|i| vec2(50.0, 50.0) + Vec2::from_angle((i as Float).to_radians()) * 20.0)
+            .collect();
+
+        let pieces = fit_arc_pieces(&circle, 0.01);
+
+        assert!(pieces.len() <= 3);
+        for piece in &pieces {
+            let Piece::Arc { radius, sweep, .. } = piece else {
+                panic!("Expected only arcs, got {pieces:?}");
+            };
+            assert!((radius - 20.0).abs() < 0.01);
+            assert!(sweep);
+        }
+        let Some(Piece::Arc { end, .. }) = pieces.last() else {
+            unreachable!()
+        };
+        assert!((*end - circle[0]).len() < 1e-3);
+
+        // Sharp corners are not rounded off.
+        let corners = [
+            vec2(0.0, 0.0),
+            vec2(0.0, 1.0),
+            vec2(1.0, 1.0),
+            vec2(1.0, 0.0),
+        ];
+        assert_eq!(
+            fit_arc_pieces(&corners, 0.01),
+            corners[1..]
+                .iter()
+                .map(|&p| Piece::Line(p))
+                .collect::<Vec<_>>()
+        );
+    }
+}
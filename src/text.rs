@@ -0,0 +1,131 @@
+use crate::vec::{vec2, Float, Vec2};
+
+/// Distance from the start of one character to the start of the next, as a fraction
+/// of the height.
+const ADVANCE: Float = 0.75;
+
+/// The lines of a character, on a grid 2 wide and 4 high, with y pointing down.
+/// Lowercase letters are drawn as uppercase. Characters without lines, like a space,
+/// leave an empty spot.
+fn glyph(character: char) -> &'static [&'static [(u8, u8)]] {
+    const O: &[(u8, u8)] = &[(1, 0), (0, 1), (0, 3), (1, 4), (2, 3), (2, 1), (1, 0)];
+    const P: &[(u8, u8)] = &[(0, 4), (0, 0), (1, 0), (2, 1), (1, 2), (0, 2)];
+
+    match character.to_ascii_uppercase() {
+        'A' => &[&[(0, 4), (0, 1), (1, 0), (2, 1), (2, 4)], &[(0, 2), (2, 2)]],
+        'B' => &[&[
+            (0, 2),
+            (1, 2),
+            (2, 3),
+            (1, 4),
+            (0, 4),
+            (0, 0),
+            (1, 0),
+            (2, 1),
+            (1, 2),
+        ]],
+        'C' => &[&[(2, 1), (1, 0), (0, 1), (0, 3), (1, 4), (2, 3)]],
+        'D' => &[&[(0, 0), (0, 4), (1, 4), (2, 3), (2, 1), (1, 0), (0, 0)]],
+        'E' => &[&[(2, 0), (0, 0), (0, 4), (2, 4)], &[(0, 2), (1, 2)]],
+        'F' => &[&[(2, 0), (0, 0), (0, 4)], &[(0, 2), (1, 2)]],
+        'G' => &[&[
+            (2, 1),
+            (1, 0),
+            (0, 1),
+            (0, 3),
+            (1, 4),
+            (2, 4),
+            (2, 2),
+            (1, 2),
+        ]],
+        'H' => &[&[(0, 0), (0, 4)], &[(2, 0), (2, 4)], &[(0, 2), (2, 2)]],
+        'I' => &[&[(0, 0), (2, 0)], &[(1, 0), (1, 4)], &[(0, 4), (2, 4)]],
+        'J' => &[&[(2, 0), (2, 3), (1, 4), (0, 3)]],
+        'K' => &[&[(0, 0), (0, 4)], &[(2, 0), (0, 2), (2, 4)]],
+        'L' => &[&[(0, 0), (0, 4), (2, 4)]],
+        'M' => &[&[(0, 4), (0, 0), (1, 2), (2, 0), (2, 4)]],
+        'N' => &[&[(0, 4), (0, 0), (2, 4), (2, 0)]],
+        'O' => &[O],
+        'P' => &[P],
+        'Q' => &[O, &[(1, 3), (2, 4)]],
+        'R' => &[P, &[(1, 2), (2, 4)]],
+        'S' => &[&[(2, 1), (1, 0), (0, 1), (2, 3), (1, 4), (0, 3)]],
+        'T' => &[&[(0, 0), (2, 0)], &[(1, 0), (1, 4)]],
+        'U' => &[&[(0, 0), (0, 3), (1, 4), (2, 3), (2, 0)]],
+        'V' => &[&[(0, 0), (1, 4), (2, 0)]],
+        'W' => &[&[(0, 0), (0, 4), (1, 2), (2, 4), (2, 0)]],
+        'X' => &[&[(0, 0), (2, 4)], &[(2, 0), (0, 4)]],
+        'Y' => &[&[(0, 0), (1, 2), (2, 0)], &[(1, 2), (1, 4)]],
+        'Z' => &[&[(0, 0), (2, 0), (0, 4), (2, 4)]],
+        '0' => &[O, &[(0, 3), (2, 1)]],
+        '1' => &[&[(0, 1), (1, 0), (1, 4)], &[(0, 4), (2, 4)]],
+        '2' => &[&[(0, 1), (1, 0), (2, 1), (0, 4), (2, 4)]],
+        '3' => &[&[(0, 0), (2, 0), (1, 2), (2, 3), (1, 4), (0, 3)]],
+        '4' => &[&[(2, 4), (2, 0), (0, 3), (2, 3)]],
+        '5' => &[&[(2, 0), (0, 0), (0, 2), (1, 2), (2, 3), (1, 4), (0, 4)]],
+        '6' => &[&[
+            (2, 0),
+            (1, 0),
+            (0, 1),
+            (0, 3),
+            (1, 4),
+            (2, 3),
+            (1, 2),
+            (0, 2),
+        ]],
+        '7' => &[&[(0, 0), (2, 0), (1, 4)]],
+        '8' => &[&[(1, 0), (0, 1), (2, 3), (1, 4), (0, 3), (2, 1), (1, 0)]],
+        '9' => &[&[
+            (2, 2),
+            (1, 2),
+            (0, 1),
+            (1, 0),
+            (2, 1),
+            (2, 3),
+            (1, 4),
+            (0, 4),
+        ]],
+        '-' => &[&[(0, 2), (2, 2)]],
+        '_' => &[&[(0, 4), (2, 4)]],
+        _ => &[],
+    }
+}
+
+/// Single stroke text, that a plotter can draw without filling anything. Letters, digits,
+/// `-` and `_` are supported, other characters are left out.
+///
+/// The text starts at `position`, the top left corner of the first character, and the
+/// characters are `height` high.
+pub fn text(text: &str, position: Vec2, height: Float) -> Vec<Vec<Vec2>> {
+    let scale = height / 4.0;
+    text.chars()
+        .enumerate()
+        .flat_map(|(index, character)| {
+            let origin = position + vec2(index as Float * ADVANCE * height, 0.0);
+            glyph(character).iter().map(move |line| {
+                line.iter()
+                    .map(|&(x, y)| origin + vec2(x as Float, y as Float) * scale)
+                    .collect()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paths::bounding_box;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn characters_are_side_by_side() {
+        let lines = text("Hi-2", vec2(10.0, 5.0), 4.0);
+
+        // Three lines for the H and the I, one for the dash and the 2.
+        assert_eq!(lines.len(), 8);
+        let (min, max) = bounding_box(&lines).unwrap();
+        assert_eq!(min, vec2(10.0, 5.0));
+        // The last character starts three advances in, and is half as wide as it is high.
+        assert_eq!(max, vec2(10.0 + 3.0 * 3.0 + 2.0, 9.0));
+    }
+}
@@ -1,25 +1,95 @@
-use std::ops;
+use std::{ops, str::FromStr};
 
-pub const fn vec2(x: f32, y: f32) -> Vec2 {
+/// Floating point type used for all geometry.
+/// The `high-precision` feature switches it to `f64`, for long running
+/// calculations where `f32` accumulates too much error.
+#[cfg(not(feature = "high-precision"))]
+pub type Float = f32;
+#[cfg(feature = "high-precision")]
+pub type Float = f64;
+
+#[cfg(not(feature = "high-precision"))]
+pub use std::f32::consts;
+#[cfg(feature = "high-precision")]
+pub use std::f64::consts;
+
+pub const fn vec2(x: Float, y: Float) -> Vec2 {
     Vec2 { x, y }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vec2 {
-    pub x: f32,
-    pub y: f32,
+    pub x: Float,
+    pub y: Float,
 }
 
 impl Vec2 {
     pub const ZERO: Vec2 = vec2(0.0, 0.0);
 
+    /// Unit vector pointing at `angle` radians from the positive x-axis.
+    pub fn from_angle(angle: Float) -> Vec2 {
+        let (sin, cos) = angle.sin_cos();
+        vec2(cos, sin)
+    }
+
     pub fn normalize(&self) -> Vec2 {
         *self / self.len()
     }
 
-    pub fn len(&self) -> f32 {
+    pub fn len(&self) -> Float {
         (self.x.powi(2) + self.y.powi(2)).sqrt()
     }
+
+    pub fn dot(&self, other: Vec2) -> Float {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Turned a quarter counter-clockwise (in a y-up coordinate system).
+    pub fn perp(&self) -> Vec2 {
+        vec2(-self.y, self.x)
+    }
+
+    /// Rotates counter-clockwise by `angle` radians (in a y-up coordinate system).
+    pub fn rotate(&self, angle: Float) -> Vec2 {
+        let (sin, cos) = angle.sin_cos();
+        vec2(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+}
+
+/// Parses `x,y`, for use in command line arguments.
+impl FromStr for Vec2 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s
+            .split_once(',')
+            .ok_or_else(|| format!("Expected `x,y`, got `{s}`"))?;
+
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<Float>()
+                .map_err(|e| format!("Invalid coordinate `{value}`: {e}"))
+        };
+
+        Ok(vec2(parse(x)?, parse(y)?))
+    }
+}
+
+/// Serialized as `[x, y]`, to keep long lists of points compact.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Vec2 {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        [self.x, self.y].serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Vec2 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [x, y] = <[Float; 2]>::deserialize(deserializer)?;
+        Ok(vec2(x, y))
+    }
 }
 
 impl ops::Add for Vec2 {
@@ -55,10 +125,10 @@ impl ops::Neg for Vec2 {
     }
 }
 
-impl ops::Div<f32> for Vec2 {
+impl ops::Div<Float> for Vec2 {
     type Output = Self;
 
-    fn div(self, rhs: f32) -> Self::Output {
+    fn div(self, rhs: Float) -> Self::Output {
         Vec2 {
             x: self.x / rhs,
             y: self.y / rhs,
@@ -66,13 +136,47 @@ impl ops::Div<f32> for Vec2 {
     }
 }
 
-impl ops::Mul<f32> for Vec2 {
+impl ops::Mul<Float> for Vec2 {
     type Output = Self;
 
-    fn mul(self, rhs: f32) -> Self::Output {
+    fn mul(self, rhs: Float) -> Self::Output {
         Vec2 {
             x: self.x * rhs,
             y: self.y * rhs,
         }
     }
 }
+
+#[cfg(all(test, feature = "high-precision"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_additions_stay_accurate() {
+        // Each step is far below the precision of an f32 at 1.0,
+        // so with f32 the sum would never move.
+        let step = vec2(1e-9, 0.0);
+        let mut point = vec2(1.0, 0.0);
+        for _ in 0..1000 {
+            point = point + step;
+        }
+
+        assert!((point.x - 1.000001).abs() < 1e-12);
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn serde_round_trip_as_array() {
+        let points = vec![vec2(1.5, -2.0), vec2(0.0, 3.25)];
+
+        let json = serde_json::to_string(&points).unwrap();
+        assert_eq!(json, "[[1.5,-2.0],[0.0,3.25]]");
+        assert_eq!(serde_json::from_str::<Vec<Vec2>>(&json).unwrap(), points);
+        assert!(serde_json::from_str::<Vec2>("[1.0]").is_err());
+    }
+}